@@ -2,171 +2,305 @@
 //! [`inquire`](https://docs.rs/inquire/0.3.0/inquire/) and
 //! [`termion`](https://docs.rs/termion/1.5.6/termion/).
 
+mod settings;
+
 use inquire::{
     ui::{RenderConfig, Styled},
     validator::Validation,
-    Text,
+    Select, Text,
+};
+use settings::Settings;
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use wordle::{
+    analysis::Constraints,
+    daily::epoch_day,
+    normalize::normalize_guess,
+    prelude::*,
+    render::{
+        ansi::{render_grid, render_keyboard},
+        svg::{self, SvgOptions},
+        text::{describe_guess_with, describe_keyboard_with},
+    },
+    replay::Recorder,
+    session::{BoardSummary, SessionStats},
+    validator::GuessValidator,
+    word_list::WordLists,
 };
-use std::collections::HashMap;
-use termion::style;
-use wordle::prelude::*;
 
-/// Return a string with the given letter and the appropriate colour for its position type.
+/// Create a render config for `inquire`, for the given guess number out of [`MAX_GUESSES`],
+/// starting from 1.
 ///
-/// The colours are based on the original Wordle game, and implemented using Termion.
-///
-/// Ideally, the word should also be printed in bold. This is left up to the caller, as this
-/// function only handles individual letters. Additionally, this function DOES NOT RESET the
-/// terminal colours at the end of the letter. Each colour overrides the last, and the colours
-/// only need to be reset at the end of the word.
-fn pretty_print_letter_with_position(letter: char, position: Option<Position>) -> String {
-    use termion::color;
-
-    let mut string: String = match position {
-        None => format!("{}", color::Fg(color::White)),
-        Some(position) => match position {
-            Position::NotInWord => {
-                format!("{}", color::Fg(color::Black))
-            }
-            Position::WrongPosition => {
-                format!("{}", color::Fg(color::Yellow))
-            }
-            Position::Correct => {
-                format!("{}", color::Fg(color::Green))
-            }
-        },
-    };
+/// `inquire`'s render config needs a `&'static str` as the prompt string, so we leak the prompt.
+/// There's one prompt per guess, so this leaks at most [`MAX_GUESSES`] short strings.
+fn create_render_config(guess_number: usize) -> RenderConfig {
+    use inquire::ui::Color;
+
+    let prompt: &'static str =
+        Box::leak(format!("({guess_number}/{MAX_GUESSES}) >").into_boxed_str());
 
-    string.push(letter);
-    string
+    let prompt_prefix = Styled::new(prompt).with_fg(Color::LightGreen);
+    let answered_prompt_prefix = Styled::new(prompt).with_fg(Color::Black);
+
+    let mut config = RenderConfig::default_colored();
+    config.prompt_prefix = prompt_prefix;
+    config.answered_prompt_prefix = answered_prompt_prefix;
+
+    config
 }
 
-/// Return a string with the given letter and the appropriate colour for its position type.
+/// Complete the given input to the longest prefix shared by every word in the given word list
+/// that starts with it.
 ///
-/// See [`pretty_print_letter_with_position`].
-fn pretty_print_letter_struct(letter: Letter) -> String {
-    pretty_print_letter_with_position(letter.letter, Some(letter.position))
+/// This is used for tab completion in the prompt. If the input can't be extended, we return
+/// [`None`].
+fn complete_guess(word_list: &WordLists, input: &str) -> Option<String> {
+    let completions = word_list.complete(input);
+    let mut completions = completions.iter();
+    let first = completions.next()?;
+
+    let common_length = completions.fold(first.len(), |length, word| {
+        first
+            .bytes()
+            .zip(word.bytes())
+            .take(length)
+            .take_while(|(a, b)| a == b)
+            .count()
+    });
+
+    (common_length > input.len()).then(|| first[..common_length].to_string())
 }
 
-/// Print the player's guess word highlighted according to classic Wordle colours, indented by 7 spaces.
-///
-/// The identation is to align with the printed keyboard. See [`print_keyboard`].
-fn print_guess(letters: &Word) {
-    print!("       {}", style::Bold);
-    for letter in letters.map(pretty_print_letter_struct) {
-        print!("{}", letter);
+/// Get the value given with the flag `--<name>`, either as `--<name> <value>` or
+/// `--<name>=<value>`, if there is one.
+fn flag_value(name: &str) -> Option<String> {
+    let flag = format!("--{name}");
+    let prefix = format!("{flag}=");
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        } else if let Some(value) = arg.strip_prefix(&prefix) {
+            return Some(value.to_string());
+        }
     }
-    println!("{}", style::Reset);
+
+    None
 }
 
-/// Print the standard QWERTY keyboard with the letters highlighted as the best position they've
-/// seen in a previous guess.
-///
-/// See [`Game::keyboard`].
-fn print_keyboard(keyboard: &HashMap<char, Option<Position>>) {
-    // We're assuming a standard QWERTY keyboard for convenience
-    const ROW_1: [char; 10] = ['Q', 'W', 'E', 'R', 'T', 'Y', 'U', 'I', 'O', 'P'];
-    const ROW_2: [char; 9] = ['A', 'S', 'D', 'F', 'G', 'H', 'J', 'K', 'L'];
-    const ROW_3: [char; 7] = ['Z', 'X', 'C', 'V', 'B', 'N', 'M'];
-
-    macro_rules! print_row {
-        ( $x:ident ) => {
-            for letter in $x {
-                let position = keyboard
-                    .get(&letter)
-                    .expect("Game::keyboard should contain all Latin letters");
-                print!("{} ", pretty_print_letter_with_position(letter, *position));
-            }
-        };
+/// Write the board to the given path as an SVG image, with the letters hidden so that it can be
+/// shared without spoiling the answer.
+fn export_image(path: &str, guesses: &[Word]) {
+    let options = SvgOptions {
+        hide_letters: true,
+        ..SvgOptions::default()
+    };
+
+    match std::fs::write(path, svg::render_board(guesses, &options)) {
+        Ok(()) => println!("Saved the board to {path}"),
+        Err(error) => eprintln!("Couldn't save the board to {path}: {error}"),
     }
+}
 
-    print!("{}", style::Bold);
+/// Write the recorded input of the game to the given path, so that it can be attached to a bug
+/// report and replayed with [`Game::replay_script`].
+fn save_recording(path: &str, recorder: &Recorder) {
+    match std::fs::write(path, recorder.script().to_string()) {
+        Ok(()) => println!("Saved the recording to {path}"),
+        Err(error) => eprintln!("Couldn't save the recording to {path}: {error}"),
+    }
+}
 
-    print_row!(ROW_1);
-    println!();
+/// Add the finished game to the stats saved in `wordle/stats` next to the config file, and return
+/// the new stats.
+///
+/// If the stats can't be saved, we print an error but still return them.
+fn record_stats(guesses: &[Word]) -> SessionStats {
+    let path = Settings::config_path().map(|path| path.with_file_name("stats"));
+    let mut stats = path
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map_or_else(SessionStats::default, |text| SessionStats::parse(&text));
 
-    print!(" ");
-    print_row!(ROW_2);
-    println!();
+    stats.record(&BoardSummary::from_history(guesses));
 
-    print!("  ");
-    print_row!(ROW_3);
+    if let Some(path) = path {
+        let saved = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, stats.to_string()));
+        if let Err(error) = saved {
+            eprintln!("Couldn't save the stats to {}: {error}", path.display());
+        }
+    }
 
-    println!("{}", style::Reset);
+    stats
 }
 
-/// Create a render config for `inquire`.
-///
-/// `inquire`'s render config needs a `&'static str` as the prompt string, which is why we need a
-/// separate function to generate it.
-fn create_render_config(guesses: u8) -> RenderConfig {
-    use inquire::ui::Color;
+/// Print the stats, including the distribution of how many guesses each win took.
+fn print_stats(stats: &SessionStats) {
+    let win_rate = stats.win_rate().unwrap_or_default() * 100.0;
+    println!(
+        "\nPlayed {}, won {win_rate:.0}%, current streak {}, best streak {}",
+        stats.boards, stats.current_streak, stats.best_streak
+    );
 
-    // This section is needed because RenderConfig.prompt_prefix needs to be
-    // Styled<&'static str>, so the string needs to be a literal
-
-    let prompt_prefix = Styled::new(match guesses {
-        6 => "(1/6) >",
-        5 => "(2/6) >",
-        4 => "(3/6) >",
-        3 => "(4/6) >",
-        2 => "(5/6) >",
-        1 => "(6/6) >",
-        _ => unreachable!("We should never want a prompt with more than 6 guesses"),
-    })
-    .with_fg(Color::LightGreen);
-
-    let answered_prompt_prefix = Styled::new(match guesses {
-        6 => "(1/6) >",
-        5 => "(2/6) >",
-        4 => "(3/6) >",
-        3 => "(4/6) >",
-        2 => "(5/6) >",
-        1 => "(6/6) >",
-        _ => unreachable!("We should never want a prompt with more than 6 guesses"),
-    })
-    .with_fg(Color::Black);
+    let most = stats
+        .distribution
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    for (guesses, &count) in (1..).zip(&stats.distribution) {
+        let bar = "#".repeat(usize::try_from(count * 20 / most).unwrap_or_default());
+        println!("{guesses} {bar} {count}");
+    }
+}
 
-    let mut config = RenderConfig::default_colored();
-    config.prompt_prefix = prompt_prefix;
-    config.answered_prompt_prefix = answered_prompt_prefix;
+/// An option in the menu that's shown at startup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MenuChoice {
+    /// Play a game with a random word.
+    QuickPlay,
 
-    config
+    /// Play a game with today's daily word.
+    Daily,
+
+    /// Change the settings and save them to the config file.
+    Settings,
+
+    /// Exit without playing.
+    Quit,
+}
+
+impl MenuChoice {
+    /// Every option, in the order they're shown in the menu.
+    const ALL: [Self; 4] = [Self::QuickPlay, Self::Daily, Self::Settings, Self::Quit];
+}
+
+impl fmt::Display for MenuChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::QuickPlay => "Quick play",
+            Self::Daily => "Daily",
+            Self::Settings => "Settings",
+            Self::Quit => "Quit",
+        })
+    }
+}
+
+/// Show the startup menu until the user chooses a game, and return that game.
+///
+/// If the user quits, we return [`None`].
+fn choose_game(settings: &mut Settings) -> Option<Game> {
+    loop {
+        match Select::new("What would you like to do?", MenuChoice::ALL.to_vec())
+            .prompt()
+            .ok()?
+        {
+            MenuChoice::QuickPlay => return Some(Game::new()),
+            MenuChoice::Daily => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                return Some(Game::daily(epoch_day(now.as_secs())));
+            }
+            MenuChoice::Settings => {
+                if settings.edit().is_err() {
+                    continue;
+                }
+                match settings.save() {
+                    Ok(path) => println!("Saved the settings to {}\n", path.display()),
+                    Err(error) => eprintln!("Couldn't save the settings: {error}\n"),
+                }
+            }
+            MenuChoice::Quit => return None,
+        }
+    }
 }
 
 /// Run the main game loop.
 ///
 /// This loop consists of prompting the user for a guess, making that guess against the [`Game`],
 /// and responding accordingly.
+///
+/// If the `--export-image <path>` argument is given, the board is saved as an SVG image at the
+/// end of the game. If the `--screen-reader` argument is given, each guess and the keyboard are
+/// described in sentences rather than drawn in colour. If the `--record <path>` argument is given,
+/// every guess is recorded and saved as a replay script at the end of the game.
+///
+/// When run without any arguments in a terminal, a menu is shown first, where the user can choose
+/// a game or change the [`Settings`].
+///
+/// If the game was won or lost, the share text from [`Game::share_string_with`] is printed at the
+/// end, so that it can be copied, and the game is added to the saved [`SessionStats`].
 fn main() {
-    let mut game = Game::new();
+    let mut settings = Settings::load();
+    let interactive = std::env::args().len() == 1 && termion::is_tty(&std::io::stdin());
 
-    let validator = |input: &str| {
-        let valid = Game::is_valid_guess(input);
-        match valid {
-            Ok(()) => Ok(Validation::Valid),
-            Err(error) => Ok(Validation::Invalid(error.into())),
+    let mut game = if interactive {
+        match choose_game(&mut settings) {
+            Some(game) => game,
+            None => return,
         }
+    } else {
+        Game::new()
     };
 
-    let mut remaining_guesses: u8 = 6;
+    let export_path = flag_value("export-image");
+    let record_path = flag_value("record");
+    let screen_reader =
+        settings.screen_reader || std::env::args().any(|arg| arg == "--screen-reader");
+    let ansi_options = settings.ansi_options();
+    let mut recorder = record_path.as_ref().map(|_| Recorder::new(&game));
+    let messages = English;
+
+    let word_list = game.word_list.clone();
+
     let mut past_guesses: Vec<Word> = Vec::new();
 
-    println!("Welcome to Wordle!\n");
+    println!("{}\n", messages.welcome());
+    if !screen_reader {
+        println!("{}\n", render_grid(&[], MAX_GUESSES, &ansi_options));
+    }
 
     loop {
-        if remaining_guesses == 0 {
-            println!("\nOut of guesses!");
-            println!("Thanks for playing Wordle! The word was {}!", game.word);
+        if past_guesses.len() >= MAX_GUESSES {
+            println!("\n{}", messages.out_of_guesses());
+            println!("{}", messages.loss(game.word.reveal_for_display()));
             break;
         };
 
+        // The guesses are checked exactly like the game checks them, including its policy
+        let guess_validator = GuessValidator::for_game(&game);
+        let before = Constraints::from_history(&game.guess_history);
+        let validator = move |input: &str| {
+            let valid =
+                normalize_guess(input).and_then(|guess| guess_validator.validate(&guess, &before));
+            match valid {
+                Ok(()) => Ok(Validation::Valid),
+                Err(error) => Ok(Validation::Invalid(messages.guess_error(&error).into())),
+            }
+        };
+
         if let Ok(guess) = Text::new("")
-            .with_render_config(create_render_config(remaining_guesses))
+            .with_render_config(create_render_config(past_guesses.len() + 1))
             .with_validator(validator)
-            .with_formatter(&str::to_ascii_uppercase)
+            .with_completer(&|input| Ok(complete_guess(&word_list, input)))
+            .with_formatter(&|input| normalize_guess(input).unwrap_or_else(|_| input.to_string()))
             .prompt()
         {
+            if let Some(recorder) = &mut recorder {
+                recorder.record_guess(&guess);
+            }
+
+            let guess = normalize_guess(&guess)
+                .expect("User should not have been able to enter an unnormalizable guess");
+
             let letters = game.make_guess(&guess).unwrap_or_else(|_| {
                 panic!("User should not have been able to enter any invalid guess: {guess:?}")
             });
@@ -175,12 +309,19 @@ fn main() {
 
             print!("{}", termion::clear::All);
 
-            for guess in &past_guesses {
-                print_guess(guess);
+            if screen_reader {
+                println!("{}", describe_guess_with(&letters, &messages));
+                println!("{}", describe_keyboard_with(&game.keyboard, &messages));
+            } else {
+                println!(
+                    "{}\n",
+                    render_grid(&past_guesses, MAX_GUESSES, &ansi_options)
+                );
+                println!(
+                    "{}",
+                    render_keyboard(&game.keyboard, settings.layout, &ansi_options)
+                );
             }
-            println!();
-
-            print_keyboard(&game.keyboard);
 
             if letters
                 .iter()
@@ -188,14 +329,25 @@ fn main() {
                 .count()
                 == 5
             {
-                println!("\nCongratulations! The word was {}!", game.word);
+                println!("\n{}", messages.win(game.word.reveal_for_display()));
                 break;
             }
-
-            remaining_guesses -= 1;
         } else {
-            println!("\nThanks for playing Wordle! The word was {}!", game.word);
+            println!("\n{}", messages.loss(game.word.reveal_for_display()));
             break;
         }
     }
+
+    if let Some(share) = game.share_string_with(true, &ansi_options.theme) {
+        println!("\n{share}");
+        print_stats(&record_stats(&game.guess_history));
+    }
+
+    if let Some(path) = export_path {
+        export_image(&path, &past_guesses);
+    }
+
+    if let (Some(path), Some(recorder)) = (record_path, &recorder) {
+        save_recording(&path, recorder);
+    }
 }
@@ -0,0 +1,198 @@
+//! This module handles the settings of the CLI, which are saved in a config file.
+//!
+//! The config file has one `key = value` pair per line, like this:
+//!
+//! ```text
+//! colour = true
+//! screen-reader = false
+//! theme = dark
+//! layout = qwerty
+//! ```
+//!
+//! Unknown keys and invalid values are ignored, so that an old version of the CLI can still read
+//! the config file of a newer one.
+
+use inquire::{error::InquireResult, Confirm, Select};
+use std::{fmt, io, path::PathBuf};
+use wordle::{keyboard::Layout, render::ansi::AnsiOptions, theme::Theme};
+
+/// The settings of the CLI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Settings {
+    /// Whether to draw the board in colour. This is ignored if the `NO_COLOR` environment variable
+    /// is set. See [`AnsiOptions::from_env`].
+    pub colour: bool,
+
+    /// Whether to describe each guess and the keyboard in sentences rather than drawing them.
+    pub screen_reader: bool,
+
+    /// The colour theme of the board and keyboard.
+    pub theme: Theme,
+
+    /// The layout of the keyboard.
+    pub layout: Layout,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            colour: true,
+            screen_reader: false,
+            theme: Theme::CLASSIC,
+            layout: Layout::Qwerty,
+        }
+    }
+}
+
+impl Settings {
+    /// Get the path of the config file, which is `wordle/config` in `$XDG_CONFIG_HOME`, or in
+    /// `$HOME/.config` if that isn't set.
+    ///
+    /// If neither variable is set, there's nowhere to save the settings, so we return [`None`].
+    pub fn config_path() -> Option<PathBuf> {
+        let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME")
+                    .filter(|dir| !dir.is_empty())
+                    .map(|home| PathBuf::from(home).join(".config"))
+            })?;
+
+        Some(config_dir.join("wordle").join("config"))
+    }
+
+    /// Parse the settings from the text of a config file. See the [module documentation](self).
+    pub fn parse(text: &str) -> Self {
+        let mut settings = Self::default();
+
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+
+            match (key.trim(), value.trim()) {
+                ("colour", value) => settings.colour = value.parse().unwrap_or(settings.colour),
+                ("screen-reader", value) => {
+                    settings.screen_reader = value.parse().unwrap_or(settings.screen_reader);
+                }
+                ("theme", value) => {
+                    settings.theme = Theme::by_name(value).unwrap_or(settings.theme);
+                }
+                ("layout", value) => {
+                    settings.layout = Layout::by_name(value).unwrap_or(settings.layout);
+                }
+                _ => {}
+            }
+        }
+
+        settings
+    }
+
+    /// Load the settings from the config file, or get the default settings if there isn't one.
+    pub fn load() -> Self {
+        Self::config_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map_or_else(Self::default, |text| Self::parse(&text))
+    }
+
+    /// Save the settings to the config file, creating its directory if needed.
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let path = Self::config_path().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "Neither XDG_CONFIG_HOME nor HOME is set",
+            )
+        })?;
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, self.to_string())?;
+
+        Ok(path)
+    }
+
+    /// Ask the user for each setting in turn, starting from the current values.
+    pub fn edit(&mut self) -> InquireResult<()> {
+        self.colour = Confirm::new("Draw the board in colour?")
+            .with_default(self.colour)
+            .prompt()?;
+
+        self.screen_reader = Confirm::new("Describe guesses in sentences for screen readers?")
+            .with_default(self.screen_reader)
+            .prompt()?;
+
+        let names: Vec<&str> = Theme::ALL.iter().map(|theme| theme.name).collect();
+        let current = Theme::ALL
+            .iter()
+            .position(|theme| *theme == self.theme)
+            .unwrap_or(0);
+        let name = Select::new("Theme:", names)
+            .with_starting_cursor(current)
+            .prompt()?;
+        self.theme = Theme::by_name(name).unwrap_or(self.theme);
+
+        let names: Vec<&str> = Layout::ALL.iter().map(|layout| layout.name()).collect();
+        let current = Layout::ALL
+            .iter()
+            .position(|layout| *layout == self.layout)
+            .unwrap_or(0);
+        let name = Select::new("Keyboard layout:", names)
+            .with_starting_cursor(current)
+            .prompt()?;
+        self.layout = Layout::by_name(name).unwrap_or(self.layout);
+
+        Ok(())
+    }
+
+    /// Get the options for drawing the board and keyboard with these settings.
+    pub fn ansi_options(&self) -> AnsiOptions {
+        let from_env = AnsiOptions::from_env();
+
+        AnsiOptions {
+            colour: self.colour && from_env.colour,
+            theme: self.theme,
+        }
+    }
+}
+
+impl fmt::Display for Settings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "colour = {}", self.colour)?;
+        writeln!(f, "screen-reader = {}", self.screen_reader)?;
+        writeln!(f, "theme = {}", self.theme.name)?;
+        writeln!(f, "layout = {}", self.layout.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_round_trip() {
+        let settings = Settings {
+            colour: false,
+            screen_reader: true,
+            theme: Theme::HIGH_CONTRAST,
+            layout: Layout::Azerty,
+        };
+        assert_eq!(Settings::parse(&settings.to_string()), settings);
+        assert_eq!(Settings::parse(""), Settings::default());
+    }
+
+    #[test]
+    fn parse_ignores_invalid_lines() {
+        let settings = Settings::parse(
+            "colour=false\nhard-mode = true\ntheme = neon\nlayout = dvorak\nnonsense",
+        );
+        assert_eq!(
+            settings,
+            Settings {
+                colour: false,
+                ..Settings::default()
+            }
+        );
+    }
+}
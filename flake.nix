@@ -69,6 +69,9 @@
             inherit (craneLib.crateNameFromCargoToml {inherit src;}) version;
           };
 
+        # The most bytes that the web app's WASM can take up
+        webSizeBudget = 1536 * 1024;
+
         trunkPreBuildTools = with pkgs.nodePackages; [
           autoprefixer
           postcss
@@ -127,6 +130,19 @@
             inherit src;
           };
 
+          # Keep the initial download of the web app small. See web/README.md
+          web-size = pkgs.runCommand "wordle-web-size" {} ''
+            budget=${toString webSizeBudget}
+            for wasm in ${packages.web}/*.wasm; do
+              size=$(stat --format=%s "$wasm")
+              echo "$wasm is $size bytes, and the budget is $budget bytes"
+              if [ "$size" -gt "$budget" ]; then
+                exit 1
+              fi
+            done
+            touch $out
+          '';
+
           nextest = craneLib.cargoNextest (commonArgs
             // {
               inherit cargoArtifacts;
@@ -164,6 +180,7 @@
 
                 trunkIndexPath = "web/index.html";
                 cargoExtraArgs = "--package=wordle-web";
+                TRUNK_BUILD_CARGO_PROFILE = "wasm-release";
                 CARGO_BUILD_TARGET = "wasm32-unknown-unknown";
                 inherit (pkgs) wasm-bindgen-cli;
 
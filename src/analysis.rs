@@ -0,0 +1,622 @@
+//! This module handles analysing a game in progress, for trainers and other tools that want to
+//! show more than the colours of previous guesses.
+
+use super::{
+    letters::Position,
+    scoring,
+    solver::Solver,
+    valid_words::{ALPHABET, GOOD_WORDS},
+    Word,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+use thiserror::Error;
+
+/// An enum representing possible errors from parsing a [`Knowledge`] query string.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum QueryError {
+    /// The pattern doesn't describe exactly 5 letters with letters, `.`, and `[^...]`.
+    #[error("Invalid pattern: {0:?}")]
+    InvalidPattern(String),
+
+    /// The part after the pattern isn't a `+` followed by letters.
+    #[error("Invalid required letters: {0:?}")]
+    InvalidRequired(String),
+}
+
+/// The constraints on the answer that are known from the scored guesses so far.
+///
+/// This can be written as a regex with [`to_regex`](Knowledge::to_regex) or as a query string with
+/// [`to_query_string`](Knowledge::to_query_string), so that players can paste it into other word
+/// search tools. A query string can be parsed again with [`parse_query`](Knowledge::parse_query).
+///
+/// The query string is the regex without its anchors, followed by a space, a `+`, and every
+/// letter known to be in the answer, repeated as many times as it's known to appear, like
+/// `s[^t]a.. +ast`. The `+` part is left out if no letters are known to be in the answer. All the
+/// letters are lowercase.
+///
+/// This is less precise than the history itself, since it doesn't record exact letter counts, so
+/// [`candidates`](Knowledge::candidates) may include a few words that [`candidates`] doesn't.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Knowledge {
+    /// The letter in each position of the answer, if it's known. All the letters are uppercase.
+    pub correct: [Option<char>; 5],
+
+    /// The letters known not to be in each position of the answer.
+    pub excluded: [BTreeSet<char>; 5],
+
+    /// The minimum number of times that each letter is known to appear in the answer.
+    pub required: BTreeMap<char, usize>,
+}
+
+impl Knowledge {
+    /// Get what's known about the answer from the given scored guesses.
+    ///
+    /// The guesses must have been scored with the [`Classic`](scoring::ScoringRules::Classic)
+    /// rules.
+    pub fn from_history(history: &[Word]) -> Self {
+        let mut knowledge = Self::default();
+        let mut absent = BTreeSet::new();
+
+        for word in history {
+            let mut counts: BTreeMap<char, usize> = BTreeMap::new();
+
+            for (index, letter) in word.iter().enumerate() {
+                let c = letter.letter.to_ascii_uppercase();
+                let count = counts.entry(c).or_insert(0);
+
+                match letter.position {
+                    Position::Correct => {
+                        knowledge.correct[index] = Some(c);
+                        *count += 1;
+                    }
+                    Position::WrongPosition => {
+                        knowledge.excluded[index].insert(c);
+                        *count += 1;
+                    }
+                    Position::NotInWord => {
+                        knowledge.excluded[index].insert(c);
+                    }
+                }
+            }
+
+            for (c, count) in counts {
+                if count == 0 {
+                    absent.insert(c);
+                }
+                let required = knowledge.required.entry(c).or_insert(0);
+                *required = (*required).max(count);
+            }
+        }
+
+        knowledge.required.retain(|_, count| *count > 0);
+        for c in absent {
+            if !knowledge.required.contains_key(&c) {
+                for excluded in &mut knowledge.excluded {
+                    excluded.insert(c);
+                }
+            }
+        }
+
+        // Exclusions don't matter where the letter is known
+        for (correct, excluded) in knowledge.correct.iter().zip(&mut knowledge.excluded) {
+            if correct.is_some() {
+                excluded.clear();
+            }
+        }
+
+        knowledge
+    }
+
+    /// Parse a query string from [`to_query_string`](Knowledge::to_query_string).
+    ///
+    /// The letters can be in either case, and the result always has uppercase letters.
+    ///
+    /// # Errors
+    ///
+    /// If the pattern or the required letters are invalid, we return the appropriate
+    /// [`QueryError`] variant.
+    pub fn parse_query(query: &str) -> Result<Self, QueryError> {
+        let mut parts = query.split_whitespace();
+        let pattern = parts.next().unwrap_or_default();
+        let invalid_pattern = || QueryError::InvalidPattern(pattern.to_string());
+
+        let mut knowledge = Self::default();
+        let mut chars = pattern.chars();
+        let mut index = 0;
+
+        while let Some(c) = chars.next() {
+            let position = index;
+            index += 1;
+            if position >= 5 {
+                return Err(invalid_pattern());
+            }
+
+            match c {
+                '.' => {}
+                '[' => {
+                    if chars.next() != Some('^') {
+                        return Err(invalid_pattern());
+                    }
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some(c) if c.is_ascii_alphabetic() => {
+                                knowledge.excluded[position].insert(c.to_ascii_uppercase());
+                            }
+                            _ => return Err(invalid_pattern()),
+                        }
+                    }
+                }
+                c if c.is_ascii_alphabetic() => {
+                    knowledge.correct[position] = Some(c.to_ascii_uppercase());
+                }
+                _ => return Err(invalid_pattern()),
+            }
+        }
+
+        if index != 5 {
+            return Err(invalid_pattern());
+        }
+
+        for part in parts {
+            let letters = part
+                .strip_prefix('+')
+                .filter(|letters| letters.chars().all(|c| c.is_ascii_alphabetic()))
+                .ok_or_else(|| QueryError::InvalidRequired(part.to_string()))?;
+
+            for c in letters.chars() {
+                *knowledge
+                    .required
+                    .entry(c.to_ascii_uppercase())
+                    .or_insert(0) += 1;
+            }
+        }
+
+        Ok(knowledge)
+    }
+
+    /// Get the pattern for each position, without anchors.
+    fn pattern(&self) -> String {
+        self.correct
+            .iter()
+            .zip(&self.excluded)
+            .map(|(correct, excluded)| match correct {
+                Some(c) => c.to_ascii_lowercase().to_string(),
+                None if excluded.is_empty() => ".".to_string(),
+                None => {
+                    let letters: String = excluded.iter().map(char::to_ascii_lowercase).collect();
+                    format!("[^{letters}]")
+                }
+            })
+            .collect()
+    }
+
+    /// Write the constraints on each position as a regex, like `^s[^t]a..$`.
+    ///
+    /// A regex like this can't say which letters must appear somewhere in the answer. See
+    /// [`to_query_string`](Knowledge::to_query_string) for that.
+    pub fn to_regex(&self) -> String {
+        format!("^{}$", self.pattern())
+    }
+
+    /// Write the constraints as a query string. See the [type documentation](Knowledge).
+    pub fn to_query_string(&self) -> String {
+        let required: String = self
+            .required
+            .iter()
+            .flat_map(|(c, &count)| std::iter::repeat_n(c.to_ascii_lowercase(), count))
+            .collect();
+
+        if required.is_empty() {
+            self.pattern()
+        } else {
+            format!("{} +{required}", self.pattern())
+        }
+    }
+
+    /// Check whether the given word satisfies every constraint.
+    pub fn matches(&self, word: &str) -> bool {
+        let word = word.to_ascii_uppercase();
+        let letters: Vec<char> = word.chars().collect();
+
+        letters.len() == 5
+            && letters
+                .iter()
+                .zip(self.correct.iter().zip(&self.excluded))
+                .all(|(c, (correct, excluded))| match correct {
+                    Some(correct) => c == correct,
+                    None => !excluded.contains(c),
+                })
+            && self
+                .required
+                .iter()
+                .all(|(c, &count)| letters.iter().filter(|&l| l == c).count() >= count)
+    }
+
+    /// Get every word in [`GOOD_WORDS`] that satisfies every constraint.
+    pub fn candidates(&self) -> impl Iterator<Item = &'static str> + '_ {
+        GOOD_WORDS
+            .iter()
+            .copied()
+            .filter(move |word| self.matches(word))
+    }
+}
+
+/// The exact constraints on the answer from every scored guess so far.
+///
+/// Unlike [`Knowledge`], this keeps the guesses themselves, so a word only
+/// [`matches`](Constraints::matches) if it would have given exactly the same colours to every
+/// guess, including duplicate letters.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Constraints {
+    /// Every scored guess recorded so far, in order.
+    history: Vec<Word>,
+}
+
+impl Constraints {
+    /// Create constraints from the given scored guesses, like
+    /// [`Game::guess_history`](super::Game::guess_history).
+    ///
+    /// The guesses must have been scored with the [`Classic`](scoring::ScoringRules::Classic)
+    /// rules.
+    pub fn from_history(history: &[Word]) -> Self {
+        Self {
+            history: history.to_vec(),
+        }
+    }
+
+    /// Add the feedback from another scored guess.
+    pub fn record(&mut self, word: Word) {
+        self.history.push(word);
+    }
+
+    /// Get every scored guess recorded so far, in order.
+    pub fn history(&self) -> &[Word] {
+        &self.history
+    }
+
+    /// Check whether the given word could still be the answer.
+    ///
+    /// The word doesn't have to be in [`GOOD_WORDS`], so this can check words from custom lists.
+    pub fn matches(&self, word: &str) -> bool {
+        is_consistent(word, &self.history)
+    }
+
+    /// Summarise what's known about the answer from every scored guess so far. See
+    /// [`Knowledge::from_history`].
+    pub fn knowledge(&self) -> Knowledge {
+        Knowledge::from_history(&self.history)
+    }
+
+    /// Get every word in [`GOOD_WORDS`] that could still be the answer, like [`candidates`].
+    ///
+    /// The length of this is the number of possible words remaining, which can be shown after
+    /// each guess.
+    pub fn filter_candidates(&self) -> Vec<&'static str> {
+        candidates(&self.history).collect()
+    }
+}
+
+/// A letter grade for a guess, from how much of the information of the best guess it would give.
+///
+/// The grades come from the solver's grade out of 100 with these thresholds:
+///
+/// | Grade | Score     |
+/// |-------|-----------|
+/// | A     | 90 to 100 |
+/// | B     | 75 to 89  |
+/// | C     | 60 to 74  |
+/// | D     | 40 to 59  |
+/// | F     | 0 to 39   |
+///
+/// See [`grade_guess`] and [`Solver::grade`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Grade {
+    /// The guess gives at least 90% of the information of the best guess.
+    A,
+
+    /// The guess gives at least 75% of the information of the best guess.
+    B,
+
+    /// The guess gives at least 60% of the information of the best guess.
+    C,
+
+    /// The guess gives at least 40% of the information of the best guess.
+    D,
+
+    /// The guess gives less than 40% of the information of the best guess.
+    F,
+}
+
+impl Grade {
+    /// Get the grade for the given score out of 100, like from [`Solver::grade`]. Scores over 100
+    /// are an A.
+    pub fn from_score(score: u8) -> Self {
+        match score {
+            90.. => Self::A,
+            75..=89 => Self::B,
+            60..=74 => Self::C,
+            40..=59 => Self::D,
+            _ => Self::F,
+        }
+    }
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::A => "A",
+            Self::B => "B",
+            Self::C => "C",
+            Self::D => "D",
+            Self::F => "F",
+        })
+    }
+}
+
+/// Grade the given guess against what was known before it was played.
+///
+/// This finds the best guess given the constraints, so it's as slow as
+/// [`Solver::best_guess`]. See [`Grade`].
+pub fn grade_guess(before: &Constraints, guess: &str) -> Grade {
+    Grade::from_score(Solver::from_history(before.history()).grade(guess))
+}
+
+/// Check if the answer is consistent with every scored guess in the history, meaning that it
+/// would have given exactly the same colours.
+pub(crate) fn is_consistent(answer: &str, history: &[Word]) -> bool {
+    history.iter().all(|word| {
+        let guess: String = word.iter().map(|letter| letter.letter).collect();
+        scoring::score_guess(answer, &guess).is_ok_and(|scored| {
+            scored
+                .iter()
+                .zip(word)
+                .all(|(a, b)| a.position == b.position)
+        })
+    })
+}
+
+/// Get every word in [`GOOD_WORDS`] that could still be the answer, given the scored guesses so
+/// far.
+///
+/// The guesses must have been scored with the [`Classic`](scoring::ScoringRules::Classic) rules.
+pub fn candidates(history: &[Word]) -> impl Iterator<Item = &'static str> + '_ {
+    GOOD_WORDS
+        .iter()
+        .copied()
+        .filter(move |answer| is_consistent(answer, history))
+}
+
+/// Count the words in [`GOOD_WORDS`] that could still be the answer after each guess in the
+/// history, so that a finished game can be annotated row by row.
+///
+/// The count at index `i` is the number of [`candidates`] given the first `i + 1` guesses, so the
+/// result is as long as the history and never increases.
+///
+/// The guesses must have been scored with the [`Classic`](scoring::ScoringRules::Classic) rules.
+pub fn candidate_counts(history: &[Word]) -> Vec<usize> {
+    let mut remaining: Vec<&str> = GOOD_WORDS.to_vec();
+
+    history
+        .iter()
+        .map(|word| {
+            remaining.retain(|answer| is_consistent(answer, std::slice::from_ref(word)));
+            remaining.len()
+        })
+        .collect()
+}
+
+/// Estimate the probability that each letter appears in the answer, given the scored guesses so
+/// far.
+///
+/// The probabilities are in the same order as [`ALPHABET`], and each one is the fraction of the
+/// remaining [`candidates`] that contain that letter. Every candidate is assumed to be equally
+/// likely. Letters that have already been played will usually be exactly 0 or 1, so this is most
+/// useful for shading the keys that haven't been played yet.
+///
+/// If no candidates are left, which can only happen if the history wasn't scored against a word
+/// in [`GOOD_WORDS`], then every probability is 0.
+pub fn letter_probabilities(history: &[Word]) -> [f64; 26] {
+    let mut counts = [0_usize; 26];
+    let mut total = 0_usize;
+
+    for answer in candidates(history) {
+        total += 1;
+        for (count, letter) in counts.iter_mut().zip(ALPHABET) {
+            if answer.contains(letter) {
+                *count += 1;
+            }
+        }
+    }
+
+    if total == 0 {
+        return [0.0; 26];
+    }
+
+    counts.map(|count| count as f64 / total as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(letter: char) -> usize {
+        ALPHABET
+            .iter()
+            .position(|&c| c == letter)
+            .expect("The letter should be in the alphabet")
+    }
+
+    #[test]
+    fn no_history_uses_every_word() {
+        assert_eq!(candidates(&[]).count(), GOOD_WORDS.len());
+
+        let probabilities = letter_probabilities(&[]);
+        assert!(probabilities.iter().all(|&p| p > 0.0 && p < 1.0));
+        assert!(probabilities[index('E')] > probabilities[index('Q')]);
+    }
+
+    #[test]
+    fn history_narrows_probabilities() {
+        let history = [
+            scoring::score_guess("HUNCH", "CRANE").unwrap(),
+            scoring::score_guess("HUNCH", "MOIST").unwrap(),
+        ];
+
+        let candidates: Vec<&str> = candidates(&history).collect();
+        assert!(candidates.contains(&"HUNCH"));
+        assert!(candidates.iter().all(|word| !word.contains('R')));
+
+        let probabilities = letter_probabilities(&history);
+        assert_eq!(probabilities[index('N')], 1.0);
+        assert_eq!(probabilities[index('C')], 1.0);
+        assert_eq!(probabilities[index('R')], 0.0);
+        assert_eq!(probabilities[index('O')], 0.0);
+        assert!(probabilities[index('H')] > 0.0);
+    }
+
+    #[test]
+    fn knowledge_from_history() {
+        let history = [
+            scoring::score_guess("SHALT", "STAIR").unwrap(),
+            scoring::score_guess("SHALT", "EERIE").unwrap(),
+        ];
+        let knowledge = Knowledge::from_history(&history);
+
+        assert_eq!(knowledge.to_regex(), "^s[^eirt]a[^eir][^eir]$");
+        assert_eq!(knowledge.to_query_string(), "s[^eirt]a[^eir][^eir] +ast");
+        assert_eq!(
+            Knowledge::parse_query(&knowledge.to_query_string()),
+            Ok(knowledge.clone())
+        );
+
+        let exact: Vec<&str> = super::candidates(&history).collect();
+        let loose: Vec<&str> = knowledge.candidates().collect();
+        assert!(exact.contains(&"SHALT"));
+        assert!(exact.iter().all(|word| loose.contains(word)));
+
+        assert_eq!(Knowledge::from_history(&[]).to_query_string(), ".....");
+        assert_eq!(
+            Knowledge::from_history(&[]).candidates().count(),
+            GOOD_WORDS.len()
+        );
+    }
+
+    #[test]
+    fn parse_query() {
+        let knowledge = Knowledge::parse_query("S[^T]A.. +tE").unwrap();
+        assert_eq!(knowledge.correct, [Some('S'), None, Some('A'), None, None]);
+        assert_eq!(knowledge.excluded[1], BTreeSet::from(['T']));
+        assert_eq!(knowledge.required, BTreeMap::from([('E', 1), ('T', 1)]));
+        assert!(knowledge.matches("skate"));
+        assert!(!knowledge.matches("STATE"));
+        assert!(!knowledge.matches("SLANT"));
+
+        for query in ["", "s[^t]a.", "s[^t]a...", "s[t]a..", "s[^ta..", "s1a.."] {
+            assert_eq!(
+                Knowledge::parse_query(query),
+                Err(QueryError::InvalidPattern(
+                    query.split_whitespace().next().unwrap_or("").to_string()
+                ))
+            );
+        }
+        assert_eq!(
+            Knowledge::parse_query("..... e"),
+            Err(QueryError::InvalidRequired("e".to_string()))
+        );
+    }
+
+    #[test]
+    fn candidate_counts_narrow_each_row() {
+        let history = [
+            scoring::score_guess("HUNCH", "CRANE").unwrap(),
+            scoring::score_guess("HUNCH", "MOIST").unwrap(),
+            scoring::score_guess("HUNCH", "HUNCH").unwrap(),
+        ];
+
+        let counts = candidate_counts(&history);
+        assert_eq!(counts.len(), 3);
+        for (played, &count) in counts.iter().enumerate() {
+            assert_eq!(count, candidates(&history[..=played]).count());
+        }
+        assert!(counts[0] >= counts[1]);
+        assert_eq!(counts[2], 1);
+
+        assert!(candidate_counts(&[]).is_empty());
+    }
+
+    #[test]
+    fn constraints_filter_candidates() {
+        let mut constraints = Constraints::default();
+        assert!(constraints.matches("FUZZY"));
+        assert_eq!(constraints.filter_candidates().len(), GOOD_WORDS.len());
+
+        let history = [
+            scoring::score_guess("SHALT", "STAIR").unwrap(),
+            scoring::score_guess("SHALT", "EERIE").unwrap(),
+        ];
+        for word in history {
+            constraints.record(word);
+        }
+        assert_eq!(constraints, Constraints::from_history(&history));
+        assert_eq!(constraints.history(), history);
+
+        assert!(constraints.matches("shalt"));
+        assert!(!constraints.matches("STAIR"));
+        assert!(!constraints.matches("SHAL"));
+        assert_eq!(
+            constraints.filter_candidates(),
+            candidates(&history).collect::<Vec<_>>()
+        );
+        assert!(constraints
+            .filter_candidates()
+            .iter()
+            .all(|word| Knowledge::from_history(&history).matches(word)));
+    }
+
+    #[test]
+    fn impossible_history_has_no_probabilities() {
+        let history = [scoring::score_guess("DYSON", "DYSON").unwrap()];
+        assert_eq!(candidates(&history).count(), 0);
+        assert_eq!(letter_probabilities(&history), [0.0; 26]);
+    }
+
+    #[test]
+    fn grades() {
+        let grades: Vec<Grade> = [100, 90, 89, 75, 74, 60, 59, 40, 39, 0, 255]
+            .into_iter()
+            .map(Grade::from_score)
+            .collect();
+        assert_eq!(
+            grades,
+            [
+                Grade::A,
+                Grade::A,
+                Grade::B,
+                Grade::B,
+                Grade::C,
+                Grade::C,
+                Grade::D,
+                Grade::D,
+                Grade::F,
+                Grade::F,
+                Grade::A
+            ]
+        );
+        assert_eq!(Grade::C.to_string(), "C");
+
+        let before = Constraints::from_history(&[
+            scoring::score_guess("HUNCH", "CRANE").unwrap(),
+            scoring::score_guess("HUNCH", "MOIST").unwrap(),
+        ]);
+        let solver = Solver::from_history(before.history());
+        assert_eq!(grade_guess(&before, solver.best_guess().unwrap()), Grade::A);
+        assert_eq!(grade_guess(&before, "CRANE"), Grade::F);
+    }
+}
@@ -0,0 +1,190 @@
+//! This module provides hooks for frontends that want to collect metrics about games.
+//!
+//! The library never collects or sends anything itself. A deployment that wants metrics can
+//! implement [`Analytics`] and call it from its frontend, and decide for itself where the events
+//! go. The events only contain anonymised data, so they never include the guessed words or the
+//! answer, only the colours of each guess.
+//!
+//! Each event can be made from a [`Game`] with its `from_game` constructor, so frontends don't
+//! have to work out the fields themselves.
+
+use super::{is_won, letters::Position, puzzle::PuzzleId, Game, Word, MAX_GUESSES};
+
+/// A game was started.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GameStarted {
+    /// The epoch day of the game, if it's a daily game. See [`Game::daily`](super::Game::daily).
+    pub day: Option<u64>,
+}
+
+impl GameStarted {
+    /// Create an event for starting the given game.
+    pub fn from_game(game: &Game) -> Self {
+        Self {
+            day: match game.puzzle {
+                PuzzleId::Daily(day) => Some(day),
+                PuzzleId::Custom => None,
+            },
+        }
+    }
+}
+
+/// A valid guess was made.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GuessMade {
+    /// The number of this guess in the game, starting from 1.
+    pub guess_number: usize,
+
+    /// The colours of the scored guess, without the letters.
+    pub pattern: [Position; 5],
+}
+
+impl GuessMade {
+    /// Create an event for the given scored guess, dropping the letters.
+    pub fn from_word(guess_number: usize, word: &Word) -> Self {
+        Self {
+            guess_number,
+            pattern: word.map(|letter| letter.position),
+        }
+    }
+
+    /// Create an event for the latest guess of the given game, or return [`None`] if no guesses
+    /// have been made.
+    pub fn from_game(game: &Game) -> Option<Self> {
+        let word = game.guess_history.last()?;
+        Some(Self::from_word(game.guess_history.len(), word))
+    }
+}
+
+/// A game was won or lost.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GameFinished {
+    /// Whether the player guessed the answer.
+    pub won: bool,
+
+    /// The number of valid guesses that the player made.
+    pub guesses: usize,
+}
+
+impl GameFinished {
+    /// Create an event for the given game, or return [`None`] if it's still in progress.
+    ///
+    /// The game is won or lost by the same rule as
+    /// [`Game::share_string_with`](super::Game::share_string_with).
+    pub fn from_game(game: &Game) -> Option<Self> {
+        let guesses = game.guess_history.len();
+        let won = is_won(&game.guess_history);
+
+        (won || guesses >= MAX_GUESSES).then_some(Self { won, guesses })
+    }
+}
+
+/// A sink for anonymised game events.
+///
+/// Every method does nothing by default, so implementations only need to handle the events
+/// they care about.
+pub trait Analytics {
+    /// Called when a game is started.
+    fn game_started(&self, _event: &GameStarted) {}
+
+    /// Called after every valid guess.
+    fn guess_made(&self, _event: &GuessMade) {}
+
+    /// Called when a game is won or lost.
+    fn game_finished(&self, _event: &GameFinished) {}
+}
+
+/// An [`Analytics`] implementation that ignores every event.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NoAnalytics;
+
+impl Analytics for NoAnalytics {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Game;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct Recorder {
+        guesses: RefCell<Vec<GuessMade>>,
+    }
+
+    impl Analytics for Recorder {
+        fn guess_made(&self, event: &GuessMade) {
+            self.guesses.borrow_mut().push(*event);
+        }
+    }
+
+    #[test]
+    fn guess_events_are_anonymised() {
+        let mut game = Game {
+            word: "DYSON".to_string().into(),
+            ..Game::new()
+        };
+        let recorder = Recorder::default();
+
+        let word = game.make_guess("SOUND").expect("SOUND is a valid guess");
+        recorder.guess_made(&GuessMade::from_word(1, &word));
+        NoAnalytics.guess_made(&GuessMade::from_word(1, &word));
+
+        use Position::*;
+        assert_eq!(
+            recorder.guesses.into_inner(),
+            vec![GuessMade {
+                guess_number: 1,
+                pattern: [
+                    WrongPosition,
+                    WrongPosition,
+                    NotInWord,
+                    WrongPosition,
+                    WrongPosition
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn events_from_games() {
+        let mut game = Game::daily(19358);
+        assert_eq!(
+            GameStarted::from_game(&game),
+            GameStarted { day: Some(19358) }
+        );
+        assert_eq!(
+            GameStarted::from_game(&Game::new()),
+            GameStarted { day: None }
+        );
+        assert_eq!(GuessMade::from_game(&game), None);
+
+        game.word = "DYSON".to_string().into();
+        let word = game.make_guess("SOUND").expect("SOUND is a valid guess");
+        assert_eq!(
+            GuessMade::from_game(&game),
+            Some(GuessMade::from_word(1, &word))
+        );
+        assert_eq!(GameFinished::from_game(&game), None);
+
+        game.make_guess("DYSON").expect("DYSON is a valid guess");
+        assert_eq!(
+            GameFinished::from_game(&game),
+            Some(GameFinished {
+                won: true,
+                guesses: 2
+            })
+        );
+
+        let mut lost = Game::with_answer("DYSON").expect("DYSON should be a valid answer");
+        for _ in 0..MAX_GUESSES {
+            lost.make_guess("CRANE").expect("CRANE is a valid guess");
+        }
+        assert_eq!(
+            GameFinished::from_game(&lost),
+            Some(GameFinished {
+                won: false,
+                guesses: MAX_GUESSES
+            })
+        );
+    }
+}
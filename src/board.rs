@@ -0,0 +1,281 @@
+//! This module handles the state of the board while the player is typing.
+//!
+//! Frontends should use [`BoardState`] to handle key presses rather than managing the current
+//! guess themselves, so that typing behaves the same everywhere.
+
+use super::{valid_words::ALPHABET, MAX_GUESSES};
+use thiserror::Error;
+
+/// An enum representing possible errors from typing on a [`BoardState`].
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum CursorError {
+    /// The character isn't a Latin letter.
+    #[error("Only Latin letters can be typed")]
+    InvalidCharacter,
+
+    /// The current row already has as many letters as it can hold.
+    #[error("The current row is full")]
+    RowFull,
+
+    /// The current row doesn't have enough letters to be submitted.
+    #[error("The current row is not full yet")]
+    RowIncomplete,
+
+    /// Every row on the board has already been used.
+    #[error("There are no rows left on the board")]
+    NoRowsLeft,
+
+    /// The board is locked, so it can't be typed on. See [`BoardState::lock`].
+    #[error("The board is locked")]
+    Locked,
+}
+
+/// The position of the cursor on the board and the letters typed in the current row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoardState {
+    /// The number of rows on the board.
+    rows: usize,
+
+    /// The number of letters in each row.
+    row_length: usize,
+
+    /// The index of the row currently being typed in.
+    ///
+    /// If this equals [`rows`](BoardState::rows), then every row has been used.
+    current_row: usize,
+
+    /// The letters typed in the current row so far.
+    current_guess: Vec<char>,
+
+    /// Whether the board is locked. See [`lock`](BoardState::lock).
+    locked: bool,
+}
+
+impl Default for BoardState {
+    fn default() -> Self {
+        Self::new(MAX_GUESSES, 5)
+    }
+}
+
+impl BoardState {
+    /// Create an empty board with the given number of rows, each holding `row_length` letters.
+    pub fn new(rows: usize, row_length: usize) -> Self {
+        Self {
+            rows,
+            row_length,
+            current_row: 0,
+            current_guess: Vec::with_capacity(row_length),
+            locked: false,
+        }
+    }
+
+    /// Get the index of the row currently being typed in.
+    pub fn current_row(&self) -> usize {
+        self.current_row
+    }
+
+    /// Get the index of the column that the next letter will be typed in.
+    pub fn current_column(&self) -> usize {
+        self.current_guess.len()
+    }
+
+    /// Get the letters typed in the current row so far.
+    pub fn current_guess(&self) -> &[char] {
+        &self.current_guess
+    }
+
+    /// Check whether the current row has as many letters as it can hold.
+    pub fn is_row_full(&self) -> bool {
+        self.current_guess.len() >= self.row_length
+    }
+
+    /// Check whether every row on the board has been used.
+    pub fn is_board_full(&self) -> bool {
+        self.current_row >= self.rows
+    }
+
+    /// Check whether the board is locked. See [`lock`](BoardState::lock).
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Lock the board, so that nothing can be typed, deleted, or submitted until it's
+    /// [unlocked](BoardState::unlock).
+    ///
+    /// Frontends should lock the board while a guess is being revealed, so that pressing enter
+    /// again during the animation can't submit another guess, and typing can't change the row
+    /// that's about to be shown.
+    pub fn lock(&mut self) {
+        self.locked = true;
+    }
+
+    /// Unlock the board after [`lock`](BoardState::lock).
+    pub fn unlock(&mut self) {
+        self.locked = false;
+    }
+
+    /// Type a letter into the current row.
+    ///
+    /// The letter is converted to uppercase.
+    ///
+    /// # Errors
+    ///
+    /// If the board is locked, the letter isn't a Latin letter, or there's no space for it, then
+    /// we return the appropriate [`CursorError`] variant and leave the board unchanged.
+    pub fn push(&mut self, letter: char) -> Result<(), CursorError> {
+        let letter = letter.to_ascii_uppercase();
+
+        if self.locked {
+            return Err(CursorError::Locked);
+        } else if !ALPHABET.contains(&letter) {
+            return Err(CursorError::InvalidCharacter);
+        } else if self.is_board_full() {
+            return Err(CursorError::NoRowsLeft);
+        } else if self.is_row_full() {
+            return Err(CursorError::RowFull);
+        }
+
+        self.current_guess.push(letter);
+        Ok(())
+    }
+
+    /// Delete the last letter in the current row, returning it if there was one.
+    ///
+    /// If the board is locked, nothing is deleted and we return [`None`].
+    pub fn pop(&mut self) -> Option<char> {
+        if self.locked {
+            return None;
+        }
+
+        self.current_guess.pop()
+    }
+
+    /// Delete all the letters in the current row, returning whether there were any.
+    ///
+    /// If the board is locked, nothing is deleted and we return `false`.
+    pub fn clear(&mut self) -> bool {
+        if self.locked {
+            return false;
+        }
+
+        let had_letters = !self.current_guess.is_empty();
+        self.current_guess.clear();
+        had_letters
+    }
+
+    /// Get the current row as a guess that can be passed to
+    /// [`Game::make_guess`](super::Game::make_guess).
+    ///
+    /// This doesn't move on to the next row, since the guess might be rejected. Call
+    /// [`next_row`](BoardState::next_row) once the guess has been accepted.
+    ///
+    /// # Errors
+    ///
+    /// If the board is locked or full, or the current row is incomplete, then we return the
+    /// appropriate [`CursorError`] variant.
+    pub fn guess(&self) -> Result<String, CursorError> {
+        if self.locked {
+            Err(CursorError::Locked)
+        } else if self.is_board_full() {
+            Err(CursorError::NoRowsLeft)
+        } else if !self.is_row_full() {
+            Err(CursorError::RowIncomplete)
+        } else {
+            Ok(self.current_guess.iter().collect())
+        }
+    }
+
+    /// Move the cursor to the start of the next row, clearing the current guess.
+    ///
+    /// This works even if the board is locked, so that frontends can lock the board as soon as a
+    /// guess is submitted and move on once it's accepted.
+    ///
+    /// # Errors
+    ///
+    /// If there are no rows left, then we return [`CursorError::NoRowsLeft`].
+    pub fn next_row(&mut self) -> Result<(), CursorError> {
+        if self.is_board_full() {
+            return Err(CursorError::NoRowsLeft);
+        }
+
+        self.current_row += 1;
+        self.current_guess.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typing_and_deleting() {
+        let mut board = BoardState::default();
+
+        assert_eq!(board.pop(), None);
+        assert_eq!(board.guess(), Err(CursorError::RowIncomplete));
+
+        for c in "crane".chars() {
+            assert_eq!(board.push(c), Ok(()));
+        }
+        assert_eq!(board.current_column(), 5);
+        assert_eq!(board.push('S'), Err(CursorError::RowFull));
+        assert_eq!(board.guess(), Ok("CRANE".to_string()));
+
+        assert_eq!(board.pop(), Some('E'));
+        assert_eq!(board.push('1'), Err(CursorError::InvalidCharacter));
+        assert_eq!(board.push('é'), Err(CursorError::InvalidCharacter));
+        assert_eq!(board.current_guess(), &['C', 'R', 'A', 'N']);
+
+        assert!(board.clear());
+        assert!(!board.clear());
+        assert_eq!(board.current_column(), 0);
+    }
+
+    #[test]
+    fn moving_through_rows() {
+        let mut board = BoardState::new(2, 3);
+
+        for _ in 0..2 {
+            for c in "abc".chars() {
+                board.push(c).unwrap();
+            }
+            assert_eq!(board.guess(), Ok("ABC".to_string()));
+            assert_eq!(board.next_row(), Ok(()));
+            assert_eq!(board.current_guess(), &[]);
+        }
+
+        assert!(board.is_board_full());
+        assert_eq!(board.current_row(), 2);
+        assert_eq!(board.push('a'), Err(CursorError::NoRowsLeft));
+        assert_eq!(board.guess(), Err(CursorError::NoRowsLeft));
+        assert_eq!(board.next_row(), Err(CursorError::NoRowsLeft));
+    }
+
+    #[test]
+    fn locking() {
+        let mut board = BoardState::default();
+        for c in "cran".chars() {
+            board.push(c).unwrap();
+        }
+
+        board.lock();
+        assert!(board.is_locked());
+        assert_eq!(board.push('e'), Err(CursorError::Locked));
+        assert_eq!(board.pop(), None);
+        assert!(!board.clear());
+        assert_eq!(board.guess(), Err(CursorError::Locked));
+        assert_eq!(board.current_guess(), &['C', 'R', 'A', 'N']);
+
+        board.unlock();
+        assert!(!board.is_locked());
+        assert_eq!(board.push('e'), Ok(()));
+        assert_eq!(board.guess(), Ok("CRANE".to_string()));
+
+        board.lock();
+        assert_eq!(board.next_row(), Ok(()));
+        assert_eq!(board.current_row(), 1);
+        assert!(board.is_locked());
+    }
+}
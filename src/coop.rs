@@ -0,0 +1,270 @@
+//! This module handles co-operative games, where several players take turns guessing on one board.
+
+use super::{theme::Theme, Game, GuessError, Word};
+use thiserror::Error;
+
+/// An enum representing possible errors from guessing in a [`CoopGame`].
+#[derive(Debug, Error, PartialEq)]
+pub enum CoopError {
+    /// A co-op game needs at least one player.
+    #[error("A co-op game needs at least one player")]
+    NoPlayers,
+
+    /// The player isn't part of this game.
+    #[error("{0:?} is not playing in this game")]
+    UnknownPlayer(String),
+
+    /// It's another player's turn, whose name is contained in this variant.
+    #[error("It's {0}'s turn")]
+    NotYourTurn(String),
+
+    /// The guess itself was invalid.
+    #[error(transparent)]
+    Guess(#[from] GuessError),
+}
+
+/// A single guess in a [`CoopGame`], along with the player who made it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoopGuess {
+    /// The name of the player who made the guess.
+    pub player: String,
+
+    /// The scored guess.
+    pub word: Word,
+}
+
+/// A game of Wordle where several named players take turns to guess.
+///
+/// Players guess in the order they were given, and the game keeps track of who made each guess.
+/// An invalid guess doesn't use up the player's turn.
+///
+/// With the `serde` feature, deserializing checks that there's at least one player and that the
+/// current player is one of them, just like [`CoopGame::new`]. It also checks that the history
+/// has exactly the guesses of the underlying game, each made by one of the players.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CoopGameData"))]
+pub struct CoopGame {
+    /// The underlying game.
+    game: Game,
+
+    /// The names of the players, in turn order.
+    players: Vec<String>,
+
+    /// The index of the player whose turn it is.
+    current_player: usize,
+
+    /// Every valid guess made so far, in order.
+    history: Vec<CoopGuess>,
+}
+
+/// The fields of a [`CoopGame`] as they're deserialized, before they've been checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CoopGameData {
+    game: Game,
+    players: Vec<String>,
+    current_player: usize,
+    history: Vec<CoopGuess>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CoopGameData> for CoopGame {
+    type Error = &'static str;
+
+    fn try_from(data: CoopGameData) -> Result<Self, Self::Error> {
+        if data.players.is_empty() {
+            Err("a co-op game needs at least one player")
+        } else if data.current_player >= data.players.len() {
+            Err("the current player is not playing in this game")
+        } else if !data
+            .history
+            .iter()
+            .map(|guess| &guess.word)
+            .eq(&data.game.guess_history)
+        {
+            Err("the history doesn't match the guesses of the game")
+        } else if !data
+            .history
+            .iter()
+            .all(|guess| data.players.contains(&guess.player))
+        {
+            Err("a guess in the history was made by someone not playing in this game")
+        } else {
+            Ok(Self {
+                game: data.game,
+                players: data.players,
+                current_player: data.current_player,
+                history: data.history,
+            })
+        }
+    }
+}
+
+impl CoopGame {
+    /// Create a co-op game with the given players, in turn order.
+    ///
+    /// # Errors
+    ///
+    /// If there are no players, we return [`CoopError::NoPlayers`].
+    pub fn new(game: Game, players: Vec<String>) -> Result<Self, CoopError> {
+        if players.is_empty() {
+            return Err(CoopError::NoPlayers);
+        }
+
+        Ok(Self {
+            game,
+            players,
+            current_player: 0,
+            history: Vec::new(),
+        })
+    }
+
+    /// Get the underlying game.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Get the names of the players, in turn order.
+    pub fn players(&self) -> &[String] {
+        &self.players
+    }
+
+    /// Get the name of the player whose turn it is.
+    pub fn current_player(&self) -> &str {
+        &self.players[self.current_player]
+    }
+
+    /// Get every valid guess made so far, in order.
+    pub fn history(&self) -> &[CoopGuess] {
+        &self.history
+    }
+
+    /// Get every valid guess made by the given player, in order.
+    pub fn guesses_by<'a>(&'a self, player: &'a str) -> impl Iterator<Item = &'a CoopGuess> + 'a {
+        self.history
+            .iter()
+            .filter(move |guess| guess.player == player)
+    }
+
+    /// Render the finished game as share text with the [`Classic`](Theme::CLASSIC) theme. See
+    /// [`share_string_with`](CoopGame::share_string_with).
+    pub fn share_string(&self, hide_letters: bool) -> Option<String> {
+        self.share_string_with(hide_letters, &Theme::CLASSIC)
+    }
+
+    /// Render the finished game as share text, like [`Game::share_string_with`], but with the
+    /// name of the player who made each guess at the end of its row:
+    ///
+    /// ```text
+    /// Wordle 2/6
+    ///
+    /// ⬜⬜⬜🟨⬜ Alice
+    /// 🟩🟩🟩🟩🟩 Bob
+    /// ```
+    ///
+    /// If the game is still in progress, we return [`None`].
+    pub fn share_string_with(&self, hide_letters: bool, theme: &Theme) -> Option<String> {
+        let text = self.game.share_string_with(hide_letters, theme)?;
+        let (header, rows) = text.split_once("\n\n")?;
+
+        let rows: Vec<String> = rows
+            .lines()
+            .zip(&self.history)
+            .map(|(row, guess)| format!("{row} {}", guess.player))
+            .collect();
+
+        Some(format!("{header}\n\n{}", rows.join("\n")))
+    }
+
+    /// Make a guess as the given player, and move on to the next player's turn if it's valid.
+    ///
+    /// # Errors
+    ///
+    /// If the player isn't in this game or it isn't their turn, we return the appropriate
+    /// [`CoopError`] variant. If the guess is invalid, we return [`CoopError::Guess`]. See
+    /// [`Game::make_guess`].
+    pub fn make_guess(&mut self, player: &str, guess: &str) -> Result<Word, CoopError> {
+        if !self.players.iter().any(|p| p == player) {
+            return Err(CoopError::UnknownPlayer(player.to_string()));
+        } else if player != self.current_player() {
+            return Err(CoopError::NotYourTurn(self.current_player().to_string()));
+        }
+
+        let word = self.game.make_guess(guess)?;
+
+        self.history.push(CoopGuess {
+            player: player.to_string(),
+            word,
+        });
+        self.current_player = (self.current_player + 1) % self.players.len();
+
+        Ok(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn players_take_turns() {
+        assert_eq!(
+            CoopGame::new(Game::new(), Vec::new()),
+            Err(CoopError::NoPlayers)
+        );
+
+        let mut game = CoopGame::new(Game::daily(0), vec!["Alice".to_string(), "Bob".to_string()])
+            .expect("A game with players should be created");
+
+        assert_eq!(game.current_player(), "Alice");
+        assert_eq!(
+            game.make_guess("Bob", "WORDY"),
+            Err(CoopError::NotYourTurn("Alice".to_string()))
+        );
+        assert_eq!(
+            game.make_guess("Eve", "WORDY"),
+            Err(CoopError::UnknownPlayer("Eve".to_string()))
+        );
+
+        // An invalid guess doesn't use up Alice's turn
+        assert_eq!(
+            game.make_guess("Alice", "spurg"),
+            Err(CoopError::Guess(GuessError::InvalidWord))
+        );
+        assert!(game.make_guess("Alice", "WORDY").is_ok());
+
+        assert_eq!(game.current_player(), "Bob");
+        assert!(game.make_guess("Bob", "DUSTY").is_ok());
+        assert!(game.make_guess("Alice", "CRANE").is_ok());
+
+        let players: Vec<&str> = game.history().iter().map(|g| g.player.as_str()).collect();
+        assert_eq!(players, vec!["Alice", "Bob", "Alice"]);
+        assert_eq!(game.guesses_by("Alice").count(), 2);
+        assert_eq!(game.guesses_by("Bob").count(), 1);
+    }
+
+    #[test]
+    fn share_string_names_players() {
+        let mut game = CoopGame::new(
+            Game::with_answer("DYSON").expect("DYSON should be a valid answer"),
+            vec!["Alice".to_string(), "Bob".to_string()],
+        )
+        .expect("A game with players should be created");
+
+        assert!(game.make_guess("Alice", "CRANE").is_ok());
+        assert_eq!(game.share_string(true), None);
+
+        assert!(game.make_guess("Bob", "DYSON").is_ok());
+        assert_eq!(
+            game.share_string(true).expect("The game should be won"),
+            "Wordle 2/6\n\n⬜⬜⬜🟨⬜ Alice\n🟩🟩🟩🟩🟩 Bob"
+        );
+        assert_eq!(
+            game.share_string_with(false, &Theme::DARK)
+                .expect("The game should be won"),
+            "Wordle 2/6\n\n⬛⬛⬛🟨⬛ CRANE Alice\n🟩🟩🟩🟩🟩 DYSON Bob"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! This module handles choosing the daily word deterministically.
+//!
+//! Every frontend needs to agree on the daily word forever, regardless of platform, Rust version,
+//! or dependency versions. That rules out things like [`HashMap`](std::collections::HashMap)'s
+//! hasher or a seeded [`rand`] generator, whose output is allowed to change between versions.
+//!
+//! Instead, the mapping is specified exactly here:
+//!
+//! 1. A day is identified by its *epoch day*, which is the number of whole days since 1970-01-01
+//!    in UTC. See [`epoch_day`].
+//! 2. The epoch day is hashed with [`splitmix64`].
+//! 3. The hash modulo the length of [`GOOD_WORDS`] is the index of the daily word in that list.
+//!
+//! [`HASH_TEST_VECTORS`] and [`DAILY_WORD_TEST_VECTORS`] can be used to check other
+//! implementations of this mapping against this one.
+
+use super::valid_words::{FALLBACK_WORD, GOOD_WORDS};
+
+/// The number of seconds in a day.
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Pairs of inputs and outputs of [`splitmix64`].
+pub const HASH_TEST_VECTORS: [(u64, u64); 5] = [
+    (0, 0xe220a8397b1dcdaf),
+    (1, 0x910a2dec89025cc1),
+    (2, 0x975835de1c9756ce),
+    (19358, 0x04f9bf393c9223b8),
+    (u64::MAX, 0xe4d971771b652c20),
+];
+
+/// Pairs of epoch days and their daily words, according to [`daily_word`].
+///
+/// These only hold for the current [`GOOD_WORDS`] list.
+pub const DAILY_WORD_TEST_VECTORS: [(u64, &str); 5] = [
+    (0, "HUNCH"),
+    (1, "SAUNA"),
+    (19000, "CHALK"),
+    (19358, "AWAKE"),
+    (20000, "NORTH"),
+];
+
+/// Hash the given number with the SplitMix64 algorithm.
+///
+/// This is the output function of Sebastiano Vigna's SplitMix64 generator, applied once to the
+/// input plus the golden gamma constant. All arithmetic wraps on overflow.
+pub const fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Get the epoch day of the given Unix timestamp in seconds, which is the number of whole days
+/// since 1970-01-01 in UTC.
+pub const fn epoch_day(unix_seconds: u64) -> u64 {
+    unix_seconds / SECONDS_PER_DAY
+}
+
+/// Get the index into [`GOOD_WORDS`] of the daily word for the given epoch day.
+pub fn daily_word_index(day: u64) -> usize {
+    // The remainder is less than the list length, so it always fits in a usize
+    splitmix64(day)
+        .checked_rem(GOOD_WORDS.len() as u64)
+        .unwrap_or(0) as usize
+}
+
+/// Get the daily word for the given epoch day.
+pub fn daily_word(day: u64) -> &'static str {
+    GOOD_WORDS
+        .get(daily_word_index(day))
+        .unwrap_or(&FALLBACK_WORD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splitmix64_test_vectors() {
+        for (input, output) in HASH_TEST_VECTORS {
+            assert_eq!(splitmix64(input), output, "splitmix64({input})");
+        }
+    }
+
+    #[test]
+    fn daily_word_test_vectors() {
+        for (day, word) in DAILY_WORD_TEST_VECTORS {
+            assert_eq!(daily_word(day), word, "daily_word({day})");
+        }
+    }
+
+    #[test]
+    fn epoch_day_boundaries() {
+        assert_eq!(epoch_day(0), 0);
+        assert_eq!(epoch_day(SECONDS_PER_DAY - 1), 0);
+        assert_eq!(epoch_day(SECONDS_PER_DAY), 1);
+
+        // 2023-01-01T00:00:00Z
+        assert_eq!(epoch_day(1_672_531_200), 19358);
+    }
+}
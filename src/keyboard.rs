@@ -0,0 +1,194 @@
+//! This module handles the keyboard, which shows the best position that each letter has been seen
+//! in, and the [`Layout`]s that it can be drawn with.
+//!
+//! Frontends draw the keyboard with [`Keyboard::iter_rows`], so the layouts only live here.
+
+use super::{letters::Position, ordered_position::OrderedPosition, valid_words::ALPHABET, Word};
+use std::fmt;
+
+/// A keyboard layout, which decides the order of the keys in each row.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Layout {
+    /// The standard English layout.
+    #[default]
+    Qwerty,
+
+    /// The standard French layout.
+    Azerty,
+}
+
+impl Layout {
+    /// Every layout, in the order they should be shown in settings.
+    pub const ALL: [Self; 2] = [Self::Qwerty, Self::Azerty];
+
+    /// Get the short name of the layout, which is used in settings files.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Qwerty => "qwerty",
+            Self::Azerty => "azerty",
+        }
+    }
+
+    /// Get the layout with the given [`name`](Layout::name), if there is one.
+    pub fn by_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|layout| layout.name() == name)
+    }
+
+    /// Get the rows of the layout from top to bottom, as uppercase letters.
+    ///
+    /// Every letter in [`ALPHABET`] is in exactly one row.
+    pub fn rows(self) -> [&'static str; 3] {
+        match self {
+            Self::Qwerty => ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"],
+            Self::Azerty => ["AZERTYUIOP", "QSDFGHJKLM", "WXCVBN"],
+        }
+    }
+}
+
+/// The best position that each letter has been seen in, like
+/// [`Game::keyboard`](super::Game::keyboard).
+///
+/// The keyboard has a key for every uppercase letter in [`ALPHABET`], which is [`None`] if it
+/// hasn't been guessed yet. Otherwise, [`NotInWord`](Position::NotInWord) is the lowest position,
+/// then [`WrongPosition`](Position::WrongPosition), and then [`Correct`](Position::Correct).
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyboard {
+    /// The best position of each letter, in the same order as [`ALPHABET`].
+    positions: [Option<Position>; 26],
+}
+
+impl fmt::Debug for Keyboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl Keyboard {
+    /// Create a keyboard where no keys have been guessed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compute the keyboard of a game that has had the given guesses, in order.
+    ///
+    /// The keyboard only depends on the guesses, so this always matches
+    /// [`Game::keyboard`](super::Game::keyboard) after the same guesses have been made. That means
+    /// a keyboard can be rebuilt from a saved or replayed history rather than being stored
+    /// alongside it.
+    pub fn from_history(guesses: &[Word]) -> Self {
+        let mut keyboard = Self::new();
+        for word in guesses {
+            keyboard.merge(word);
+        }
+        keyboard
+    }
+
+    /// Get the index of the given letter in [`ALPHABET`], ignoring case.
+    fn index(letter: char) -> Option<usize> {
+        let letter = letter.to_ascii_uppercase();
+        ALPHABET.iter().position(|&c| c == letter)
+    }
+
+    /// Get the key for the given letter, ignoring case.
+    ///
+    /// If there's no key for the letter, because it isn't in [`ALPHABET`], we return [`None`].
+    /// Otherwise, we return the [`best_position`](Keyboard::best_position) of the key.
+    pub fn get(&self, letter: char) -> Option<Option<Position>> {
+        Self::index(letter).and_then(|index| self.positions.get(index).copied())
+    }
+
+    /// Get the best position that the given letter has been seen in, ignoring case.
+    ///
+    /// If the letter hasn't been guessed yet, or there's no key for it, this is [`None`].
+    pub fn best_position(&self, letter: char) -> Option<Position> {
+        self.get(letter).flatten()
+    }
+
+    /// Update the keys according to the positions of the letters in the given guess, keeping the
+    /// best position known for each letter.
+    ///
+    /// Letters that aren't in [`ALPHABET`] are ignored.
+    pub fn merge(&mut self, word: &Word) {
+        for letter in word {
+            if let Some(current) =
+                Self::index(letter.letter).and_then(|index| self.positions.get_mut(index))
+            {
+                if OrderedPosition(Some(letter.position)) > OrderedPosition(*current) {
+                    *current = Some(letter.position);
+                }
+            }
+        }
+    }
+
+    /// Iterate over every key in alphabetical order, with its best position.
+    pub fn iter(&self) -> impl Iterator<Item = (char, Option<Position>)> + '_ {
+        ALPHABET.into_iter().zip(self.positions)
+    }
+
+    /// Iterate over the rows of the given layout from top to bottom, where each row is an iterator
+    /// over its keys from left to right, with their best positions.
+    pub fn iter_rows(
+        &self,
+        layout: Layout,
+    ) -> impl Iterator<Item = impl Iterator<Item = (char, Option<Position>)> + '_> + '_ {
+        layout
+            .rows()
+            .into_iter()
+            .map(|row| row.chars().map(|key| (key, self.best_position(key))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{letters::Letter, scoring::score_guess};
+
+    #[test]
+    fn layouts_have_every_letter() {
+        for layout in Layout::ALL {
+            let mut letters: Vec<char> = layout.rows().concat().chars().collect();
+            letters.sort();
+            assert_eq!(letters, ALPHABET);
+            assert_eq!(Layout::by_name(layout.name()), Some(layout));
+        }
+        assert_eq!(Layout::by_name("dvorak"), None);
+    }
+
+    #[test]
+    fn merge_keeps_best_position() {
+        let mut keyboard = Keyboard::new();
+        assert!(keyboard.iter().all(|(_, position)| position.is_none()));
+
+        keyboard.merge(&score_guess("DYSON", "DRONE").unwrap());
+        assert_eq!(keyboard.best_position('O'), Some(Position::WrongPosition));
+        keyboard.merge(&score_guess("DYSON", "NYLON").unwrap());
+
+        assert_eq!(keyboard.best_position('D'), Some(Position::Correct));
+        assert_eq!(keyboard.best_position('o'), Some(Position::Correct));
+        assert_eq!(keyboard.best_position('N'), Some(Position::Correct));
+        assert_eq!(keyboard.best_position('R'), Some(Position::NotInWord));
+        assert_eq!(keyboard.get('Q'), Some(None));
+        assert_eq!(keyboard.get('é'), None);
+        assert_eq!(keyboard.best_position('é'), None);
+
+        keyboard.merge(&[Letter::new('é', Position::Correct); 5]);
+        assert_eq!(keyboard.get('é'), None);
+    }
+
+    #[test]
+    fn iter_rows_follows_layout() {
+        let keyboard = Keyboard::from_history(&[score_guess("DYSON", "QUART").unwrap()]);
+
+        let rows: Vec<Vec<(char, Option<Position>)>> = keyboard
+            .iter_rows(Layout::Azerty)
+            .map(Iterator::collect)
+            .collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0][0], ('A', Some(Position::NotInWord)));
+        assert_eq!(rows[0][1], ('Z', None));
+        assert_eq!(rows[1][0], ('Q', Some(Position::NotInWord)));
+        assert_eq!(rows[2].len(), 6);
+    }
+}
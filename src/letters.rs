@@ -1,7 +1,11 @@
 //! This module handles the concept of letters and their associated positions.
 
+use super::Word;
+use std::fmt;
+
 /// A letter with an associated [`Position`] in the word.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Letter {
     /// The actual character that this Letter wraps.
     pub letter: char,
@@ -12,6 +16,7 @@ pub struct Letter {
 
 /// A position in the word.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Position {
     /// The letter doesn't appear in the word at all, or all the instances of that letter have
     /// already been placed in the word.
@@ -45,8 +50,8 @@ impl Letter {
     /// know enough context to figure it out.
     ///
     /// The context we need is the target word and the rest of the guess, and the logic for working
-    /// it out is in [`Game::make_guess`](super::Game::make_guess).
-    pub fn simple_check_letter_pair(
+    /// it out is in [`scoring`](super::scoring).
+    pub(crate) fn simple_check_letter_pair(
         letter: &char,
         expected_letter: &char,
         word: &str,
@@ -65,3 +70,67 @@ impl Letter {
         })
     }
 }
+
+/// What's known about how many times a letter appears in the target word.
+///
+/// This comes from the way that duplicate letters are scored. If a guess has a letter highlighted
+/// twice, then the word has at least two of that letter. If a guess also has that letter
+/// not highlighted, then the word has exactly as many as were highlighted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LetterCount {
+    /// The minimum number of times the letter appears in the word.
+    pub at_least: usize,
+
+    /// Whether [`at_least`](LetterCount::at_least) is the exact number of times the letter
+    /// appears in the word.
+    pub exact: bool,
+}
+
+impl fmt::Display for LetterCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.exact {
+            write!(f, "exactly {}", self.at_least)
+        } else {
+            write!(f, "at least {}", self.at_least)
+        }
+    }
+}
+
+impl LetterCount {
+    /// Work out what the given scored guess says about the count of the given letter.
+    ///
+    /// If the letter isn't in the guess, then the guess says nothing about it, so we return
+    /// [`None`].
+    ///
+    /// This assumes that the guess was scored with the
+    /// [`Classic`](super::scoring::ScoringRules::Classic) rules.
+    pub fn from_guess(word: &Word, letter: char) -> Option<Self> {
+        let letter = letter.to_ascii_uppercase();
+
+        let mut seen = false;
+        let mut highlighted = 0;
+        let mut not_highlighted = false;
+
+        for l in word.iter().filter(|l| l.letter == letter) {
+            seen = true;
+            match l.position {
+                Position::Correct | Position::WrongPosition => highlighted += 1,
+                Position::NotInWord => not_highlighted = true,
+            }
+        }
+
+        seen.then_some(Self {
+            at_least: highlighted,
+            exact: not_highlighted,
+        })
+    }
+
+    /// Combine what two guesses say about the count of the same letter.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            at_least: self.at_least.max(other.at_least),
+            exact: self.exact || other.exact,
+        }
+    }
+}
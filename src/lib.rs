@@ -2,56 +2,244 @@
 //!
 //! A library to handle the backend details of standard Wordle games.
 //! See [the New York Times' Wordle](https://www.nytimes.com/games/wordle/index.html).
+//!
+//! The library never panics, whatever input it's given, since a panic aborts the whole app when
+//! running in WASM. Invalid input is reported with an error instead. This is enforced with Clippy
+//! lints outside of tests.
+
+#![cfg_attr(
+    not(test),
+    deny(
+        clippy::expect_used,
+        clippy::panic,
+        clippy::todo,
+        clippy::unimplemented,
+        clippy::unreachable,
+        clippy::unwrap_used
+    )
+)]
 
+pub mod analysis;
+pub mod analytics;
+pub mod board;
+pub mod coop;
+pub mod daily;
+pub mod keyboard;
 pub mod letters;
+pub mod messages;
+pub mod normalize;
+pub mod phrase;
+pub mod policy;
+pub mod pools;
+pub mod puzzle;
+#[cfg(feature = "render")]
+pub mod render;
+pub mod replay;
+pub mod rules;
+pub mod scoring;
+pub mod session;
+pub mod solver;
+pub mod spoiler;
+pub mod theme;
+pub mod time_attack;
 pub mod valid_words;
+pub mod validator;
+pub mod word_list;
 
 pub mod prelude {
     //! This module just re-exports some commonly used types.
 
-    pub use super::letters::{Letter, Position};
-    pub use super::{Game, GuessError, Word};
+    pub use super::board::{BoardState, CursorError};
+    pub use super::keyboard::{Keyboard, Layout};
+    pub use super::letters::{Letter, LetterCount, Position};
+    pub use super::messages::{English, Messages};
+    pub use super::policy::{GamePolicy, GuessBudget};
+    pub use super::scoring::ScoringRules;
+    pub use super::{Game, GuessError, Word, MAX_GUESSES};
 }
 
-use letters::{Letter, Position};
+use keyboard::Keyboard;
+use letters::{Letter, LetterCount, Position};
+use messages::{English, Messages};
+use policy::{GamePolicy, GuessBudget};
+use pools::AnswerPools;
+use puzzle::PuzzleId;
 use rand::seq::SliceRandom;
-use std::{cmp::Ordering, collections::HashMap};
+use scoring::ScoringRules;
+use spoiler::SpoilerGuard;
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    time::{Duration, Instant},
+};
 use thiserror::Error;
+use word_list::{WordList, WordLists};
 
 /// A word is just an array of 5 [`Letter`]s.
 pub type Word = [Letter; 5];
 
+/// The most guesses that a game can take before it's lost.
+pub const MAX_GUESSES: usize = 6;
+
+/// Check whether the given guesses, in order, won the game, which means that the last guess is
+/// all [`Correct`](Position::Correct) and there are at most [`MAX_GUESSES`] guesses.
+///
+/// Guessing the answer after too many guesses doesn't count as a win.
+pub(crate) fn is_won(history: &[Word]) -> bool {
+    history.len() <= MAX_GUESSES
+        && history.last().is_some_and(|word| {
+            word.iter()
+                .all(|letter| letter.position == Position::Correct)
+        })
+}
+
 /// An enum representing possible errors resulting from an invalid guess.
+///
+/// The [`Display`](fmt::Display) messages come from [`English`]. Use a different [`Messages`]
+/// implementation to show them in another language.
 #[derive(Debug, Error, PartialEq)]
 pub enum GuessError {
     /// The guess must be exclusively ASCII characters.
     ///
     /// This is just because the word list is exclusively ASCII characters.
-    #[error("Guess must be exclusively ASCII characters")]
+    #[error("{}", English.guess_error(self))]
     IncludesNonAscii,
 
     /// The guess must be in the [`VALID_WORDS`](valid_words::VALID_WORDS) list.
-    #[error("Guess must be a valid word")]
+    #[error("{}", English.guess_error(self))]
     InvalidWord,
 
     /// The guess must be exactly 5 letters.
-    #[error("Guess must be exactly 5 letters")]
+    #[error("{}", English.guess_error(self))]
     WrongWordLength,
+
+    /// The guess was made too soon after the previous one.
+    ///
+    /// This can only happen if the game's [`GamePolicy`] has a
+    /// [`min_guess_interval`](GamePolicy::min_guess_interval), which is contained in this variant.
+    #[error("{}", English.guess_error(self))]
+    TooFast(Duration),
+
+    /// The client has made too many guess attempts recently.
+    ///
+    /// This can only happen when using [`make_guess_limited`](Game::make_guess_limited).
+    #[error("{}", English.guess_error(self))]
+    TooManyAttempts,
+
+    /// The game's [`GamePolicy`] doesn't allow this word as the first guess.
+    #[error("{}", English.guess_error(self))]
+    BannedOpener,
+
+    /// The game's [`GamePolicy`] requires the first guess to have at least this many vowels.
+    #[error("{}", English.guess_error(self))]
+    TooFewVowels(usize),
+
+    /// The guess has already been made, and the game's [`GamePolicy`] rejects repeated guesses.
+    #[error("{}", English.guess_error(self))]
+    RepeatedGuess,
+
+    /// The game's [`GamePolicy`] is in hard mode, and the guess doesn't keep a letter that's known
+    /// to be correct in place. This contains the letter and its position, starting from 1.
+    #[error("{}", English.guess_error(self))]
+    MissingCorrectLetter(char, usize),
+
+    /// The game's [`GamePolicy`] is in hard mode, and the guess doesn't use a letter that's known
+    /// to be in the answer, as many times as it's known to be there.
+    #[error("{}", English.guess_error(self))]
+    MissingLetter(char),
+}
+
+/// An enum representing possible errors from creating a game with a chosen answer. See
+/// [`Game::with_answer`].
+#[derive(Debug, Error, PartialEq)]
+pub enum AnswerError {
+    /// The answer isn't a valid guess, so it couldn't be guessed.
+    #[error("{0:?} can't be an answer: {1}")]
+    InvalidWord(String, #[source] GuessError),
 }
 
 /// A game of Wordle.
-#[derive(Clone, Debug, PartialEq)]
+///
+/// The [`Debug`](fmt::Debug) implementation redacts the target word so that it can't leak into
+/// logs by accident. Enable the `reveal` feature to show it. The guess history is always left out,
+/// since the last guess of a won game is the target word.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
     /// The target word that the user needs to guess.
-    pub word: String,
+    ///
+    /// This is wrapped in a [`SpoilerGuard`], so frontends have to call
+    /// [`reveal_for_display`](SpoilerGuard::reveal_for_display) to show it.
+    pub word: SpoilerGuard<String>,
+
+    /// The best position that each letter has been seen in previously.
+    pub keyboard: Keyboard,
+
+    /// This hashmap maps letters to what's known about how many times they appear in the target
+    /// word, based on duplicate letters in previous guesses.
+    ///
+    /// Letters that haven't been guessed yet aren't in this map. Guesses are only counted if they
+    /// were scored with the [`Classic`](ScoringRules::Classic) rules, since other rules don't
+    /// necessarily reveal letter counts.
+    pub letter_counts: HashMap<char, LetterCount>,
+
+    /// The policy that restricts how this game can be played.
+    ///
+    /// This is unrestricted by default.
+    pub policy: GamePolicy,
+
+    /// The rules used to score guesses.
+    ///
+    /// These are the [`Classic`](scoring::ScoringRules::Classic) rules by default.
+    pub scoring_rules: ScoringRules,
+
+    /// The lists that accepted guesses come from.
+    ///
+    /// These are the [`Builtin`](WordLists::Builtin) lists by default. See
+    /// [`with_word_list`](Game::with_word_list).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub word_list: WordLists,
+
+    /// The puzzle that this game is playing.
+    ///
+    /// This is [`Custom`](PuzzleId::Custom) unless the game was created for a specific puzzle,
+    /// like with [`daily`](Game::daily).
+    pub puzzle: PuzzleId,
+
+    /// Every valid guess made so far, in order.
+    ///
+    /// This is enough to redraw the board of a restored game.
+    pub guess_history: Vec<Word>,
 
-    /// This hashmap contains all uppercase Latin letters, and maps them to the best
-    /// position that they've been seen in previously.
+    /// The time that the last valid guess was made, if the policy needs to know it.
     ///
-    /// If they have not been guessed previously, this is [`None`], otherwise
-    /// [`NotInWord`](Position::NotInWord) is the lowest position, then
-    /// [`WrongPosition`](Position::WrongPosition), and then [`Correct`](Position::Correct).
-    pub keyboard: HashMap<char, Option<Position>>,
+    /// This isn't serialized, since an [`Instant`] only makes sense in the process that created it.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_guess_time: Option<Instant>,
+}
+
+impl fmt::Debug for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let word = self.word.reveal_for_display();
+        let redacted = "*".repeat(word.chars().count());
+        let word: &dyn fmt::Debug = if cfg!(feature = "reveal") {
+            &word
+        } else {
+            &redacted
+        };
+
+        f.debug_struct("Game")
+            .field("word", word)
+            .field("keyboard", &self.keyboard)
+            .field("letter_counts", &self.letter_counts)
+            .field("policy", &self.policy)
+            .field("scoring_rules", &self.scoring_rules)
+            .field("word_list", &self.word_list)
+            .field("puzzle", &self.puzzle)
+            .field("last_guess_time", &self.last_guess_time)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Default for Game {
@@ -60,31 +248,202 @@ impl Default for Game {
     }
 }
 
+/// See [`Game::with_answer`].
+impl TryFrom<&str> for Game {
+    type Error = AnswerError;
+
+    fn try_from(answer: &str) -> Result<Self, Self::Error> {
+        Self::with_answer(answer)
+    }
+}
+
 impl Game {
     /// Create a game by choosing a random target word from [`GOOD_WORDS`](valid_words::GOOD_WORDS).
-    ///
-    /// This constructor also ensures that the [`keyboard`](Game::keyboard) contains all uppercase
-    /// Latin letters, and initially maps them all to [`None`]. See
-    /// [`new_keyboard_map`](Game::new_keyboard_map).
     pub fn new() -> Self {
         Self {
             word: {
-                let word = *valid_words::GOOD_WORDS
+                let word = valid_words::GOOD_WORDS
                     .choose(&mut rand::thread_rng())
-                    .expect("valid_words::GOOD_WORDS should never be empty");
-                word.to_string().to_ascii_uppercase()
+                    .unwrap_or(&valid_words::FALLBACK_WORD);
+                SpoilerGuard::new(word.to_ascii_uppercase())
             },
-            keyboard: Self::new_keyboard_map(),
+            keyboard: Keyboard::new(),
+            letter_counts: HashMap::new(),
+            policy: GamePolicy::default(),
+            scoring_rules: ScoringRules::default(),
+            word_list: WordLists::Builtin,
+            puzzle: PuzzleId::Custom,
+            guess_history: Vec::new(),
+            last_guess_time: None,
+        }
+    }
+
+    /// Create a game with the given target word, for tests and custom challenges.
+    ///
+    /// The answer can be in any case, and can be any word in
+    /// [`VALID_WORDS`](valid_words::VALID_WORDS), not just the ones in
+    /// [`GOOD_WORDS`](valid_words::GOOD_WORDS). This is also available as
+    /// [`TryFrom<&str>`](TryFrom).
+    ///
+    /// # Errors
+    ///
+    /// If the answer isn't a valid guess, we return [`AnswerError::InvalidWord`] with the reason
+    /// from [`is_valid_guess`](Game::is_valid_guess).
+    pub fn with_answer(answer: &str) -> Result<Self, AnswerError> {
+        Self::is_valid_guess(answer)
+            .map_err(|error| AnswerError::InvalidWord(answer.to_string(), error))?;
+
+        Ok(Self {
+            word: SpoilerGuard::new(answer.to_ascii_uppercase()),
+            ..Self::new()
+        })
+    }
+
+    /// Create a game for the given epoch day, where the target word is the daily word.
+    ///
+    /// Every game created for the same day has the same target word. See the [`daily`] module
+    /// for exactly how the word is chosen.
+    pub fn daily(day: u64) -> Self {
+        Self {
+            word: SpoilerGuard::new(daily::daily_word(day).to_ascii_uppercase()),
+            puzzle: PuzzleId::Daily(day),
+            ..Self::new()
+        }
+    }
+
+    /// Create a game for the given epoch day in the given region, where the target word is the
+    /// daily word from the active answer pool.
+    ///
+    /// If no pool is active, this is the same as [`daily`](Game::daily). See
+    /// [`AnswerPools::daily_word`]. Pools are opt-in, so [`daily`](Game::daily) itself never
+    /// consults them.
+    pub fn daily_with_pools(day: u64, region: Option<&str>, pools: &AnswerPools) -> Self {
+        Self {
+            word: SpoilerGuard::new(pools.daily_word(day, region).to_ascii_uppercase()),
+            puzzle: PuzzleId::Daily(day),
+            ..Self::new()
+        }
+    }
+
+    /// Create a game with a random target word from the answer pool that's active on the given
+    /// epoch day in the given region.
+    ///
+    /// If no pool is active, this is the same as [`new`](Game::new). See
+    /// [`AnswerPools::random_word`]. Pools are opt-in, so [`new`](Game::new) itself never
+    /// consults them.
+    pub fn new_with_pools(day: u64, region: Option<&str>, pools: &AnswerPools) -> Self {
+        Self {
+            word: SpoilerGuard::new(pools.random_word(day, region).to_ascii_uppercase()),
+            ..Self::new()
+        }
+    }
+
+    /// Create a game with a random target word from the answers of the given word list, which
+    /// only accepts guesses from that list.
+    ///
+    /// If the list has no answers, the target word is chosen like [`new`](Game::new).
+    pub fn with_word_list(list: impl WordList + Send + Sync + 'static) -> Self {
+        let game = Self::new();
+        let word = list
+            .answers()
+            .choose(&mut rand::thread_rng())
+            .map(|word| SpoilerGuard::new(word.to_ascii_uppercase()))
+            .unwrap_or(game.word.clone());
+
+        Self {
+            word,
+            word_list: WordLists::Custom(std::sync::Arc::new(list)),
+            ..game
         }
     }
 
-    /// Create an empty keyboard map.
-    pub fn new_keyboard_map() -> HashMap<char, Option<Position>> {
-        let mut map = HashMap::new();
-        for c in valid_words::ALPHABET {
-            map.insert(c, None);
+    /// Create a game with a random target word, like [`new`](Game::new), but restricted by the
+    /// given policy.
+    pub fn with_policy(policy: GamePolicy) -> Self {
+        Self {
+            policy,
+            ..Self::new()
         }
-        map
+    }
+
+    /// Get the [`Rules`](rules::Rules) that this game is played under.
+    pub fn rules(&self) -> rules::Rules {
+        rules::Rules::for_game(self)
+    }
+
+    /// Summarise what's known about the answer from the guesses made so far, like the confirmed
+    /// positions, the letters known to be in the answer and how many times, and the positions that
+    /// each letter is known not to be in.
+    ///
+    /// This comes from the [`Constraints`](analysis::Constraints) of the
+    /// [`guess_history`](Game::guess_history), so it only makes sense for games scored with the
+    /// [`Classic`](ScoringRules::Classic) rules.
+    pub fn knowledge(&self) -> analysis::Knowledge {
+        analysis::Constraints::from_history(&self.guess_history).knowledge()
+    }
+
+    /// Render the finished game as share text with the [`Classic`](theme::Theme::CLASSIC) theme.
+    /// See [`share_string_with`](Game::share_string_with).
+    pub fn share_string(&self, hide_letters: bool) -> Option<String> {
+        self.share_string_with(hide_letters, &theme::Theme::CLASSIC)
+    }
+
+    /// Render the finished game as share text, like the official Wordle:
+    ///
+    /// ```text
+    /// Wordle 19358 3/6
+    ///
+    /// ⬜🟨⬜⬜⬜
+    /// 🟩⬜🟩⬜🟨
+    /// 🟩🟩🟩🟩🟩
+    /// ```
+    ///
+    /// The header has the day of a [`Daily`](PuzzleId::Daily) puzzle, and then the number of
+    /// guesses out of [`MAX_GUESSES`], or `X` if the game was lost. Each
+    /// row has the [`emoji`](theme::TileStyle::emoji) of the theme's tiles.
+    ///
+    /// A game is won if the answer was guessed within the maximum number of guesses, and lost
+    /// once that many guesses have been made without winning. If the game is still in progress,
+    /// we return [`None`], since there's no score to share yet.
+    ///
+    /// If `hide_letters` is false, each row is followed by the guessed word, which spoils the
+    /// answer of a won game.
+    pub fn share_string_with(&self, hide_letters: bool, theme: &theme::Theme) -> Option<String> {
+        let max = MAX_GUESSES;
+        let guesses = self.guess_history.len();
+        let score = if is_won(&self.guess_history) {
+            guesses.to_string()
+        } else if guesses >= max {
+            "X".to_string()
+        } else {
+            return None;
+        };
+        let header = match self.puzzle {
+            PuzzleId::Daily(day) => format!("Wordle {day} {score}/{max}"),
+            PuzzleId::Custom => format!("Wordle {score}/{max}"),
+        };
+
+        let rows: Vec<String> = self
+            .guess_history
+            .iter()
+            .map(|word| {
+                let tiles: String = word
+                    .iter()
+                    .map(|letter| theme.tile(letter.position).emoji)
+                    .collect();
+                if hide_letters {
+                    tiles
+                } else {
+                    let letters: String = word
+                        .iter()
+                        .map(|letter| letter.letter.to_ascii_uppercase())
+                        .collect();
+                    format!("{tiles} {letters}")
+                }
+            })
+            .collect();
+
+        Some(format!("{header}\n\n{}", rows.join("\n")))
     }
 
     /// Check if the guess is valid, returning `Ok(())` if it is.
@@ -110,10 +469,32 @@ impl Game {
         Ok(())
     }
 
+    /// Check if the guess is valid for this game, returning `Ok(())` if it is.
+    ///
+    /// This is like [`is_valid_guess`](Game::is_valid_guess), but checks the game's own
+    /// [`word_list`](Game::word_list), and also checks the game's [`policy`](Game::policy),
+    /// which can put extra restrictions on the first guess and on guesses after it.
+    ///
+    /// These are the same checks as a [`GuessValidator`](validator::GuessValidator) made with
+    /// [`for_game`](validator::GuessValidator::for_game).
+    ///
+    /// # Errors
+    ///
+    /// If the guess is invalid, we return the appropriate [`GuessError`] variant.
+    pub fn validate_guess(&self, guess: &str) -> Result<(), GuessError> {
+        validator::check_guess(
+            std::slice::from_ref(&self.word_list),
+            &self.policy,
+            guess,
+            &self.guess_history,
+        )
+    }
+
     /// Guess the given word against the target word.
     ///
     /// This method returns an array of five [`Letter`]s. Each Letter has a [`Position`]. As per
-    /// classic Wordle rules, the positions are calculated as follows:
+    /// classic Wordle rules, the positions are calculated as follows, although the game's
+    /// [`scoring_rules`](Game::scoring_rules) can change this:
     ///
     /// If a letter is in the word and in the correct position, then it is [`Correct`](letters::Position::Correct).
     /// If a letter is not in the word at all, then it is [`NotInWord`](letters::Position::NotInWord).
@@ -127,133 +508,133 @@ impl Game {
     /// # Errors
     ///
     /// If the guess is invalid, we return the appropriate [`GuessError`] variant. See
-    /// [`is_valid_guess`](Game::is_valid_guess).
+    /// [`validate_guess`](Game::validate_guess).
+    ///
+    /// If the game's [`policy`](Game::policy) has a minimum interval between guesses, and this guess
+    /// was made too soon after the last one, then we return [`GuessError::TooFast`].
     pub fn make_guess(&mut self, guess: &str) -> Result<Word, GuessError> {
-        Self::is_valid_guess(guess)?;
+        self.validate_guess(guess)?;
+        self.check_guess_interval()?;
 
-        let guess = guess.to_ascii_uppercase();
+        let word =
+            scoring::score_guess_with(self.word.reveal_for_display(), guess, &self.scoring_rules)?;
+        self.keyboard.merge(&word);
+        if self.scoring_rules == ScoringRules::Classic {
+            self.update_letter_counts(&word);
+        }
+        self.guess_history.push(word);
+
+        Ok(word)
+    }
+
+    /// Make a guess like [`make_guess`](Game::make_guess), where the player took `entry_duration`
+    /// to enter the guess.
+    ///
+    /// If the game's [`policy`](Game::policy) considers this entry suspiciously fast, then its
+    /// [`on_suspicious_entry`](GamePolicy::on_suspicious_entry) hook is called after making the
+    /// guess. Guesses that are rejected, whether they're invalid or too fast, never call the hook.
+    ///
+    /// # Errors
+    ///
+    /// See [`make_guess`](Game::make_guess).
+    pub fn make_timed_guess(
+        &mut self,
+        guess: &str,
+        entry_duration: Duration,
+    ) -> Result<Word, GuessError> {
+        let word = self.make_guess(guess)?;
 
-        let pairs: Vec<(char, char)> = guess.chars().zip(self.word.chars()).collect();
-
-        let optional_letters: [(char, Option<Letter>); 5] = [
-            (
-                pairs[0].0,
-                Letter::simple_check_letter_pair(&pairs[0].0, &pairs[0].1, &self.word),
-            ),
-            (
-                pairs[1].0,
-                Letter::simple_check_letter_pair(&pairs[1].0, &pairs[1].1, &self.word),
-            ),
-            (
-                pairs[2].0,
-                Letter::simple_check_letter_pair(&pairs[2].0, &pairs[2].1, &self.word),
-            ),
-            (
-                pairs[3].0,
-                Letter::simple_check_letter_pair(&pairs[3].0, &pairs[3].1, &self.word),
-            ),
-            (
-                pairs[4].0,
-                Letter::simple_check_letter_pair(&pairs[4].0, &pairs[4].1, &self.word),
-            ),
-        ];
-
-        // This maps each letter to its number of occurences in the target word
-        let mut instances_in_word_map: HashMap<char, usize> = HashMap::new();
-        for c in valid_words::ALPHABET {
-            instances_in_word_map.insert(c, self.word.chars().filter(|cc| *cc == c).count());
+        if self.policy.is_suspicious_entry(entry_duration) {
+            if let Some(hook) = &self.policy.on_suspicious_entry {
+                hook(guess, entry_duration);
+            }
         }
 
-        // Shadow to make it immutable
-        let instances_in_word_map = instances_in_word_map;
-
-        // This maps each character in the alphabet to a tuple. The first element is the number of
-        // correctly placed letters in the guess, and the second number is how many times that
-        // letter still needs to be placed in the guess
-        let mut correct_letters_map: HashMap<char, (usize, usize)> = HashMap::new();
-        for c in valid_words::ALPHABET {
-            let correct_letters = optional_letters
-                .iter()
-                .filter(|l| match l.1 {
-                    None => false,
-                    Some(ll) => ll.letter == c && ll.position == Position::Correct,
-                })
-                .count();
-            correct_letters_map.insert(c, (correct_letters, instances_in_word_map.get(&c).expect("`instances_in_word_map` should contain all letters in the Latin alphabet ({c:?})") - correct_letters));
+        Ok(word)
+    }
+
+    /// Make a guess like [`make_guess`](Game::make_guess), but only if the given budget allows it.
+    ///
+    /// Every call takes one attempt from the budget, even if the guess turns out to be invalid,
+    /// so that untrusted clients can't use the game to check arbitrary words as fast as they like.
+    ///
+    /// # Errors
+    ///
+    /// If the budget has no attempts left, we return [`GuessError::TooManyAttempts`] without
+    /// looking at the guess. Otherwise, see [`make_guess`](Game::make_guess).
+    pub fn make_guess_limited(
+        &mut self,
+        guess: &str,
+        budget: &mut GuessBudget,
+    ) -> Result<Word, GuessError> {
+        if !budget.try_take() {
+            return Err(GuessError::TooManyAttempts);
         }
 
-        let word: Word = optional_letters.map(|(orig_char, opt_letter)|
-            opt_letter.map_or_else(|| {
-                // If we get here, then the letter is either in the wrong position, or all
-                // occurences of this letter have been placed correctly already
-                let instances_in_word = instances_in_word_map.get(&orig_char).expect("`instances_in_word_map` should contain all letters in the Latin alphabet ({orig_char:?})");
-
-                let (instances_in_correct_positions_in_guess, remaining_places): &(usize, usize) =
-                    correct_letters_map.get(&orig_char).expect(
-                        "`correct_letters_map` should contain all letters in the Latin alphabet ({orig_char:?})",
-                    );
-
-                // We know how many times this letter appears in the word and in correct positions
-                // in the current guess
-                // We also know that this letter is not in the correct position, and instances_in_word > 0
-
-                match instances_in_word.cmp(instances_in_correct_positions_in_guess) {
-                    Ordering::Greater => {
-                        if *remaining_places > 0 {
-                            // The letter needs to stay in the guess, but in a different position
-                            // We also want to decrement the remaining uses of this letter
-                            correct_letters_map
-                                .get_mut(&orig_char)
-                                .expect("`correct_letters_map` should contain all letters in the Latin alphabet ({orig_char:?})")
-                                .1 -= 1;
-                            Letter::new(orig_char, Position::WrongPosition)
-                        } else {
-                            // We've used up all the remaining places for this character
-                            Letter::new(orig_char, Position::NotInWord)
-                        }
-                    }
-                    Ordering::Equal => {
-                        // We already have enough instances of this letter
-                        Letter::new(orig_char, Position::NotInWord)
-                    }
-                    Ordering::Less => unreachable!(concat!(
-                        "We cannot have more instances of the letter in the correct position ",
-                        "in the guess than there are instances in the target word"
-                    )),
-                }
-            }, |l| l)
-        );
+        self.make_guess(guess)
+    }
 
-        self.update_keyboard(&word);
+    /// Replay a recorded [`Script`](replay::Script) in a new game, normalizing each recorded guess
+    /// with the script's [`normalization`](replay::Script::normalization) and passing it to
+    /// [`make_guess`](Game::make_guess) in order.
+    ///
+    /// We return the game after every guess has been made, along with the result of each guess,
+    /// including the invalid ones. The recorded times aren't waited for, so a
+    /// [`min_guess_interval`](GamePolicy::min_guess_interval) isn't reproduced.
+    ///
+    /// # Errors
+    ///
+    /// If the script's settings can't be used to create a game, we return the error from
+    /// [`Script::new_game`](replay::Script::new_game).
+    pub fn replay_script(
+        script: &replay::Script,
+    ) -> Result<(Self, Vec<Result<Word, GuessError>>), replay::ReplayError> {
+        let mut game = script.new_game()?;
+        let results = script
+            .guesses
+            .iter()
+            .map(|recorded| script.replay_guess(&mut game, &recorded.guess))
+            .collect();
 
-        Ok(word)
+        Ok((game, results))
     }
 
-    /// Update the game's keyboard according to the positions of the letters in the given guess.
-    fn update_keyboard(&mut self, letters: &Word) {
-        use ordered_position::OrderedPosition;
+    /// Check that enough time has passed since the last guess, according to the game's
+    /// [`policy`](Game::policy), and record the time of this guess if it has.
+    ///
+    /// If the policy has no minimum interval, then we don't need to know the time, so this is
+    /// always `Ok(())`.
+    fn check_guess_interval(&mut self) -> Result<(), GuessError> {
+        let Some(min_interval) = self.policy.min_guess_interval else {
+            return Ok(());
+        };
+
+        let now = Instant::now();
+        if let Some(last_time) = self.last_guess_time {
+            if now.duration_since(last_time) < min_interval {
+                return Err(GuessError::TooFast(min_interval));
+            }
+        }
+
+        self.last_guess_time = Some(now);
+        Ok(())
+    }
 
+    /// Update the game's letter counts according to the duplicate letters in the given guess.
+    fn update_letter_counts(&mut self, letters: &Word) {
         for letter in letters {
-            let current_pos = self
-                .keyboard
-                .get(&letter.letter)
-                .expect("Game::keyboard should contain all Latin letters");
-
-            if OrderedPosition(Some(letter.position)).cmp(&OrderedPosition(*current_pos))
-                == Ordering::Greater
-            {
-                let pos = self
-                    .keyboard
-                    .get_mut(&letter.letter)
-                    .expect("Game::keyboard should contain all Latin letters");
-                *pos = Some(letter.position);
+            if let Some(count) = LetterCount::from_guess(letters, letter.letter) {
+                self.letter_counts
+                    .entry(letter.letter)
+                    .and_modify(|current| *current = current.merge(count))
+                    .or_insert(count);
             }
         }
     }
 }
 
 mod ordered_position {
-    //! This module is an implementation detail to allow the [`Game::update_keyboard`] method to
+    //! This module is an implementation detail to allow the [`Keyboard::merge`] method to
     //! correctly order the `Option<Position>` types.
 
     use super::*;
@@ -338,8 +719,8 @@ mod tests {
     #[test]
     fn make_guess_correct_output() {
         let mut game = Game {
-            word: "DYSON".to_string(),
-            keyboard: Game::new_keyboard_map(),
+            word: "DYSON".to_string().into(),
+            ..Game::new()
         };
 
         assert_eq!(
@@ -414,8 +795,8 @@ mod tests {
         );
 
         let mut game = Game {
-            word: "BLEEP".to_string(),
-            keyboard: Game::new_keyboard_map(),
+            word: "BLEEP".to_string().into(),
+            ..Game::new()
         };
 
         assert_eq!(
@@ -432,8 +813,8 @@ mod tests {
         );
 
         let mut game = Game {
-            word: "EERIE".to_string(),
-            keyboard: Game::new_keyboard_map(),
+            word: "EERIE".to_string().into(),
+            ..Game::new()
         };
 
         assert_eq!(
@@ -449,6 +830,349 @@ mod tests {
         )
     }
 
+    #[test]
+    #[cfg(not(feature = "reveal"))]
+    fn debug_redacts_word() {
+        let game = Game {
+            word: "DYSON".to_string().into(),
+            ..Game::new()
+        };
+
+        let mut won = game.clone();
+        won.make_guess("DYSON").unwrap();
+
+        for debug in [
+            format!("{game:?}"),
+            format!("{game:#?}"),
+            format!("{won:?}"),
+            format!("{won:#?}"),
+        ] {
+            assert!(
+                !debug.contains("DYSON"),
+                "{debug:?} should not contain the word"
+            );
+            assert!(
+                debug.contains("\"*****\""),
+                "{debug:?} should contain a redacted word"
+            );
+            assert!(debug.contains(".."), "{debug:?} should be non-exhaustive");
+        }
+    }
+
+    #[test]
+    fn letter_counts() {
+        let mut game = Game {
+            word: "BLEEP".to_string().into(),
+            ..Game::new()
+        };
+
+        game.make_guess("PEACE")
+            .expect("input `PEACE` should be a valid guess");
+        assert_eq!(
+            game.letter_counts.get(&'E'),
+            Some(&LetterCount {
+                at_least: 2,
+                exact: false
+            })
+        );
+        assert_eq!(
+            game.letter_counts.get(&'A'),
+            Some(&LetterCount {
+                at_least: 0,
+                exact: true
+            })
+        );
+        assert_eq!(game.letter_counts.get(&'B'), None);
+
+        game.make_guess("EERIE")
+            .expect("input `EERIE` should be a valid guess");
+        assert_eq!(
+            game.letter_counts.get(&'E'),
+            Some(&LetterCount {
+                at_least: 2,
+                exact: true
+            })
+        );
+        assert_eq!(game.letter_counts[&'E'].to_string(), "exactly 2");
+    }
+
+    #[test]
+    fn policy_min_guess_interval() {
+        let mut game = Game::with_policy(GamePolicy {
+            min_guess_interval: Some(Duration::from_secs(60)),
+            ..GamePolicy::default()
+        });
+
+        assert!(game.make_guess("WORDY").is_ok());
+        assert_eq!(
+            game.make_guess("DUSTY"),
+            Err(GuessError::TooFast(Duration::from_secs(60)))
+        );
+
+        // Invalid guesses are rejected for being invalid before the interval is checked
+        assert_eq!(game.make_guess("spurg"), Err(GuessError::InvalidWord));
+    }
+
+    #[test]
+    fn policy_suspicious_entry_hook() {
+        use std::sync::{Arc, Mutex};
+
+        let flagged: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let mut game = Game::with_policy(GamePolicy {
+            suspicious_entry_threshold: Some(Duration::from_millis(300)),
+            on_suspicious_entry: Some({
+                let flagged = Arc::clone(&flagged);
+                Arc::new(move |guess, _| flagged.lock().unwrap().push(guess.to_string()))
+            }),
+            ..GamePolicy::default()
+        });
+
+        assert!(game
+            .make_timed_guess("WORDY", Duration::from_millis(50))
+            .is_ok());
+        assert!(game
+            .make_timed_guess("DUSTY", Duration::from_secs(2))
+            .is_ok());
+        assert_eq!(
+            game.make_timed_guess("spurg", Duration::from_millis(50)),
+            Err(GuessError::InvalidWord)
+        );
+
+        assert_eq!(*flagged.lock().unwrap(), vec!["WORDY".to_string()]);
+
+        // A guess that's rejected for being too fast was never accepted, so it isn't flagged
+        let mut game = Game {
+            policy: GamePolicy {
+                min_guess_interval: Some(Duration::from_secs(60)),
+                ..game.policy.clone()
+            },
+            ..Game::new()
+        };
+        assert!(game
+            .make_timed_guess("CRANE", Duration::from_secs(2))
+            .is_ok());
+        assert_eq!(
+            game.make_timed_guess("SOOTY", Duration::from_millis(50)),
+            Err(GuessError::TooFast(Duration::from_secs(60)))
+        );
+        assert_eq!(*flagged.lock().unwrap(), vec!["WORDY".to_string()]);
+    }
+
+    #[test]
+    fn policy_opener_restrictions() {
+        let mut game = Game::with_policy(GamePolicy {
+            banned_openers: ["SOARE".to_string()].into(),
+            min_opener_vowels: Some(2),
+            ..GamePolicy::default()
+        });
+
+        assert_eq!(game.make_guess("soare"), Err(GuessError::BannedOpener));
+        assert_eq!(game.make_guess("DUSTY"), Err(GuessError::TooFewVowels(2)));
+        assert!(game.make_guess("AUDIO").is_ok());
+
+        // The restrictions only apply to the first guess
+        assert!(game.validate_guess("SOARE").is_ok());
+        assert!(game.make_guess("DUSTY").is_ok());
+    }
+
+    #[test]
+    fn policy_hard_mode() {
+        let mut game = Game::with_policy(GamePolicy {
+            hard_mode: true,
+            reject_repeated_guesses: true,
+            ..GamePolicy::default()
+        });
+        game.word = "DYSON".to_string().into();
+
+        assert!(game.make_guess("DOSES").is_ok());
+        assert_eq!(game.make_guess("doses"), Err(GuessError::RepeatedGuess));
+        assert_eq!(
+            game.make_guess("CRANE"),
+            Err(GuessError::MissingCorrectLetter('D', 1))
+        );
+        assert_eq!(
+            game.make_guess("DUSTY"),
+            Err(GuessError::MissingLetter('O'))
+        );
+        assert_eq!(game.guess_history.len(), 1);
+
+        let validator = validator::GuessValidator::for_game(&game);
+        let before = analysis::Constraints::from_history(&game.guess_history);
+        for guess in ["DOSES", "CRANE", "DISCO"] {
+            assert_eq!(
+                validator.validate(guess, &before),
+                game.validate_guess(guess)
+            );
+        }
+    }
+
+    #[test]
+    fn make_guess_limited_budget() {
+        let mut game = Game::new();
+        let mut budget = GuessBudget::new(2, Duration::from_secs(3600));
+
+        assert_eq!(
+            game.make_guess_limited("spurg", &mut budget),
+            Err(GuessError::InvalidWord)
+        );
+        assert!(game.make_guess_limited("WORDY", &mut budget).is_ok());
+        assert_eq!(
+            game.make_guess_limited("DUSTY", &mut budget),
+            Err(GuessError::TooManyAttempts)
+        );
+    }
+
+    #[test]
+    fn keyboard_is_function_of_history() {
+        use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(1221);
+
+        for scoring_rules in [ScoringRules::Classic, ScoringRules::LenientDuplicates] {
+            for _ in 0..200 {
+                let mut game = Game {
+                    word: valid_words::GOOD_WORDS
+                        .choose(&mut rng)
+                        .unwrap()
+                        .to_string()
+                        .into(),
+                    scoring_rules: scoring_rules.clone(),
+                    ..Game::new()
+                };
+                let mut history = Vec::new();
+
+                for _ in 0..6 {
+                    let guess = valid_words::VALID_WORDS.choose(&mut rng).unwrap();
+                    history.push(game.make_guess(guess).unwrap());
+                    assert_eq!(game.keyboard, Keyboard::from_history(&history));
+                    assert_eq!(game.guess_history, history);
+                }
+            }
+        }
+
+        assert_eq!(Keyboard::from_history(&[]), Keyboard::new());
+    }
+
+    #[test]
+    fn with_answer() {
+        let game = Game::with_answer("dyson").unwrap();
+        assert_eq!(game.word.reveal_for_display(), "DYSON");
+        assert_eq!(game.puzzle, PuzzleId::Custom);
+        assert!(game.guess_history.is_empty());
+        assert_eq!(
+            Game::try_from("AAHED").unwrap().word.reveal_for_display(),
+            "AAHED"
+        );
+
+        for (answer, error) in [
+            ("ABCDE", GuessError::InvalidWord),
+            ("DYSONS", GuessError::WrongWordLength),
+            ("ÉCLAT", GuessError::IncludesNonAscii),
+        ] {
+            assert_eq!(
+                Game::try_from(answer),
+                Err(AnswerError::InvalidWord(answer.to_string(), error))
+            );
+        }
+    }
+
+    #[test]
+    fn share_string() {
+        let mut game = Game {
+            word: "DYSON".to_string().into(),
+            puzzle: PuzzleId::Daily(19358),
+            ..Game::new()
+        };
+        game.make_guess("CRANE").unwrap();
+        assert_eq!(game.share_string(true), None);
+
+        game.make_guess("dyson").unwrap();
+        assert_eq!(
+            game.share_string(true).unwrap(),
+            "Wordle 19358 2/6\n\n⬜⬜⬜🟨⬜\n🟩🟩🟩🟩🟩"
+        );
+        assert_eq!(
+            game.share_string_with(false, &theme::Theme::DARK).unwrap(),
+            "Wordle 19358 2/6\n\n⬛⬛⬛🟨⬛ CRANE\n🟩🟩🟩🟩🟩 DYSON"
+        );
+
+        game.puzzle = PuzzleId::Custom;
+        assert!(game.share_string(true).unwrap().starts_with("Wordle 2/6\n"));
+        assert_eq!(Game::new().share_string(false), None);
+
+        // A game is only lost once every guess has been used
+        let mut lost = Game::with_answer("DYSON").unwrap();
+        for _ in 0..5 {
+            lost.make_guess("CRANE").unwrap();
+            assert_eq!(lost.share_string(true), None);
+        }
+        lost.make_guess("CRANE").unwrap();
+        assert_eq!(
+            lost.share_string(true).unwrap(),
+            format!("Wordle X/6\n\n{}", ["⬜⬜⬜🟨⬜"; 6].join("\n"))
+        );
+
+        // Guessing the answer after too many guesses doesn't count as a win
+        lost.make_guess("DYSON").unwrap();
+        assert!(lost.share_string(true).unwrap().starts_with("Wordle X/6\n"));
+    }
+
+    #[test]
+    fn knowledge_from_guesses() {
+        let mut game = Game::with_answer("DYSON").unwrap();
+        assert_eq!(game.knowledge(), analysis::Knowledge::default());
+
+        game.make_guess("CRANE").unwrap();
+        game.make_guess("DUSTY").unwrap();
+
+        let knowledge = game.knowledge();
+        assert_eq!(
+            knowledge,
+            analysis::Knowledge::from_history(&game.guess_history)
+        );
+        assert_eq!(knowledge.correct, [Some('D'), None, Some('S'), None, None]);
+        assert_eq!(knowledge.required.get(&'N'), Some(&1));
+        assert_eq!(knowledge.required.get(&'Y'), Some(&1));
+        assert!(knowledge.excluded[3].contains(&'N'));
+        assert!(knowledge.excluded[4].contains(&'Y'));
+        assert!(knowledge.excluded[1].contains(&'C'));
+        assert!(knowledge.matches(game.word.reveal_for_display()));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        let mut game = Game {
+            policy: GamePolicy {
+                min_opener_vowels: Some(1),
+                ..GamePolicy::default()
+            },
+            ..Game::daily(19358)
+        };
+        game.make_guess("CRANE").unwrap();
+        game.make_guess("AWAKE").unwrap();
+
+        let json = serde_json::to_string(&game).unwrap();
+        assert!(!json.contains(game.word.reveal_for_display().as_str()));
+        let restored: Game = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, game);
+        assert_eq!(restored.guess_history.len(), 2);
+
+        struct AllCorrect;
+        impl scoring::ScoringRule for AllCorrect {
+            fn score(&self, _answer: &str, guess: &str) -> Word {
+                scoring::score_guess(guess, guess).unwrap()
+            }
+        }
+
+        let custom = Game {
+            scoring_rules: ScoringRules::Custom(std::sync::Arc::new(AllCorrect)),
+            ..Game::new()
+        };
+        assert!(serde_json::to_string(&custom).is_err());
+        assert!(serde_json::from_str::<ScoringRules>("\"custom\"").is_err());
+    }
+
     #[test]
     fn ordered_position() {
         use ordered_position::OrderedPosition;
@@ -0,0 +1,459 @@
+//! This module handles the user-facing messages of the game, so that frontends can be translated.
+//!
+//! The [`Messages`] trait provides every message, [`English`] is the default implementation, and
+//! [`MessageTable`] allows loading translations from a simple text format at runtime.
+
+use super::{letters::Position, scoring::Explanation, GuessError};
+use std::collections::HashMap;
+
+/// A source of user-facing messages.
+///
+/// Frontends should get all their shared text through this trait rather than hard-coding it.
+pub trait Messages {
+    /// The greeting shown at the start of a game.
+    fn welcome(&self) -> String;
+
+    /// The message shown when the player guesses the target word.
+    fn win(&self, word: &str) -> String;
+
+    /// The message shown when the player runs out of guesses, before [`loss`](Messages::loss).
+    fn out_of_guesses(&self) -> String;
+
+    /// The message shown when the player runs out of guesses or gives up.
+    fn loss(&self, word: &str) -> String;
+
+    /// A description of the given guess error.
+    fn guess_error(&self, error: &GuessError) -> String;
+
+    /// A description of a single letter in a scored guess, for screen readers.
+    fn describe_letter(&self, letter: char, position: Position) -> String;
+
+    /// A description of the keys on the keyboard with the given position, for screen readers.
+    ///
+    /// The letters are given as a single string, like `"ABC"`, and are never empty.
+    fn describe_keys(&self, position: Position, letters: &str) -> String;
+
+    /// A sentence explaining why a tile got its colour. See
+    /// [`score_guess_explained`](super::scoring::score_guess_explained).
+    fn explain(&self, explanation: &Explanation) -> String;
+
+    /// The language of these messages as a BCP 47 tag, like `"en"`, for the `lang` attribute of
+    /// rendered HTML.
+    fn language(&self) -> String;
+
+    /// The label of the button that reveals a hidden answer.
+    fn show_answer(&self) -> String;
+
+    /// The caption that gives the answer of a finished game.
+    fn answer_was(&self, word: &str) -> String;
+}
+
+/// The default English messages.
+///
+/// These are also used for the [`Display`](std::fmt::Display) implementation of [`GuessError`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct English;
+
+impl Messages for English {
+    fn welcome(&self) -> String {
+        "Welcome to Wordle!".to_string()
+    }
+
+    fn win(&self, word: &str) -> String {
+        format!("Congratulations! The word was {word}!")
+    }
+
+    fn out_of_guesses(&self) -> String {
+        "Out of guesses!".to_string()
+    }
+
+    fn loss(&self, word: &str) -> String {
+        format!("Thanks for playing Wordle! The word was {word}!")
+    }
+
+    fn guess_error(&self, error: &GuessError) -> String {
+        match error {
+            GuessError::IncludesNonAscii => {
+                "Guess must be exclusively ASCII characters".to_string()
+            }
+            GuessError::InvalidWord => "Guess must be a valid word".to_string(),
+            GuessError::WrongWordLength => "Guess must be exactly 5 letters".to_string(),
+            GuessError::TooFast(interval) => format!("Guesses must be at least {interval:?} apart"),
+            GuessError::TooManyAttempts => "Too many guesses, please wait a bit".to_string(),
+            GuessError::BannedOpener => "That word isn't allowed as the first guess".to_string(),
+            GuessError::TooFewVowels(1) => "The first guess must have at least 1 vowel".to_string(),
+            GuessError::RepeatedGuess => "You've already guessed that word".to_string(),
+            GuessError::MissingCorrectLetter(letter, position) => {
+                format!("Letter {position} must be {letter}")
+            }
+            GuessError::MissingLetter(letter) => format!("Guess must contain {letter}"),
+            GuessError::TooFewVowels(count) => {
+                format!("The first guess must have at least {count} vowels")
+            }
+        }
+    }
+
+    fn describe_letter(&self, letter: char, position: Position) -> String {
+        match position {
+            Position::Correct => format!("{letter} is correct"),
+            Position::WrongPosition => format!("{letter} is in the wrong position"),
+            Position::NotInWord => format!("{letter} is not in the word"),
+        }
+    }
+
+    fn describe_keys(&self, position: Position, letters: &str) -> String {
+        let letters = letters
+            .chars()
+            .map(String::from)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        match position {
+            Position::Correct => format!("Correct: {letters}"),
+            Position::WrongPosition => format!("In the wrong position: {letters}"),
+            Position::NotInWord => format!("Not in the word: {letters}"),
+        }
+    }
+
+    fn explain(&self, explanation: &Explanation) -> String {
+        match *explanation {
+            Explanation::Correct { letter } => {
+                format!("{letter:?} is in the answer in this position")
+            }
+            Explanation::Misplaced { letter, in_answer } => format!(
+                "{letter:?} is in the answer, but not in this position (the answer has {})",
+                count_letter(in_answer, letter)
+            ),
+            Explanation::Absent { letter } => format!("{letter:?} is not in the answer"),
+            Explanation::Exhausted {
+                letter,
+                in_answer,
+                correct,
+                earlier_misplaced,
+            } => {
+                let reason = match (correct, earlier_misplaced) {
+                    (_, 0) => "the correct tiles",
+                    (0, _) => "the earlier misplaced tiles",
+                    _ => "the correct tiles and the earlier misplaced tiles",
+                };
+
+                format!(
+                    "This {letter:?} is not highlighted because the answer only has {}, already \
+                    accounted for by {reason}",
+                    count_letter(in_answer, letter)
+                )
+            }
+        }
+    }
+
+    fn language(&self) -> String {
+        "en".to_string()
+    }
+
+    fn show_answer(&self) -> String {
+        "Show the answer".to_string()
+    }
+
+    fn answer_was(&self, word: &str) -> String {
+        format!("The answer was {word}")
+    }
+}
+
+/// Count the given letter in English, like "one 'E'" or "two 'E's".
+fn count_letter(count: usize, letter: char) -> String {
+    let number = match count {
+        0 => "no".to_string(),
+        1 => "one".to_string(),
+        2 => "two".to_string(),
+        3 => "three".to_string(),
+        4 => "four".to_string(),
+        5 => "five".to_string(),
+        n => n.to_string(),
+    };
+
+    if count == 1 {
+        format!("{number} {letter:?}")
+    } else {
+        format!("{number} {letter:?}s")
+    }
+}
+
+/// A table of translated messages, loaded at runtime.
+///
+/// The table is written as one `key = message` pair per line. Blank lines and lines starting with
+/// `#` are ignored. Messages can contain `{word}`, `{interval}`, `{count}`, and `{position}`
+/// placeholders where relevant, and the descriptions for screen readers contain `{letter}` or
+/// `{letters}`.
+///
+/// These are the keys:
+///
+/// - `welcome`
+/// - `win` (with `{word}`)
+/// - `out-of-guesses`
+/// - `loss` (with `{word}`)
+/// - `error-non-ascii`
+/// - `error-invalid-word`
+/// - `error-wrong-length`
+/// - `error-too-fast` (with `{interval}`)
+/// - `error-too-many-attempts`
+/// - `error-banned-opener`
+/// - `error-too-few-vowels` (with `{count}`)
+/// - `error-repeated-guess`
+/// - `error-missing-correct-letter` (with `{letter}` and `{position}`, starting from 1)
+/// - `error-missing-letter` (with `{letter}`)
+/// - `describe-correct` (with `{letter}`)
+/// - `describe-wrong-position` (with `{letter}`)
+/// - `describe-not-in-word` (with `{letter}`)
+/// - `keys-correct` (with `{letters}`, like `A, B, C`)
+/// - `keys-wrong-position` (with `{letters}`)
+/// - `keys-not-in-word` (with `{letters}`)
+/// - `explain-correct` (with `{letter}`)
+/// - `explain-misplaced` (with `{letter}` and `{count}`, the number of times it's in the answer)
+/// - `explain-absent` (with `{letter}`)
+/// - `explain-exhausted-correct` (with `{letter}` and `{count}`), when the letter is accounted
+///   for by correct tiles
+/// - `explain-exhausted-misplaced` (with `{letter}` and `{count}`), when it's accounted for by
+///   earlier misplaced tiles
+/// - `explain-exhausted-both` (with `{letter}` and `{count}`), when it's accounted for by both
+/// - `language`, a BCP 47 tag like `fr`
+/// - `show-answer`
+/// - `answer-was` (with `{word}`)
+///
+/// Any key missing from the table falls back to [`English`], so a partial translation is still
+/// usable.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MessageTable {
+    /// The messages, keyed by their names.
+    messages: HashMap<String, String>,
+}
+
+impl MessageTable {
+    /// Parse a message table from the `key = message` format. See [`MessageTable`].
+    ///
+    /// Lines without an `=` are ignored.
+    pub fn parse(text: &str) -> Self {
+        let messages = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, message)| (key.trim().to_string(), message.trim().to_string()))
+            .collect();
+
+        Self { messages }
+    }
+
+    /// Get the message with the given key, replacing the placeholders, or fall back to the given
+    /// English message if the key is missing.
+    fn get(&self, key: &str, placeholders: &[(&str, &str)], fallback: String) -> String {
+        match self.messages.get(key) {
+            None => fallback,
+            Some(message) => placeholders
+                .iter()
+                .fold(message.clone(), |message, (name, value)| {
+                    message.replace(&format!("{{{name}}}"), value)
+                }),
+        }
+    }
+}
+
+impl Messages for MessageTable {
+    fn welcome(&self) -> String {
+        self.get("welcome", &[], English.welcome())
+    }
+
+    fn win(&self, word: &str) -> String {
+        self.get("win", &[("word", word)], English.win(word))
+    }
+
+    fn out_of_guesses(&self) -> String {
+        self.get("out-of-guesses", &[], English.out_of_guesses())
+    }
+
+    fn loss(&self, word: &str) -> String {
+        self.get("loss", &[("word", word)], English.loss(word))
+    }
+
+    fn guess_error(&self, error: &GuessError) -> String {
+        let fallback = English.guess_error(error);
+        match error {
+            GuessError::IncludesNonAscii => self.get("error-non-ascii", &[], fallback),
+            GuessError::InvalidWord => self.get("error-invalid-word", &[], fallback),
+            GuessError::WrongWordLength => self.get("error-wrong-length", &[], fallback),
+            GuessError::TooFast(interval) => self.get(
+                "error-too-fast",
+                &[("interval", &format!("{interval:?}"))],
+                fallback,
+            ),
+            GuessError::TooManyAttempts => self.get("error-too-many-attempts", &[], fallback),
+            GuessError::BannedOpener => self.get("error-banned-opener", &[], fallback),
+            GuessError::TooFewVowels(count) => self.get(
+                "error-too-few-vowels",
+                &[("count", &count.to_string())],
+                fallback,
+            ),
+            GuessError::RepeatedGuess => self.get("error-repeated-guess", &[], fallback),
+            GuessError::MissingCorrectLetter(letter, position) => self.get(
+                "error-missing-correct-letter",
+                &[
+                    ("letter", &letter.to_string()),
+                    ("position", &position.to_string()),
+                ],
+                fallback,
+            ),
+            GuessError::MissingLetter(letter) => self.get(
+                "error-missing-letter",
+                &[("letter", &letter.to_string())],
+                fallback,
+            ),
+        }
+    }
+
+    fn describe_letter(&self, letter: char, position: Position) -> String {
+        let key = match position {
+            Position::Correct => "describe-correct",
+            Position::WrongPosition => "describe-wrong-position",
+            Position::NotInWord => "describe-not-in-word",
+        };
+
+        self.get(
+            key,
+            &[("letter", &letter.to_string())],
+            English.describe_letter(letter, position),
+        )
+    }
+
+    fn describe_keys(&self, position: Position, letters: &str) -> String {
+        let key = match position {
+            Position::Correct => "keys-correct",
+            Position::WrongPosition => "keys-wrong-position",
+            Position::NotInWord => "keys-not-in-word",
+        };
+        let list = letters
+            .chars()
+            .map(String::from)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        self.get(
+            key,
+            &[("letters", &list)],
+            English.describe_keys(position, letters),
+        )
+    }
+
+    fn explain(&self, explanation: &Explanation) -> String {
+        let fallback = English.explain(explanation);
+        match *explanation {
+            Explanation::Correct { letter } => self.get(
+                "explain-correct",
+                &[("letter", &letter.to_string())],
+                fallback,
+            ),
+            Explanation::Misplaced { letter, in_answer } => self.get(
+                "explain-misplaced",
+                &[
+                    ("letter", &letter.to_string()),
+                    ("count", &in_answer.to_string()),
+                ],
+                fallback,
+            ),
+            Explanation::Absent { letter } => self.get(
+                "explain-absent",
+                &[("letter", &letter.to_string())],
+                fallback,
+            ),
+            Explanation::Exhausted {
+                letter,
+                in_answer,
+                correct,
+                earlier_misplaced,
+            } => {
+                let key = match (correct, earlier_misplaced) {
+                    (_, 0) => "explain-exhausted-correct",
+                    (0, _) => "explain-exhausted-misplaced",
+                    _ => "explain-exhausted-both",
+                };
+
+                self.get(
+                    key,
+                    &[
+                        ("letter", &letter.to_string()),
+                        ("count", &in_answer.to_string()),
+                    ],
+                    fallback,
+                )
+            }
+        }
+    }
+
+    fn language(&self) -> String {
+        self.get("language", &[], English.language())
+    }
+
+    fn show_answer(&self) -> String {
+        self.get("show-answer", &[], English.show_answer())
+    }
+
+    fn answer_was(&self, word: &str) -> String {
+        self.get("answer-was", &[("word", word)], English.answer_was(word))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn english_matches_display() {
+        for error in [
+            GuessError::IncludesNonAscii,
+            GuessError::InvalidWord,
+            GuessError::WrongWordLength,
+            GuessError::TooFast(Duration::from_secs(2)),
+            GuessError::TooManyAttempts,
+            GuessError::BannedOpener,
+            GuessError::TooFewVowels(2),
+            GuessError::RepeatedGuess,
+            GuessError::MissingCorrectLetter('E', 5),
+            GuessError::MissingLetter('U'),
+        ] {
+            assert_eq!(error.to_string(), English.guess_error(&error));
+        }
+
+        assert_eq!(
+            GuessError::TooFewVowels(1).to_string(),
+            "The first guess must have at least 1 vowel"
+        );
+    }
+
+    #[test]
+    fn message_table_parse_and_fallback() {
+        let table = MessageTable::parse(
+            "
+            # French, but only partly translated
+            welcome = Bienvenue sur Wordle !
+            win = Bravo ! Le mot était {word} !
+            error-too-fast = Attendez {interval} entre les essais
+            not a message
+            ",
+        );
+
+        assert_eq!(table.welcome(), "Bienvenue sur Wordle !");
+        assert_eq!(table.win("DYSON"), "Bravo ! Le mot était DYSON !");
+        assert_eq!(
+            table.guess_error(&GuessError::TooFast(Duration::from_secs(2))),
+            "Attendez 2s entre les essais"
+        );
+
+        // These aren't in the table, so they fall back to English
+        assert_eq!(table.loss("DYSON"), English.loss("DYSON"));
+        let explanation = Explanation::Absent { letter: 'Q' };
+        assert_eq!(table.explain(&explanation), English.explain(&explanation));
+        assert_eq!(
+            table.guess_error(&GuessError::InvalidWord),
+            English.guess_error(&GuessError::InvalidWord)
+        );
+    }
+}
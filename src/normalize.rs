@@ -0,0 +1,183 @@
+//! This module handles cleaning up raw input before it's checked as a guess.
+//!
+//! Input on phones often gets autocapitalised, picks up stray spaces, or has characters replaced
+//! with "smart" versions, and input methods for CJK languages can produce full-width letters.
+//! None of these should make a guess fail ASCII validation.
+
+use super::GuessError;
+
+/// The optional steps of normalization. The default is to only do the steps that are always
+/// safe, which are trimming whitespace, case folding, and mapping full-width characters.
+///
+/// See [`normalize_guess_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Normalization {
+    /// Remove apostrophes, including curly ones, from anywhere in the guess.
+    pub strip_apostrophes: bool,
+
+    /// Replace Latin letters with diacritics with their base letters, like `É` with `E`.
+    ///
+    /// This only covers the letters in Latin-1 and Latin Extended-A that have a single base
+    /// letter, so ligatures like `Æ` and `Œ` are left alone.
+    pub strip_diacritics: bool,
+}
+
+/// Map a full-width ASCII character, like `Ａ`, to its ASCII equivalent.
+fn from_full_width(c: char) -> char {
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// Replace a Latin letter with a diacritic with its uppercase base letter.
+fn strip_diacritic(c: char) -> char {
+    match c {
+        'À'..='Å' | 'à'..='å' | 'Ā'..='ą' => 'A',
+        'Ç' | 'ç' | 'Ć'..='č' => 'C',
+        'Ď'..='đ' => 'D',
+        'È'..='Ë' | 'è'..='ë' | 'Ē'..='ě' => 'E',
+        'Ĝ'..='ģ' => 'G',
+        'Ĥ'..='ħ' => 'H',
+        'Ì'..='Ï' | 'ì'..='ï' | 'Ĩ'..='ı' => 'I',
+        'Ĵ' | 'ĵ' => 'J',
+        'Ķ' | 'ķ' => 'K',
+        'Ĺ'..='ł' => 'L',
+        'Ñ' | 'ñ' | 'Ń'..='ň' => 'N',
+        'Ò'..='Ö' | 'Ø' | 'ò'..='ö' | 'ø' | 'Ō'..='ő' => 'O',
+        'Ŕ'..='ř' => 'R',
+        'Ś'..='š' => 'S',
+        'Ţ'..='ŧ' => 'T',
+        'Ù'..='Ü' | 'ù'..='ü' | 'Ũ'..='ų' => 'U',
+        'Ŵ' | 'ŵ' => 'W',
+        'Ý' | 'ý' | 'ÿ' | 'Ŷ'..='Ÿ' => 'Y',
+        'Ź'..='ž' => 'Z',
+        _ => c,
+    }
+}
+
+/// Check if the character is an apostrophe, including the curly and modifier letter ones.
+fn is_apostrophe(c: char) -> bool {
+    matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{02BC}' | '\u{FF07}')
+}
+
+/// Normalize a single character of input, returning [`None`] if it should be removed.
+///
+/// This is useful for frontends that handle input one key at a time. Letters are returned in
+/// uppercase. Whitespace isn't removed here, since that's only trimmed from the ends of a guess.
+pub fn normalize_letter(c: char, options: &Normalization) -> Option<char> {
+    if options.strip_apostrophes && is_apostrophe(c) {
+        return None;
+    }
+
+    let c = from_full_width(c);
+    let c = if options.strip_diacritics {
+        strip_diacritic(c)
+    } else {
+        c
+    };
+
+    Some(c.to_ascii_uppercase())
+}
+
+/// Normalize the guess with the default [`Normalization`]. See [`normalize_guess_with`].
+///
+/// # Errors
+///
+/// See [`normalize_guess_with`].
+pub fn normalize_guess(guess: &str) -> Result<String, GuessError> {
+    normalize_guess_with(guess, &Normalization::default())
+}
+
+/// Normalize the guess, returning it as 5 uppercase ASCII letters.
+///
+/// Whitespace is trimmed from both ends, full-width characters are mapped to ASCII, and the
+/// optional steps in `options` are applied to every character.
+///
+/// This doesn't check that the guess is in the word list. See
+/// [`Game::is_valid_guess`](super::Game::is_valid_guess).
+///
+/// # Errors
+///
+/// If the normalized guess still isn't exactly 5 ASCII characters, we return the appropriate
+/// [`GuessError`] variant. If any of those characters isn't a letter, like a digit or an
+/// apostrophe that wasn't stripped, we return [`GuessError::InvalidWord`], since no word in the
+/// list could match it.
+pub fn normalize_guess_with(guess: &str, options: &Normalization) -> Result<String, GuessError> {
+    let guess: String = guess
+        .trim()
+        .chars()
+        .filter_map(|c| normalize_letter(c, options))
+        .collect();
+
+    if !guess.is_ascii() {
+        Err(GuessError::IncludesNonAscii)
+    } else if guess.len() != 5 {
+        Err(GuessError::WrongWordLength)
+    } else if !guess.chars().all(|c| c.is_ascii_alphabetic()) {
+        Err(GuessError::InvalidWord)
+    } else {
+        Ok(guess)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_normalization() {
+        assert_eq!(normalize_guess("  Crane\n"), Ok("CRANE".to_string()));
+        assert_eq!(normalize_guess("ｃｒａｎｅ"), Ok("CRANE".to_string()));
+        assert_eq!(normalize_guess("\u{3000}CRANE"), Ok("CRANE".to_string()));
+        assert_eq!(normalize_guess("CRÈME"), Err(GuessError::IncludesNonAscii));
+        assert_eq!(normalize_guess("CAN’T"), Err(GuessError::IncludesNonAscii));
+        assert_eq!(normalize_guess("CR ANE"), Err(GuessError::WrongWordLength));
+        assert_eq!(normalize_guess("CR1NE"), Err(GuessError::InvalidWord));
+        assert_eq!(normalize_guess("CAN'T"), Err(GuessError::InvalidWord));
+        assert_eq!(normalize_guess("C-A_N"), Err(GuessError::InvalidWord));
+    }
+
+    #[test]
+    fn optional_normalization() {
+        let options = Normalization {
+            strip_apostrophes: true,
+            strip_diacritics: true,
+        };
+
+        assert_eq!(
+            normalize_guess_with("crème", &options),
+            Ok("CREME".to_string())
+        );
+        assert_eq!(
+            normalize_guess_with("ŁÓDŹS", &options),
+            Ok("LODZS".to_string())
+        );
+        assert_eq!(
+            normalize_guess_with("o’clock", &options),
+            Err(GuessError::WrongWordLength)
+        );
+        assert_eq!(
+            normalize_guess_with("Y'ALL’S", &options),
+            Ok("YALLS".to_string())
+        );
+        assert_eq!(
+            normalize_guess_with("ÆTHER", &options),
+            Err(GuessError::IncludesNonAscii)
+        );
+    }
+
+    #[test]
+    fn normalize_single_letters() {
+        let options = Normalization {
+            strip_apostrophes: true,
+            strip_diacritics: true,
+        };
+
+        assert_eq!(normalize_letter('é', &options), Some('E'));
+        assert_eq!(normalize_letter('ｑ', &options), Some('Q'));
+        assert_eq!(normalize_letter('’', &options), None);
+        assert_eq!(normalize_letter('é', &Normalization::default()), Some('é'));
+    }
+}
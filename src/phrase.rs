@@ -0,0 +1,186 @@
+//! This module handles phrase games, where the answer is two words that are guessed together.
+
+use super::{spoiler::SpoilerGuard, Game, GuessError, Word};
+
+/// A game of Wordle where the answer is a phrase of two 5 letter words, like "SWEET DREAM".
+///
+/// Each guess is two words separated by whitespace, giving 10 tiles. Each word is an ordinary
+/// [`Game`] underneath, so it's validated and scored independently against its own half of the
+/// answer, using that game's [`policy`](Game::policy) and [`scoring_rules`](Game::scoring_rules).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhraseGame {
+    /// The games for the first and second words of the phrase.
+    segments: [Game; 2],
+}
+
+impl Default for PhraseGame {
+    fn default() -> Self {
+        Self::new([Game::new(), Game::new()])
+    }
+}
+
+impl PhraseGame {
+    /// Create a phrase game from a game for each word of the phrase, in order.
+    pub fn new(segments: [Game; 2]) -> Self {
+        Self { segments }
+    }
+
+    /// Get the games for each word of the phrase, in order.
+    pub fn segments(&self) -> &[Game; 2] {
+        &self.segments
+    }
+
+    /// Get the answer, as two uppercase words separated by a space.
+    pub fn answer(&self) -> SpoilerGuard<String> {
+        SpoilerGuard::new(format!(
+            "{} {}",
+            self.segments[0].word.reveal_for_display(),
+            self.segments[1].word.reveal_for_display()
+        ))
+    }
+
+    /// Split the guess into its two words.
+    ///
+    /// # Errors
+    ///
+    /// If the guess isn't exactly two words, we return [`GuessError::WrongWordLength`].
+    fn split_guess(guess: &str) -> Result<[&str; 2], GuessError> {
+        let mut words = guess.split_whitespace();
+
+        match (words.next(), words.next(), words.next()) {
+            (Some(first), Some(second), None) => Ok([first, second]),
+            _ => Err(GuessError::WrongWordLength),
+        }
+    }
+
+    /// Make a guess of two words separated by whitespace, and score each word against its half
+    /// of the answer.
+    ///
+    /// Both words are checked before either is scored, and the guess is only recorded if both
+    /// words are accepted, so a rejected guess doesn't change the game at all. That includes a
+    /// guess where only one word is [`TooFast`](GuessError::TooFast) for its game's policy.
+    ///
+    /// # Errors
+    ///
+    /// If the guess isn't exactly two words, we return [`GuessError::WrongWordLength`]. Otherwise,
+    /// if either word is invalid, we return the error for the first invalid word. See
+    /// [`Game::make_guess`].
+    pub fn make_guess(&mut self, guess: &str) -> Result<[Word; 2], GuessError> {
+        let words = Self::split_guess(guess)?;
+
+        for (game, word) in self.segments.iter().zip(words) {
+            game.validate_guess(word)?;
+        }
+
+        // Guess on copies of the segments, so that neither is changed unless both words are
+        // accepted
+        let mut segments = self.segments.clone();
+        let [first, second] = &mut segments;
+        let scored = [first.make_guess(words[0])?, second.make_guess(words[1])?];
+        self.segments = segments;
+
+        Ok(scored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::letters::Position;
+
+    fn sweet_dream() -> PhraseGame {
+        PhraseGame::new([
+            Game {
+                word: "SWEET".to_string().into(),
+                ..Game::new()
+            },
+            Game {
+                word: "DREAM".to_string().into(),
+                ..Game::new()
+            },
+        ])
+    }
+
+    #[test]
+    fn phrase_guesses_need_two_words() {
+        let mut game = sweet_dream();
+        assert_eq!(game.answer().reveal_for_display(), "SWEET DREAM");
+
+        assert_eq!(game.make_guess("SWEET"), Err(GuessError::WrongWordLength));
+        assert_eq!(
+            game.make_guess("SWEET DREAM TEAMS"),
+            Err(GuessError::WrongWordLength)
+        );
+        assert_eq!(
+            game.make_guess("SWEETDREAM"),
+            Err(GuessError::WrongWordLength)
+        );
+        assert_eq!(game.make_guess("SWEET SPURG"), Err(GuessError::InvalidWord));
+        assert_eq!(game.make_guess("SPURG DREAM"), Err(GuessError::InvalidWord));
+
+        // Invalid guesses don't touch either half
+        assert_eq!(game, sweet_dream());
+    }
+
+    #[test]
+    fn phrase_guesses_are_all_or_nothing() {
+        use crate::policy::GamePolicy;
+        use std::time::Duration;
+
+        let [first, second] = sweet_dream().segments;
+        let mut game = PhraseGame::new([
+            first,
+            Game {
+                policy: GamePolicy {
+                    min_guess_interval: Some(Duration::from_secs(60)),
+                    ..GamePolicy::default()
+                },
+                ..second
+            },
+        ]);
+        assert!(game.make_guess("CRANE SOOTY").is_ok());
+
+        // The first word would be accepted, but the second is too fast, so neither is recorded
+        let before = game.clone();
+        assert_eq!(
+            game.make_guess("DREAM SWEET"),
+            Err(GuessError::TooFast(Duration::from_secs(60)))
+        );
+        assert_eq!(game, before);
+        assert_eq!(game.segments()[0].guess_history.len(), 1);
+    }
+
+    #[test]
+    fn phrase_words_are_scored_separately() {
+        let mut game = sweet_dream();
+
+        let [first, second] = game
+            .make_guess("  dream   sweet ")
+            .expect("Both words are valid");
+
+        use Position::*;
+        assert_eq!(
+            first.map(|l| l.position),
+            [NotInWord, NotInWord, Correct, NotInWord, NotInWord]
+        );
+        assert_eq!(
+            second.map(|l| l.position),
+            [NotInWord, NotInWord, Correct, NotInWord, NotInWord]
+        );
+
+        assert_eq!(
+            game.segments()[0].keyboard.best_position('E'),
+            Some(Correct)
+        );
+        assert_eq!(
+            game.segments()[1].keyboard.best_position('W'),
+            Some(NotInWord)
+        );
+
+        let [first, second] = game
+            .make_guess("SWEET DREAM")
+            .expect("Both words are valid");
+        assert!(first.iter().chain(&second).all(|l| l.position == Correct));
+    }
+}
@@ -0,0 +1,313 @@
+//! This module handles optional policies that restrict how a [`Game`](super::Game) can be played.
+//!
+//! Policies are enforced by the game itself rather than by the frontend, so that they can't be
+//! bypassed by a modified client.
+
+use super::{analysis::Knowledge, GuessError, Word};
+use std::{
+    collections::HashSet,
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The letters that count as vowels for [`GamePolicy::min_opener_vowels`].
+pub const VOWELS: [char; 5] = ['A', 'E', 'I', 'O', 'U'];
+
+/// A function to call when a guess is entered suspiciously quickly.
+///
+/// It gets called with the guess and the time that the player took to enter it.
+pub type SuspiciousEntryHook = Arc<dyn Fn(&str, Duration) + Send + Sync>;
+
+/// A set of optional rules to apply to a [`Game`](super::Game).
+///
+/// The default policy doesn't restrict the game at all.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamePolicy {
+    /// The minimum amount of time allowed between two guesses.
+    ///
+    /// If a guess is made sooner than this after the previous guess, then
+    /// [`make_guess`](super::Game::make_guess) returns [`TooFast`](super::GuessError::TooFast).
+    ///
+    /// This uses [`std::time::Instant`], which is not available on `wasm32-unknown-unknown`, so it
+    /// should only be set on native targets.
+    pub min_guess_interval: Option<Duration>,
+
+    /// If a guess is entered faster than this, then
+    /// [`on_suspicious_entry`](GamePolicy::on_suspicious_entry) gets called.
+    ///
+    /// Entering a whole word this quickly usually means it was pasted in. The guess is still
+    /// made, and it's up to the hook to decide what to do about it.
+    ///
+    /// See [`make_timed_guess`](super::Game::make_timed_guess).
+    pub suspicious_entry_threshold: Option<Duration>,
+
+    /// The hook to call when a guess is entered suspiciously quickly.
+    ///
+    /// This isn't serialized, so it needs to be set again on a deserialized policy.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub on_suspicious_entry: Option<SuspiciousEntryHook>,
+
+    /// Words that aren't allowed as the first guess, in uppercase.
+    ///
+    /// Tournaments might use this to ban the opener that a solver would choose, for example.
+    pub banned_openers: HashSet<String>,
+
+    /// The minimum number of vowels that the first guess must contain.
+    ///
+    /// Each letter in [`VOWELS`] counts once for every time it appears, so "AUDIO" has 4 vowels.
+    pub min_opener_vowels: Option<usize>,
+
+    /// Whether every guess must use what's known from the previous guesses, like the hard mode
+    /// of the official Wordle.
+    ///
+    /// Letters that were [`Correct`](super::letters::Position::Correct) must stay in place, and
+    /// letters that were in the wrong position must be used somewhere.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hard_mode: bool,
+
+    /// Whether a word that has already been guessed in this game is rejected.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reject_repeated_guesses: bool,
+}
+
+impl fmt::Debug for GamePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GamePolicy")
+            .field("min_guess_interval", &self.min_guess_interval)
+            .field(
+                "suspicious_entry_threshold",
+                &self.suspicious_entry_threshold,
+            )
+            .field(
+                "on_suspicious_entry",
+                &self.on_suspicious_entry.as_ref().map(|_| "<hook>"),
+            )
+            .field("banned_openers", &self.banned_openers)
+            .field("min_opener_vowels", &self.min_opener_vowels)
+            .field("hard_mode", &self.hard_mode)
+            .field("reject_repeated_guesses", &self.reject_repeated_guesses)
+            .finish()
+    }
+}
+
+/// Two policies are equal if they have the same settings and share the same hook.
+impl PartialEq for GamePolicy {
+    fn eq(&self, other: &Self) -> bool {
+        let hooks_equal = match (&self.on_suspicious_entry, &other.on_suspicious_entry) {
+            (None, None) => true,
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        };
+
+        self.min_guess_interval == other.min_guess_interval
+            && self.suspicious_entry_threshold == other.suspicious_entry_threshold
+            && hooks_equal
+            && self.banned_openers == other.banned_openers
+            && self.min_opener_vowels == other.min_opener_vowels
+            && self.hard_mode == other.hard_mode
+            && self.reject_repeated_guesses == other.reject_repeated_guesses
+    }
+}
+
+impl GamePolicy {
+    /// Check whether a guess entered in `entry_duration` should count as suspicious.
+    pub fn is_suspicious_entry(&self, entry_duration: Duration) -> bool {
+        self.suspicious_entry_threshold
+            .is_some_and(|threshold| entry_duration < threshold)
+    }
+
+    /// Check that the given uppercase guess is allowed as the first guess of a game.
+    ///
+    /// # Errors
+    ///
+    /// If the guess is a banned opener, we return [`GuessError::BannedOpener`]. If it has too few
+    /// vowels, we return [`GuessError::TooFewVowels`].
+    pub fn check_opener(&self, guess: &str) -> Result<(), GuessError> {
+        if self.banned_openers.contains(guess) {
+            return Err(GuessError::BannedOpener);
+        }
+
+        if let Some(min_vowels) = self.min_opener_vowels {
+            let vowels = guess.chars().filter(|c| VOWELS.contains(c)).count();
+            if vowels < min_vowels {
+                return Err(GuessError::TooFewVowels(min_vowels));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check an uppercase guess against the scored guesses already made in the game, if the policy
+    /// has [`hard_mode`](GamePolicy::hard_mode) or
+    /// [`reject_repeated_guesses`](GamePolicy::reject_repeated_guesses).
+    ///
+    /// Hard mode uses the [`Knowledge`] from the history, so it assumes the
+    /// [`Classic`](super::scoring::ScoringRules::Classic) rules.
+    ///
+    /// # Errors
+    ///
+    /// If the guess has already been made, we return [`GuessError::RepeatedGuess`]. If it breaks
+    /// hard mode, we return [`GuessError::MissingCorrectLetter`] for the first known letter that
+    /// isn't in place, or else [`GuessError::MissingLetter`] for the first known letter that isn't
+    /// used often enough.
+    pub fn check_history(&self, guess: &str, history: &[Word]) -> Result<(), GuessError> {
+        if self.reject_repeated_guesses
+            && history
+                .iter()
+                .any(|word| word.iter().map(|letter| letter.letter).eq(guess.chars()))
+        {
+            return Err(GuessError::RepeatedGuess);
+        }
+
+        if !self.hard_mode {
+            return Ok(());
+        }
+
+        let knowledge = Knowledge::from_history(history);
+        for (position, (known, c)) in knowledge.correct.iter().zip(guess.chars()).enumerate() {
+            if let Some(letter) = *known {
+                if letter != c {
+                    return Err(GuessError::MissingCorrectLetter(letter, position + 1));
+                }
+            }
+        }
+        for (&letter, &count) in &knowledge.required {
+            if guess.chars().filter(|&c| c == letter).count() < count {
+                return Err(GuessError::MissingLetter(letter));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A token bucket that limits how many guess attempts a client can make over time.
+///
+/// The bucket starts full with `capacity` tokens. Each attempt takes one token, whether or not the
+/// guess turns out to be valid, and one token is regained every `refill_interval`, up to the
+/// capacity. This allows short bursts while capping the sustained rate.
+///
+/// Budgets are kept separately from the [`Game`](super::Game), so that a server can keep one per
+/// client and share it between that client's games. See
+/// [`make_guess_limited`](super::Game::make_guess_limited).
+///
+/// Like [`GamePolicy::min_guess_interval`], this uses [`std::time::Instant`], so it should only
+/// be used on native targets.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GuessBudget {
+    /// The maximum number of tokens in the bucket.
+    capacity: u32,
+
+    /// How long it takes to regain a single token.
+    refill_interval: Duration,
+
+    /// The number of tokens currently in the bucket.
+    tokens: u32,
+
+    /// The last time that tokens were added to the bucket.
+    last_refill: Instant,
+}
+
+impl GuessBudget {
+    /// Create a full budget with the given capacity, which regains one token every `refill_interval`.
+    pub fn new(capacity: u32, refill_interval: Duration) -> Self {
+        Self::new_at(capacity, refill_interval, Instant::now())
+    }
+
+    /// Create a full budget that allows `attempts` guesses per minute, in bursts of up to `attempts`.
+    pub fn per_minute(attempts: u32) -> Self {
+        Self::new(attempts, Duration::from_secs(60) / attempts.max(1))
+    }
+
+    /// Create a full budget as if it was created at the given time.
+    fn new_at(capacity: u32, refill_interval: Duration, now: Instant) -> Self {
+        Self {
+            capacity,
+            refill_interval,
+            tokens: capacity,
+            last_refill: now,
+        }
+    }
+
+    /// Add all the tokens regained since the last refill.
+    fn refill_at(&mut self, now: Instant) {
+        if self.tokens >= self.capacity || self.refill_interval.is_zero() {
+            self.tokens = self.capacity;
+            self.last_refill = now;
+            return;
+        }
+
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let regained = elapsed.as_nanos() / self.refill_interval.as_nanos();
+        if regained == 0 {
+            return;
+        }
+
+        let regained = u32::try_from(regained).unwrap_or(u32::MAX);
+        self.tokens = self.tokens.saturating_add(regained).min(self.capacity);
+
+        // Keep the leftover time towards the next token, unless the bucket is now full
+        self.last_refill = if self.tokens == self.capacity {
+            now
+        } else {
+            self.last_refill + self.refill_interval * regained
+        };
+    }
+
+    /// Try to take a token at the given time, returning whether there was one to take.
+    fn try_take_at(&mut self, now: Instant) -> bool {
+        self.refill_at(now);
+
+        if self.tokens > 0 {
+            self.tokens -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Try to take a token for a guess attempt, returning whether there was one to take.
+    pub fn try_take(&mut self) -> bool {
+        self.try_take_at(Instant::now())
+    }
+
+    /// Get the number of attempts that could be made right now.
+    pub fn remaining(&mut self) -> u32 {
+        self.refill_at(Instant::now());
+        self.tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guess_budget_bursts_and_refills() {
+        let start = Instant::now();
+        let mut budget = GuessBudget::new_at(3, Duration::from_secs(10), start);
+
+        assert!(budget.try_take_at(start));
+        assert!(budget.try_take_at(start));
+        assert!(budget.try_take_at(start));
+        assert!(!budget.try_take_at(start));
+
+        // Not quite long enough for a token
+        assert!(!budget.try_take_at(start + Duration::from_secs(9)));
+
+        assert!(budget.try_take_at(start + Duration::from_secs(10)));
+        assert!(!budget.try_take_at(start + Duration::from_secs(15)));
+
+        // The leftover 5 seconds from before count towards this token
+        assert!(budget.try_take_at(start + Duration::from_secs(20)));
+
+        // A long wait only refills up to the capacity
+        let later = start + Duration::from_secs(1000);
+        assert!(budget.try_take_at(later));
+        assert!(budget.try_take_at(later));
+        assert!(budget.try_take_at(later));
+        assert!(!budget.try_take_at(later));
+    }
+}
@@ -0,0 +1,230 @@
+//! This module handles answer pools, which replace the normal answers on certain days.
+//!
+//! Deployments can use these to run themed events, like a pack of wintry words that's only active
+//! in December, by registering pools rather than changing code.
+//!
+//! Pools are opt-in. [`Game::new`](super::Game::new) and [`Game::daily`](super::Game::daily)
+//! never consult them, so a deployment that uses pools has to create its games with
+//! [`Game::daily_with_pools`](super::Game::daily_with_pools) and
+//! [`Game::new_with_pools`](super::Game::new_with_pools) instead.
+
+use super::{
+    daily,
+    valid_words::{FALLBACK_WORD, GOOD_WORDS},
+    Game, GuessError,
+};
+use rand::seq::SliceRandom;
+use std::ops::RangeInclusive;
+use thiserror::Error;
+
+/// An enum representing possible errors from creating an [`AnswerPool`].
+#[derive(Debug, Error, PartialEq)]
+pub enum PoolError {
+    /// An answer pool needs at least one word.
+    #[error("An answer pool needs at least one word")]
+    Empty,
+
+    /// One of the words in the pool isn't a valid guess, so it couldn't be guessed as an answer.
+    #[error("{0:?} can't be an answer: {1}")]
+    InvalidWord(String, #[source] GuessError),
+}
+
+/// A set of answers that's only active on certain days, and optionally only in certain regions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnswerPool {
+    /// The name of the pool, like "Winter".
+    name: String,
+
+    /// The answers in this pool, in uppercase.
+    words: Vec<String>,
+
+    /// The epoch days that this pool is active, inclusive. See [`daily::epoch_day`].
+    days: RangeInclusive<u64>,
+
+    /// The regions that this pool is active in. If this is empty, then it's active everywhere.
+    regions: Vec<String>,
+}
+
+impl AnswerPool {
+    /// Create an answer pool that's active on the given epoch days in every region.
+    ///
+    /// # Errors
+    ///
+    /// If there are no words, we return [`PoolError::Empty`]. If any word isn't a valid guess,
+    /// according to [`Game::is_valid_guess`], we return [`PoolError::InvalidWord`].
+    pub fn new(name: &str, words: &[&str], days: RangeInclusive<u64>) -> Result<Self, PoolError> {
+        if words.is_empty() {
+            return Err(PoolError::Empty);
+        }
+
+        let words = words
+            .iter()
+            .map(|&word| {
+                Game::is_valid_guess(word)
+                    .map(|()| word.to_ascii_uppercase())
+                    .map_err(|error| PoolError::InvalidWord(word.to_string(), error))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            name: name.to_string(),
+            words,
+            days,
+            regions: Vec::new(),
+        })
+    }
+
+    /// Restrict this pool to the given regions.
+    ///
+    /// Regions are just names, like country codes, and it's up to the deployment to decide what
+    /// they mean.
+    pub fn in_regions(mut self, regions: &[&str]) -> Self {
+        self.regions = regions.iter().map(|region| region.to_string()).collect();
+        self
+    }
+
+    /// Get the name of this pool.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the answers in this pool, in uppercase.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Check if this pool is active on the given epoch day in the given region.
+    ///
+    /// A pool without any regions is active in every region, including when the region is
+    /// [`None`]. A pool with regions is only active in those regions.
+    pub fn is_active(&self, day: u64, region: Option<&str>) -> bool {
+        self.days.contains(&day)
+            && (self.regions.is_empty()
+                || region.is_some_and(|region| self.regions.iter().any(|r| r == region)))
+    }
+}
+
+/// A collection of [`AnswerPool`]s.
+///
+/// If several pools are active at once, then the one registered last takes priority. If none of
+/// them are active, then answers come from [`GOOD_WORDS`] as usual.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnswerPools {
+    /// The registered pools, in the order they were registered.
+    pools: Vec<AnswerPool>,
+}
+
+impl AnswerPools {
+    /// Create an empty collection of pools.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a pool, which takes priority over every pool registered before it.
+    pub fn register(&mut self, pool: AnswerPool) {
+        self.pools.push(pool);
+    }
+
+    /// Get the pool that's active on the given epoch day in the given region, if there is one.
+    pub fn active(&self, day: u64, region: Option<&str>) -> Option<&AnswerPool> {
+        self.pools
+            .iter()
+            .rev()
+            .find(|pool| pool.is_active(day, region))
+    }
+
+    /// Get the daily word for the given epoch day in the given region.
+    ///
+    /// If a pool is active, then the word is chosen from that pool in the same way that
+    /// [`daily::daily_word`] chooses from [`GOOD_WORDS`]. Otherwise, it's just the normal daily
+    /// word.
+    pub fn daily_word(&self, day: u64, region: Option<&str>) -> &str {
+        match self.active(day, region) {
+            Some(pool) => daily::splitmix64(day)
+                .checked_rem(pool.words.len() as u64)
+                // The remainder is less than the pool length, so it always fits in a usize
+                .and_then(|index| pool.words.get(index as usize))
+                .map_or(FALLBACK_WORD, String::as_str),
+            None => daily::daily_word(day),
+        }
+    }
+
+    /// Get a random answer for the given epoch day in the given region.
+    ///
+    /// If a pool is active, then the word is chosen from that pool. Otherwise, it's chosen from
+    /// [`GOOD_WORDS`].
+    pub fn random_word(&self, day: u64, region: Option<&str>) -> &str {
+        let mut rng = rand::thread_rng();
+
+        match self.active(day, region) {
+            Some(pool) => pool
+                .words
+                .choose(&mut rng)
+                .map_or(FALLBACK_WORD, String::as_str),
+            None => GOOD_WORDS.choose(&mut rng).unwrap_or(&FALLBACK_WORD),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn winter() -> AnswerPool {
+        AnswerPool::new("Winter", &["frost", "SNOWY", "Chill"], 19692..=19722).unwrap()
+    }
+
+    #[test]
+    fn new_pool_checks_words() {
+        assert_eq!(winter().words(), ["FROST", "SNOWY", "CHILL"]);
+        assert_eq!(AnswerPool::new("Empty", &[], 0..=1), Err(PoolError::Empty));
+        assert_eq!(
+            AnswerPool::new("Bad", &["FROST", "FROSTY"], 0..=1),
+            Err(PoolError::InvalidWord(
+                "FROSTY".to_string(),
+                GuessError::WrongWordLength
+            ))
+        );
+    }
+
+    #[test]
+    fn pools_are_active_in_their_windows_and_regions() {
+        let pool = winter();
+        assert!(!pool.is_active(19691, None));
+        assert!(pool.is_active(19692, None));
+        assert!(pool.is_active(19722, Some("GB")));
+        assert!(!pool.is_active(19723, None));
+
+        let pool = winter().in_regions(&["GB", "US"]);
+        assert!(pool.is_active(19700, Some("GB")));
+        assert!(!pool.is_active(19700, Some("AU")));
+        assert!(!pool.is_active(19700, None));
+    }
+
+    #[test]
+    fn later_pools_take_priority() {
+        let mut pools = AnswerPools::new();
+        assert_eq!(pools.daily_word(19358, None), daily::daily_word(19358));
+
+        pools.register(winter());
+        pools.register(AnswerPool::new("New Year", &["CHEER"], 19722..=19723).unwrap());
+
+        assert_eq!(
+            pools.active(19700, None).map(AnswerPool::name),
+            Some("Winter")
+        );
+        assert_eq!(
+            pools.active(19722, None).map(AnswerPool::name),
+            Some("New Year")
+        );
+        assert_eq!(pools.active(19724, None), None);
+
+        assert_eq!(pools.daily_word(19722, None), "CHEER");
+        assert!(winter()
+            .words()
+            .contains(&pools.daily_word(19700, None).to_string()));
+        assert_eq!(pools.daily_word(19700, None), pools.daily_word(19700, None));
+        assert_eq!(pools.random_word(19723, None), "CHEER");
+        assert_eq!(pools.daily_word(19724, None), daily::daily_word(19724));
+    }
+}
@@ -0,0 +1,88 @@
+//! This module handles identifying which puzzle a game is, so that results, replays, and stats
+//! can say which puzzle they belong to.
+
+use std::fmt;
+
+/// The identifier of the puzzle that a [`Game`](super::Game) is playing.
+///
+/// The [`Display`](fmt::Display) implementation gives a short identifier like `daily-19358`,
+/// which can be parsed again with [`PuzzleId::parse`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PuzzleId {
+    /// The daily puzzle for this epoch day. See [`Game::daily`](super::Game::daily).
+    Daily(u64),
+
+    /// Any other puzzle, including games with a random word, which can't be identified.
+    #[default]
+    Custom,
+}
+
+impl PuzzleId {
+    /// Parse an identifier from its [`Display`](fmt::Display) form, or return [`None`] if it
+    /// isn't valid.
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "custom" => Some(Self::Custom),
+            _ => text
+                .strip_prefix("daily-")
+                .filter(|day| day.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|day| day.parse().ok())
+                .map(Self::Daily),
+        }
+    }
+
+    /// Check whether this puzzle can be identified, meaning that every game with this identifier
+    /// has the same answer, as long as they use the same
+    /// [`AnswerPools`](super::pools::AnswerPools) and region.
+    pub fn is_identified(&self) -> bool {
+        !matches!(self, Self::Custom)
+    }
+}
+
+impl fmt::Display for PuzzleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Daily(day) => write!(f, "daily-{day}"),
+            Self::Custom => write!(f, "custom"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Game;
+
+    #[test]
+    fn puzzle_id_round_trip() {
+        for id in [
+            PuzzleId::Daily(0),
+            PuzzleId::Daily(u64::MAX),
+            PuzzleId::Custom,
+        ] {
+            assert_eq!(PuzzleId::parse(&id.to_string()), Some(id));
+        }
+        assert_eq!(PuzzleId::Daily(19358).to_string(), "daily-19358");
+
+        for text in [
+            "",
+            "daily-",
+            "daily-+1",
+            "daily--1",
+            "daily-1.5",
+            "Daily-1",
+            "seeded-1",
+        ] {
+            assert_eq!(PuzzleId::parse(text), None, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn games_carry_their_puzzle() {
+        assert_eq!(Game::daily(19358).puzzle, PuzzleId::Daily(19358));
+        assert!(Game::daily(19358).puzzle.is_identified());
+        assert_eq!(Game::new().puzzle, PuzzleId::Custom);
+        assert!(!Game::new().puzzle.is_identified());
+    }
+}
@@ -0,0 +1,25 @@
+//! This module handles rendering boards as images, HTML, terminal output, and text.
+//!
+//! It's only available with the `render` feature.
+
+pub mod ansi;
+pub mod html;
+pub mod svg;
+pub mod text;
+
+/// Escape the characters that are special in HTML and SVG text and attributes.
+///
+/// Everything interpolated into [`html`] or [`svg`] output goes through this, letters included,
+/// since a [`Letter`](crate::letters::Letter) can hold any character.
+pub(crate) fn escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&#39;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
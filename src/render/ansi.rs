@@ -0,0 +1,282 @@
+//! This module handles rendering guesses and the keyboard for terminals, with ANSI escape codes.
+//!
+//! The colours come from a [`Theme`]. They can be turned off with [`AnsiOptions::colour`], which
+//! [`AnsiOptions::from_env`] does when the `NO_COLOR` environment variable is set. Without colour,
+//! positions are shown by brackets and case instead:
+//!
+//! - `[A]` is [`Correct`](Position::Correct)
+//! - `(A)` is [`WrongPosition`](Position::WrongPosition)
+//! - ` a ` is [`NotInWord`](Position::NotInWord)
+//! - ` A ` is a key that hasn't been guessed yet
+
+use crate::{
+    keyboard::{Keyboard, Layout},
+    letters::Position,
+    theme::Theme,
+    Word,
+};
+
+/// The escape code to make text bold.
+const BOLD: &str = "\x1b[1m";
+
+/// The escape code to reset all styles.
+const RESET: &str = "\x1b[0m";
+
+/// Options for rendering to a terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnsiOptions {
+    /// Whether to use colours and bold text at all.
+    pub colour: bool,
+
+    /// The theme to take the colours from. See [`TileStyle::ansi`](crate::theme::TileStyle::ansi).
+    pub theme: Theme,
+}
+
+impl AnsiOptions {
+    /// Get the default options, without colour if the `NO_COLOR` environment variable is set to
+    /// anything other than an empty string.
+    ///
+    /// See <https://no-color.org>.
+    pub fn from_env() -> Self {
+        let no_colour = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+
+        Self {
+            colour: !no_colour,
+            ..Self::default()
+        }
+    }
+
+    /// Get the width of a single letter, in columns.
+    fn cell_width(&self) -> usize {
+        if self.colour {
+            1
+        } else {
+            3
+        }
+    }
+
+    /// Render a single letter with the given position, or [`None`] if it hasn't been guessed.
+    fn letter(&self, letter: char, position: Option<Position>) -> String {
+        let letter = letter.to_ascii_uppercase();
+
+        if self.colour {
+            let colour = match position {
+                None => self.theme.unguessed_ansi,
+                Some(position) => self.theme.tile(position).ansi,
+            };
+            format!("\x1b[38;5;{colour}m{letter}")
+        } else {
+            match position {
+                None => format!(" {letter} "),
+                Some(Position::NotInWord) => format!(" {} ", letter.to_ascii_lowercase()),
+                Some(Position::WrongPosition) => format!("({letter})"),
+                Some(Position::Correct) => format!("[{letter}]"),
+            }
+        }
+    }
+
+    /// Render a single letter as a grid cell, which is always 3 columns wide.
+    ///
+    /// With colour, the styles are reset at the end of the cell so that they don't leak into the
+    /// grid lines.
+    fn cell(&self, letter: char, position: Position) -> String {
+        if self.colour {
+            format!(" {BOLD}{}{RESET} ", self.letter(letter, Some(position)))
+        } else {
+            self.letter(letter, Some(position))
+        }
+    }
+
+    /// Wrap the text in bold, and reset the styles at the end.
+    fn bold(&self, text: String) -> String {
+        if self.colour {
+            format!("{BOLD}{text}{RESET}")
+        } else {
+            text
+        }
+    }
+}
+
+impl Default for AnsiOptions {
+    fn default() -> Self {
+        Self {
+            colour: true,
+            theme: Theme::default(),
+        }
+    }
+}
+
+/// Render a scored guess on a single line, without a trailing newline.
+pub fn render_guess(word: &Word, options: &AnsiOptions) -> String {
+    options.bold(
+        word.iter()
+            .map(|letter| options.letter(letter.letter, Some(letter.position)))
+            .collect(),
+    )
+}
+
+/// Render the keyboard with each key coloured by the best position it's been seen in, like
+/// [`Game::keyboard`](crate::Game::keyboard).
+///
+/// Each row of the layout is a line, and each row is indented by half a key more than the one
+/// above it. There's no trailing newline.
+pub fn render_keyboard(keyboard: &Keyboard, layout: Layout, options: &AnsiOptions) -> String {
+    let indent = options.cell_width().div_ceil(2);
+
+    let rows: Vec<String> = keyboard
+        .iter_rows(layout)
+        .enumerate()
+        .map(|(index, row)| {
+            let keys: Vec<String> = row
+                .map(|(key, position)| options.letter(key, position))
+                .collect();
+            format!("{}{}", " ".repeat(index * indent), keys.join(" "))
+        })
+        .collect();
+
+    options.bold(rows.join("\n"))
+}
+
+/// Get the width of the widest row of a keyboard rendered with [`render_keyboard`], which is the
+/// top row of every [`Layout`].
+fn keyboard_width(options: &AnsiOptions) -> usize {
+    let keys = Layout::ALL
+        .iter()
+        .map(|layout| layout.rows()[0].len())
+        .max()
+        .unwrap_or(0);
+    (keys * (options.cell_width() + 1)).saturating_sub(1)
+}
+
+/// Render each guess on its own line, centred over a keyboard rendered with
+/// [`render_keyboard`]. There's no trailing newline.
+pub fn render_board(guesses: &[Word], options: &AnsiOptions) -> String {
+    let keyboard_width = keyboard_width(options);
+    let indent = " ".repeat((keyboard_width - 5 * options.cell_width()) / 2);
+
+    guesses
+        .iter()
+        .map(|word| format!("{indent}{}", render_guess(word, options)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render the guesses in a grid drawn with box-drawing characters, like the board in the web app.
+///
+/// The grid has `rows` rows, or one for each guess if there are more guesses than that, so the
+/// rows that haven't been used yet are shown empty. Like [`render_board`], it's centred over a
+/// keyboard rendered with [`render_keyboard`], and there's no trailing newline.
+pub fn render_grid(guesses: &[Word], rows: usize, options: &AnsiOptions) -> String {
+    /// The number of columns in each cell, not counting the grid lines.
+    const CELL_WIDTH: usize = 3;
+
+    let keyboard_width = keyboard_width(options);
+    let grid_width = 5 * (CELL_WIDTH + 1) + 1;
+    let indent = " ".repeat(keyboard_width.saturating_sub(grid_width) / 2);
+
+    let line = |left: &str, middle: &str, right: &str| {
+        format!(
+            "{indent}{left}{}{right}",
+            vec!["─".repeat(CELL_WIDTH); 5].join(middle)
+        )
+    };
+
+    let mut lines = vec![line("┌", "┬", "┐")];
+
+    for row in 0..rows.max(guesses.len()) {
+        if row > 0 {
+            lines.push(line("├", "┼", "┤"));
+        }
+
+        let cells: Vec<String> = match guesses.get(row) {
+            Some(word) => word
+                .iter()
+                .map(|letter| options.cell(letter.letter, letter.position))
+                .collect(),
+            None => vec![" ".repeat(CELL_WIDTH); 5],
+        };
+        lines.push(format!("{indent}│{}│", cells.join("│")));
+    }
+
+    lines.push(line("└", "┴", "┘"));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{scoring::score_guess, Game};
+
+    const PLAIN: AnsiOptions = AnsiOptions {
+        colour: false,
+        theme: Theme::CLASSIC,
+    };
+
+    #[test]
+    fn render_guess_with_and_without_colour() {
+        let word = score_guess("DYSON", "SOUND").unwrap();
+
+        assert_eq!(render_guess(&word, &PLAIN), "(S)(O) u (N)(D)");
+        assert_eq!(
+            render_guess(&word, &AnsiOptions::default()),
+            concat!(
+                "\x1b[1m",
+                "\x1b[38;5;3mS\x1b[38;5;3mO\x1b[38;5;0mU\x1b[38;5;3mN\x1b[38;5;3mD",
+                "\x1b[0m"
+            )
+        );
+    }
+
+    #[test]
+    fn render_keyboard_rows() {
+        let mut game = Game {
+            word: "DYSON".to_string().into(),
+            ..Game::new()
+        };
+        game.make_guess("DRONE").unwrap();
+
+        let keyboard = render_keyboard(&game.keyboard, Layout::Qwerty, &PLAIN);
+        let lines: Vec<&str> = keyboard.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with(" Q   W   e   r   T   Y "));
+        assert!(lines[1].starts_with("   A   S  [D]  F "));
+
+        let keyboard = render_keyboard(&game.keyboard, Layout::Azerty, &PLAIN);
+        assert!(keyboard.starts_with(" A   Z   e   r   T   Y "));
+    }
+
+    #[test]
+    fn render_grid_with_empty_rows() {
+        let guesses = [score_guess("DYSON", "SOUND").unwrap()];
+        let indent = " ".repeat(9);
+
+        assert_eq!(
+            render_grid(&guesses, 2, &PLAIN),
+            [
+                "┌───┬───┬───┬───┬───┐",
+                "│(S)│(O)│ u │(N)│(D)│",
+                "├───┼───┼───┼───┼───┤",
+                "│   │   │   │   │   │",
+                "└───┴───┴───┴───┴───┘",
+            ]
+            .map(|line| format!("{indent}{line}"))
+            .join("\n")
+        );
+
+        let coloured = render_grid(&guesses, 0, &AnsiOptions::default());
+        assert_eq!(coloured.lines().count(), 3);
+        assert!(coloured.starts_with("┌───┬"));
+        assert!(coloured.contains("│ \x1b[1m\x1b[38;5;3mS\x1b[0m │"));
+    }
+
+    #[test]
+    fn render_board_is_centred() {
+        let guesses = [score_guess("DYSON", "DYSON").unwrap()];
+
+        assert_eq!(
+            render_board(&guesses, &PLAIN),
+            " ".repeat(12) + "[D][Y][S][O][N]"
+        );
+        assert!(render_board(&guesses, &AnsiOptions::default()).starts_with("       \x1b[1m"));
+    }
+}
@@ -0,0 +1,186 @@
+//! This module handles rendering a finished game as static HTML, for embedding in other pages or
+//! printing.
+//!
+//! The HTML doesn't need any scripts or external resources. The board is drawn with
+//! [`svg::render_board`], and the answer can be hidden behind a `<details>` element, so that
+//! readers can click to reveal it.
+
+use super::{
+    escape,
+    svg::{self, SvgOptions},
+};
+use crate::{
+    is_won,
+    messages::{English, Messages},
+    theme::Theme,
+    Word, MAX_GUESSES,
+};
+
+/// Options for [`render_figure`] and [`render_page`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HtmlOptions {
+    /// The theme to draw the board with.
+    pub theme: Theme,
+
+    /// Draw the board without its letters and put the answer behind a click-to-reveal element, so
+    /// that the HTML doesn't spoil the answer.
+    pub hide_answer: bool,
+}
+
+/// Render the board and the answer as a `<figure>` element in English. See
+/// [`render_figure_with`].
+pub fn render_figure(guesses: &[Word], answer: &str, options: &HtmlOptions) -> String {
+    render_figure_with(guesses, answer, options, &English)
+}
+
+/// Render the board and the answer as a `<figure>` element, which can be embedded in any page.
+///
+/// The caption has the number of guesses out of [`MAX_GUESSES`] like share text, with `X` for a
+/// lost game.
+pub fn render_figure_with(
+    guesses: &[Word],
+    answer: &str,
+    options: &HtmlOptions,
+    messages: &impl Messages,
+) -> String {
+    let board = svg::render_board(
+        guesses,
+        &SvgOptions {
+            theme: options.theme,
+            hide_letters: options.hide_answer,
+        },
+    );
+    let answer = answer.to_ascii_uppercase();
+    let score = if is_won(guesses) {
+        guesses.len().to_string()
+    } else {
+        "X".to_string()
+    };
+
+    let caption = if options.hide_answer {
+        format!(
+            r#"<details class="wordle-answer"><summary>{}</summary>{}</details>"#,
+            escape(&messages.show_answer()),
+            escape(&answer)
+        )
+    } else {
+        format!(
+            r#"<span class="wordle-answer">{}</span>"#,
+            escape(&messages.answer_was(&answer))
+        )
+    };
+
+    format!(
+        r#"<figure class="wordle-board">{board}<figcaption>{score}/{MAX_GUESSES} · {caption}</figcaption></figure>"#
+    )
+}
+
+/// Render the board and the answer as a complete HTML page in English. See [`render_page_with`].
+pub fn render_page(guesses: &[Word], answer: &str, options: &HtmlOptions) -> String {
+    render_page_with(guesses, answer, options, &English)
+}
+
+/// Render the board and the answer as a complete HTML page, which prints with the colours of the
+/// tiles intact.
+///
+/// The `lang` attribute of the page is the [`language`](Messages::language) of the messages.
+pub fn render_page_with(
+    guesses: &[Word],
+    answer: &str,
+    options: &HtmlOptions,
+    messages: &impl Messages,
+) -> String {
+    format!(
+        concat!(
+            "<!DOCTYPE html>\n",
+            r#"<html lang="{}"><head><meta charset="utf-8"><title>Wordle</title><style>"#,
+            "body {{ font-family: sans-serif; text-align: center; }} ",
+            "figure svg {{ max-width: 100%; height: auto; }} ",
+            "@media print {{ * {{ print-color-adjust: exact; -webkit-print-color-adjust: exact; }} }}",
+            "</style></head><body>{}</body></html>\n"
+        ),
+        escape(&messages.language()),
+        render_figure_with(guesses, answer, options, messages)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{messages::MessageTable, scoring::score_guess};
+
+    #[test]
+    fn render_page_shows_answer() {
+        let guesses = [
+            score_guess("DYSON", "CRANE").unwrap(),
+            score_guess("DYSON", "DYSON").unwrap(),
+        ];
+
+        let page = render_page(&guesses, "dyson", &HtmlOptions::default());
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains(&svg::render_board(&guesses, &SvgOptions::default())));
+        assert!(page.contains("2/6 · "));
+        assert!(page.contains("The answer was DYSON"));
+        assert!(!page.contains("<details"));
+        assert!(!page.contains("<script"));
+    }
+
+    #[test]
+    fn render_figure_can_hide_answer() {
+        let guesses = [score_guess("DYSON", "DYSON").unwrap()];
+        let options = HtmlOptions {
+            theme: Theme::DARK,
+            hide_answer: true,
+        };
+
+        let figure = render_figure(&guesses, "DYSON", &options);
+        assert!(figure.starts_with("<figure"));
+        assert!(!figure.contains("<text"));
+        assert!(figure.contains("<summary>Show the answer</summary>DYSON</details>"));
+        assert!(figure.contains(&Theme::DARK.background.to_string()));
+
+        let escaped = render_figure(&[], "<b>", &options);
+        assert!(escaped.contains("X/6 · "));
+        assert!(escaped.contains("&lt;B&gt;"));
+        assert!(!escaped.contains("<B>"));
+
+        let shown = render_figure(&[], "<b>", &HtmlOptions::default());
+        assert!(shown.contains("The answer was &lt;B&gt;"));
+    }
+
+    #[test]
+    fn render_figure_scores_like_share_text() {
+        let mut guesses = vec![score_guess("CRANE", "DYSON").unwrap(); MAX_GUESSES];
+        guesses.push(score_guess("DYSON", "DYSON").unwrap());
+
+        let late = render_figure(&guesses, "DYSON", &HtmlOptions::default());
+        assert!(late.contains(&format!("X/{MAX_GUESSES} · ")));
+
+        let last = render_figure(&guesses[1..], "DYSON", &HtmlOptions::default());
+        assert!(last.contains(&format!("{MAX_GUESSES}/{MAX_GUESSES} · ")));
+    }
+
+    #[test]
+    fn render_page_uses_messages() {
+        let guesses = [score_guess("DYSON", "DYSON").unwrap()];
+        let table = MessageTable::parse(
+            "
+            language = fr
+            show-answer = Voir la réponse
+            answer-was = La réponse était {word}
+            ",
+        );
+
+        let page = render_page_with(&guesses, "DYSON", &HtmlOptions::default(), &table);
+        assert!(page.contains(r#"<html lang="fr">"#));
+        assert!(page.contains("La réponse était DYSON"));
+
+        let options = HtmlOptions {
+            hide_answer: true,
+            ..HtmlOptions::default()
+        };
+        let figure = render_figure_with(&guesses, "DYSON", &options, &table);
+        assert!(figure.contains("<summary>Voir la réponse</summary>DYSON</details>"));
+        assert!(render_page(&guesses, "DYSON", &options).contains(r#"<html lang="en">"#));
+    }
+}
@@ -0,0 +1,135 @@
+//! This module handles rendering a board as an SVG image.
+
+use super::escape;
+use crate::{theme::Theme, Word};
+use std::fmt::Write;
+
+/// The width and height of a tile, in pixels.
+const TILE_SIZE: usize = 62;
+
+/// The gap between tiles, in pixels.
+const GAP: usize = 5;
+
+/// The padding around the board, in pixels.
+const PADDING: usize = 10;
+
+/// Options for [`render_board`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SvgOptions {
+    /// The theme to draw the board with.
+    pub theme: Theme,
+
+    /// Only draw the colours of the tiles, and not the letters, so that the image doesn't spoil
+    /// the answer.
+    pub hide_letters: bool,
+}
+
+/// Render the guesses as an SVG image, with one row per guess.
+///
+/// Tiles are 62 pixels square with 5 pixel gaps between them, and there's 10 pixels of padding
+/// around the board, so the image is always 350 pixels wide.
+pub fn render_board(guesses: &[Word], options: &SvgOptions) -> String {
+    let width = 2 * PADDING + 5 * TILE_SIZE + 4 * GAP;
+    let height = 2 * PADDING + (guesses.len() * (TILE_SIZE + GAP)).saturating_sub(GAP);
+
+    let mut svg = format!(
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" "#,
+            r#"viewBox="0 0 {width} {height}">"#,
+            r#"<rect width="100%" height="100%" fill="{background}"/>"#
+        ),
+        width = width,
+        height = height,
+        background = options.theme.background,
+    );
+
+    for (row, word) in guesses.iter().enumerate() {
+        for (column, letter) in word.iter().enumerate() {
+            let x = PADDING + column * (TILE_SIZE + GAP);
+            let y = PADDING + row * (TILE_SIZE + GAP);
+
+            // Writing to a String can't fail
+            let _ = write!(
+                svg,
+                r#"<rect x="{x}" y="{y}" width="{TILE_SIZE}" height="{TILE_SIZE}" fill="{}"/>"#,
+                options.theme.tile(letter.position).colour
+            );
+
+            if !options.hide_letters {
+                let _ = write!(
+                    svg,
+                    concat!(
+                        r#"<text x="{}" y="{}" fill="{}" font-family="sans-serif" "#,
+                        r#"font-size="32" font-weight="bold" text-anchor="middle" "#,
+                        r#"dominant-baseline="central">{}</text>"#
+                    ),
+                    x + TILE_SIZE / 2,
+                    y + TILE_SIZE / 2,
+                    options.theme.text,
+                    escape(&letter.letter.to_ascii_uppercase().to_string())
+                );
+            }
+        }
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::score_guess;
+
+    #[test]
+    fn render_board_escapes_letters() {
+        let mut word = score_guess("DYSON", "CRANE").unwrap();
+        word[0].letter = '<';
+        word[1].letter = '&';
+
+        let svg = render_board(&[word], &SvgOptions::default());
+        assert!(svg.contains(">&lt;</text>"));
+        assert!(svg.contains(">&amp;</text>"));
+        assert!(!svg.contains("><</text>"));
+    }
+
+    #[test]
+    fn render_board_draws_every_tile() {
+        let guesses = [
+            score_guess("DYSON", "CRANE").unwrap(),
+            score_guess("DYSON", "DYSON").unwrap(),
+        ];
+
+        let svg = render_board(&guesses, &SvgOptions::default());
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r#"width="350" height="149""#));
+        assert!(svg.ends_with("</svg>"));
+
+        // One background plus 10 tiles
+        assert_eq!(svg.matches("<rect").count(), 11);
+        assert_eq!(svg.matches("<text").count(), 10);
+        let correct = Theme::CLASSIC.correct.colour.to_string();
+        assert_eq!(svg.matches(&correct).count(), 5);
+        assert!(svg.contains(">Y</text>"));
+    }
+
+    #[test]
+    fn render_board_can_hide_letters() {
+        let guesses = [score_guess("DYSON", "CRANE").unwrap()];
+        let options = SvgOptions {
+            theme: Theme::DARK,
+            hide_letters: true,
+        };
+
+        let svg = render_board(&guesses, &options);
+        assert_eq!(svg.matches("<rect").count(), 6);
+        assert!(!svg.contains("<text"));
+        assert!(svg.contains(&Theme::DARK.background.to_string()));
+    }
+
+    #[test]
+    fn render_empty_board() {
+        let svg = render_board(&[], &SvgOptions::default());
+        assert!(svg.contains(r#"width="350" height="20""#));
+    }
+}
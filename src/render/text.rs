@@ -0,0 +1,102 @@
+//! This module handles describing guesses and the keyboard in sentences, for screen readers.
+//!
+//! The phrasing comes from [`Messages`], so it can be translated like the rest of the game.
+
+use crate::{
+    keyboard::Keyboard,
+    letters::Position,
+    messages::{English, Messages},
+    Word,
+};
+
+/// Describe a scored guess in English. See [`describe_guess_with`].
+pub fn describe_guess(word: &Word) -> String {
+    describe_guess_with(word, &English)
+}
+
+/// Describe a scored guess, with one clause per letter in order, like "C is correct, R is not in
+/// the word, ...".
+pub fn describe_guess_with(word: &Word, messages: &impl Messages) -> String {
+    let clauses: Vec<String> = word
+        .iter()
+        .map(|letter| messages.describe_letter(letter.letter.to_ascii_uppercase(), letter.position))
+        .collect();
+
+    format!("{}.", clauses.join(", "))
+}
+
+/// Describe the keyboard in English. See [`describe_keyboard_with`].
+pub fn describe_keyboard(keyboard: &Keyboard) -> String {
+    describe_keyboard_with(keyboard, &English)
+}
+
+/// Describe the keys that have been guessed, like [`Game::keyboard`](crate::Game::keyboard),
+/// grouped by their best position.
+///
+/// The groups are in the order [`Correct`](Position::Correct),
+/// [`WrongPosition`](Position::WrongPosition), and then [`NotInWord`](Position::NotInWord), with
+/// the letters in alphabetical order. Empty groups and keys that haven't been guessed are left
+/// out, so this is empty before the first guess.
+pub fn describe_keyboard_with(keyboard: &Keyboard, messages: &impl Messages) -> String {
+    let groups: Vec<String> = [
+        Position::Correct,
+        Position::WrongPosition,
+        Position::NotInWord,
+    ]
+    .into_iter()
+    .filter_map(|position| {
+        let letters: String = keyboard
+            .iter()
+            .filter(|&(_, best)| best == Some(position))
+            .map(|(letter, _)| letter)
+            .collect();
+
+        (!letters.is_empty()).then(|| messages.describe_keys(position, &letters))
+    })
+    .collect();
+
+    if groups.is_empty() {
+        String::new()
+    } else {
+        format!("{}.", groups.join(". "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{messages::MessageTable, scoring::score_guess, Game};
+
+    #[test]
+    fn describe_guesses() {
+        let word = score_guess("DYSON", "SOUND").unwrap();
+        assert_eq!(
+            describe_guess(&word),
+            concat!(
+                "S is in the wrong position, O is in the wrong position, U is not in the word, ",
+                "N is in the wrong position, D is in the wrong position."
+            )
+        );
+
+        let table = MessageTable::parse("describe-not-in-word = {letter} n'est pas dans le mot");
+        assert!(describe_guess_with(&word, &table).contains("U n'est pas dans le mot"));
+    }
+
+    #[test]
+    fn describe_keyboards() {
+        let mut game = Game {
+            word: "DYSON".to_string().into(),
+            ..Game::new()
+        };
+        assert_eq!(describe_keyboard(&game.keyboard), "");
+
+        game.make_guess("DRONE").unwrap();
+        assert_eq!(
+            describe_keyboard(&game.keyboard),
+            "Correct: D. In the wrong position: N, O. Not in the word: E, R."
+        );
+
+        let table = MessageTable::parse("keys-correct = Bien placées : {letters}");
+        assert!(describe_keyboard_with(&game.keyboard, &table).starts_with("Bien placées : D."));
+    }
+}
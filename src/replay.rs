@@ -0,0 +1,458 @@
+//! This module handles recording the input to a game, so that bugs can be reproduced exactly.
+//!
+//! A [`Recorder`] captures the settings of a game and every guess entered into it, including
+//! invalid ones, as a [`Script`]. The script can be saved as text with its
+//! [`Display`](fmt::Display) implementation, loaded again with [`Script::parse`], and played back
+//! with [`Game::replay_script`](super::Game::replay_script).
+//!
+//! The text format is one command per line. Blank lines and lines starting with `#` are ignored.
+//!
+//! ```text
+//! # A game with lenient scoring
+//! answer DYSON
+//! puzzle custom
+//! rules word-length=5 max-guesses=6 scoring=lenient dictionary=e90ae6991a55f3ab banned-openers=CRANE
+//! normalize strip-diacritics
+//! guess 4210 crane
+//! guess 9035  Audio
+//! ```
+//!
+//! The `puzzle` line is a [`PuzzleId`], and can be left out for a [`Custom`](PuzzleId::Custom)
+//! puzzle. The `rules` line is in the format of [`Rules`]. The `normalize` line lists the optional
+//! steps of the [`Normalization`] that the frontend applied to each guess, and is left out if it
+//! only applied the default steps. Each `guess` line has the number of milliseconds since
+//! recording started, a space, and then the guess exactly as it was entered, including any
+//! whitespace.
+
+use super::{
+    normalize::{normalize_guess_with, Normalization},
+    puzzle::PuzzleId,
+    rules::{Rules, RulesError},
+    scoring::ScoringRules,
+    spoiler::SpoilerGuard,
+    Game, GuessError, Word,
+};
+use std::{fmt, time::Instant};
+use thiserror::Error;
+
+/// An enum representing possible errors from parsing a [`Script`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The script doesn't have an `answer` line.
+    #[error("The script doesn't have an answer")]
+    MissingAnswer,
+
+    /// The script doesn't have a `rules` line.
+    #[error("The script doesn't have any rules")]
+    MissingRules,
+
+    /// The game was played under rules that this version of the library can't reproduce, like
+    /// [`Custom`](ScoringRules::Custom) scoring rules or a different dictionary. See
+    /// [`Rules::is_supported`].
+    #[error("Games with these rules can't be replayed: {0}")]
+    UnsupportedRules(Rules),
+
+    /// The `rules` command on the line with this number (starting from 1) is invalid.
+    #[error("Invalid rules on line {0}: {1}")]
+    InvalidRules(usize, #[source] RulesError),
+
+    /// The `puzzle` command on the line with this number (starting from 1) isn't a valid
+    /// [`PuzzleId`].
+    #[error("Invalid puzzle on line {0}: {1:?}")]
+    InvalidPuzzle(usize, String),
+
+    /// The line with this number (starting from 1) isn't a valid command.
+    #[error("Line {0} isn't a valid command: {1:?}")]
+    InvalidLine(usize, String),
+}
+
+/// A single guess entered during a recorded game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScriptGuess {
+    /// The number of milliseconds between the start of the recording and this guess.
+    pub millis: u128,
+
+    /// The guess exactly as it was entered, which may be invalid.
+    pub guess: String,
+}
+
+/// The recorded settings and input of a game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Script {
+    /// The target word.
+    pub answer: String,
+
+    /// The puzzle that the game was playing.
+    pub puzzle: PuzzleId,
+
+    /// The rules that the game was played under.
+    pub rules: Rules,
+
+    /// How each guess was normalized before it was passed to the game.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub normalization: Normalization,
+
+    /// Every guess entered, in order.
+    pub guesses: Vec<ScriptGuess>,
+}
+
+impl Script {
+    /// Record the answer and [`Rules`] of the given game, with no guesses yet.
+    pub fn for_game(game: &Game) -> Self {
+        Self {
+            answer: game.word.reveal_for_display().clone(),
+            puzzle: game.puzzle,
+            rules: game.rules(),
+            normalization: Normalization::default(),
+            guesses: Vec::new(),
+        }
+    }
+
+    /// Parse a script from the text format. See the [module documentation](self).
+    ///
+    /// # Errors
+    ///
+    /// If any line isn't a valid command, or there's no `answer` or `rules` line, we return the
+    /// appropriate [`ReplayError`] variant.
+    pub fn parse(text: &str) -> Result<Self, ReplayError> {
+        let mut answer = None;
+        let mut puzzle = PuzzleId::Custom;
+        let mut rules = None;
+        let mut normalization = Normalization::default();
+        let mut guesses = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || ReplayError::InvalidLine(index + 1, line.to_string());
+            let (command, argument) = trimmed.split_once(' ').ok_or_else(invalid)?;
+
+            match command {
+                "answer" => answer = Some(argument.to_string()),
+                "puzzle" => {
+                    puzzle = PuzzleId::parse(argument).ok_or_else(|| {
+                        ReplayError::InvalidPuzzle(index + 1, argument.to_string())
+                    })?;
+                }
+                "rules" => {
+                    rules = Some(
+                        Rules::parse(argument)
+                            .map_err(|error| ReplayError::InvalidRules(index + 1, error))?,
+                    );
+                }
+                "normalize" => {
+                    for step in argument.split_whitespace() {
+                        match step {
+                            "strip-apostrophes" => normalization.strip_apostrophes = true,
+                            "strip-diacritics" => normalization.strip_diacritics = true,
+                            _ => return Err(invalid()),
+                        }
+                    }
+                }
+                "guess" => {
+                    // The guess is everything after the first space after the time, so it keeps
+                    // any whitespace that was entered, which trimming the line would remove
+                    let untrimmed = line.trim_start();
+                    let argument = untrimmed.get(command.len() + 1..).unwrap_or_default();
+                    let (millis, guess) = argument.split_once(' ').unwrap_or((argument, ""));
+                    guesses.push(ScriptGuess {
+                        millis: millis.parse().map_err(|_| invalid())?,
+                        guess: guess.to_string(),
+                    });
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(Self {
+            answer: answer.ok_or(ReplayError::MissingAnswer)?,
+            puzzle,
+            rules: rules.ok_or(ReplayError::MissingRules)?,
+            normalization,
+            guesses,
+        })
+    }
+
+    /// Create a game with the recorded settings and no guesses.
+    ///
+    /// # Errors
+    ///
+    /// If the rules aren't [supported](Rules::is_supported) by this version of the library, we
+    /// return [`ReplayError::UnsupportedRules`].
+    pub fn new_game(&self) -> Result<Game, ReplayError> {
+        let scoring_rules = ScoringRules::from_name(&self.rules.scoring)
+            .filter(|_| self.rules.is_supported())
+            .ok_or_else(|| ReplayError::UnsupportedRules(self.rules.clone()))?;
+
+        let mut game = Game {
+            word: SpoilerGuard::new(self.answer.to_ascii_uppercase()),
+            scoring_rules,
+            puzzle: self.puzzle,
+            ..Game::new()
+        };
+        game.policy.banned_openers = self.rules.banned_openers.iter().cloned().collect();
+        game.policy.min_opener_vowels = self.rules.min_opener_vowels;
+        game.policy.hard_mode = self.rules.hard_mode;
+        game.policy.reject_repeated_guesses = self.rules.reject_repeated_guesses;
+
+        Ok(game)
+    }
+
+    /// Normalize a recorded guess like the frontend did, and make it in the game.
+    ///
+    /// # Errors
+    ///
+    /// See [`normalize_guess_with`] and [`Game::make_guess`].
+    pub(crate) fn replay_guess(&self, game: &mut Game, guess: &str) -> Result<Word, GuessError> {
+        game.make_guess(&normalize_guess_with(guess, &self.normalization)?)
+    }
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "answer {}", self.answer)?;
+        writeln!(f, "puzzle {}", self.puzzle)?;
+        writeln!(f, "rules {}", self.rules)?;
+        if self.normalization.strip_apostrophes || self.normalization.strip_diacritics {
+            f.write_str("normalize")?;
+            if self.normalization.strip_apostrophes {
+                f.write_str(" strip-apostrophes")?;
+            }
+            if self.normalization.strip_diacritics {
+                f.write_str(" strip-diacritics")?;
+            }
+            writeln!(f)?;
+        }
+        for ScriptGuess { millis, guess } in &self.guesses {
+            writeln!(f, "guess {millis} {guess}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Records the input to a game as a [`Script`].
+///
+/// Recording is opt-in. Frontends create a recorder when a game starts and call
+/// [`record_guess`](Recorder::record_guess) with every guess that's entered, before passing it to
+/// the game.
+///
+/// This uses [`std::time::Instant`], so it should only be used on native targets.
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    /// The script recorded so far.
+    script: Script,
+
+    /// When recording started.
+    start: Instant,
+}
+
+impl Recorder {
+    /// Start recording the given game, which shouldn't have had any guesses yet.
+    ///
+    /// The guesses are assumed to be normalized with the default [`Normalization`], like with
+    /// [`normalize_guess`](super::normalize::normalize_guess).
+    pub fn new(game: &Game) -> Self {
+        Self::with_normalization(game, Normalization::default())
+    }
+
+    /// Start recording the given game, where the frontend normalizes each guess with the given
+    /// [`Normalization`] before passing it to the game.
+    pub fn with_normalization(game: &Game, normalization: Normalization) -> Self {
+        Self {
+            script: Script {
+                normalization,
+                ..Script::for_game(game)
+            },
+            start: Instant::now(),
+        }
+    }
+
+    /// Record a guess exactly as it was entered.
+    pub fn record_guess(&mut self, guess: &str) {
+        self.script.guesses.push(ScriptGuess {
+            millis: self.start.elapsed().as_millis(),
+            guess: guess.to_string(),
+        });
+    }
+
+    /// Get the script recorded so far.
+    pub fn script(&self) -> &Script {
+        &self.script
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GuessError;
+
+    #[test]
+    fn script_round_trip() {
+        let mut game = Game {
+            word: "DYSON".to_string().into(),
+            scoring_rules: ScoringRules::LenientDuplicates,
+            ..Game::new()
+        };
+        game.policy.banned_openers = ["SOARE", "CRANE"].map(String::from).into();
+        game.policy.min_opener_vowels = Some(2);
+        game.policy.hard_mode = true;
+
+        let mut recorder = Recorder::new(&game);
+        recorder.record_guess("crane");
+        recorder.record_guess("");
+        recorder.record_guess("AUDIO");
+
+        let text = recorder.script().to_string();
+        assert!(text.starts_with(&format!(
+            "answer DYSON\npuzzle custom\nrules {}\nguess ",
+            Rules::for_game(&game)
+        )));
+
+        let script = Script::parse(&format!("# A comment\n\n{text}")).unwrap();
+        assert_eq!(&script, recorder.script());
+        assert_eq!(script.guesses[1].guess, "");
+
+        let replayed = script.new_game().unwrap();
+        assert_eq!(replayed.word, game.word);
+        assert_eq!(replayed.scoring_rules, game.scoring_rules);
+        assert_eq!(replayed.policy.banned_openers, game.policy.banned_openers);
+        assert!(replayed.policy.hard_mode);
+
+        let daily = Script::for_game(&Game::daily(19358));
+        let replayed = Script::parse(&daily.to_string()).unwrap();
+        assert_eq!(replayed.puzzle, PuzzleId::Daily(19358));
+        assert_eq!(replayed.new_game().unwrap().puzzle, PuzzleId::Daily(19358));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let rules = format!("rules {}", Rules::default());
+
+        assert_eq!(Script::parse(&rules), Err(ReplayError::MissingAnswer));
+        assert_eq!(
+            Script::parse("answer DYSON"),
+            Err(ReplayError::MissingRules)
+        );
+        assert_eq!(
+            Script::parse("answer DYSON\nrules scoring=classic"),
+            Err(ReplayError::InvalidRules(
+                2,
+                RulesError::MissingRule("word-length")
+            ))
+        );
+        assert_eq!(
+            Script::parse(&format!("answer DYSON\n{rules}\nguess soon CRANE")),
+            Err(ReplayError::InvalidLine(3, "guess soon CRANE".to_string()))
+        );
+        assert_eq!(
+            Script::parse(&format!("answer DYSON\npuzzle weekly-3\n{rules}")),
+            Err(ReplayError::InvalidPuzzle(2, "weekly-3".to_string()))
+        );
+        assert_eq!(
+            Script::parse(&format!("answer DYSON\n{rules}\njump")),
+            Err(ReplayError::InvalidLine(3, "jump".to_string()))
+        );
+
+        let custom = Rules {
+            scoring: "custom".to_string(),
+            ..Rules::default()
+        };
+        assert_eq!(
+            Script::parse(&format!("answer DYSON\nrules {custom}"))
+                .unwrap()
+                .new_game(),
+            Err(ReplayError::UnsupportedRules(custom))
+        );
+    }
+
+    #[test]
+    fn replay_reproduces_results() {
+        let rules = Rules {
+            min_opener_vowels: Some(2),
+            ..Rules::default()
+        };
+        let script = Script::parse(&format!(
+            "answer DYSON\nrules {rules}\nguess 0 CRWTH\nguess 10 AUDIO\nguess 20 spurg\nguess 30 DYSON"
+        ))
+        .unwrap();
+
+        let (game, results) = Game::replay_script(&script).unwrap();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0], Err(GuessError::TooFewVowels(2)));
+        assert!(results[1].is_ok());
+        assert_eq!(results[2], Err(GuessError::InvalidWord));
+        assert!(results[3]
+            .as_ref()
+            .is_ok_and(|word| word.iter().all(|l| l.position == crate::Position::Correct)));
+        assert_eq!(game.word.reveal_for_display(), "DYSON");
+    }
+
+    #[test]
+    fn guesses_keep_their_whitespace() {
+        let game = Game::with_answer("DYSON").unwrap();
+        let mut recorder = Recorder::new(&game);
+        recorder.record_guess("  crane ");
+        recorder.record_guess("Dyson\t");
+
+        let script = Script::parse(&recorder.script().to_string()).unwrap();
+        assert_eq!(&script, recorder.script());
+        assert_eq!(script.guesses[0].guess, "  crane ");
+
+        // Indented commands still parse
+        let indented = Script::parse(&format!(
+            "  answer DYSON\n  rules {}\n  guess 5  crane",
+            Rules::default()
+        ))
+        .unwrap();
+        assert_eq!(indented.guesses[0].guess, " crane");
+
+        let (game, results) = Game::replay_script(&script).unwrap();
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(game.guess_history.len(), 2);
+    }
+
+    #[test]
+    fn replay_normalizes_like_the_frontend() {
+        let game = Game::with_answer("CREME").unwrap();
+        let normalization = Normalization {
+            strip_apostrophes: false,
+            strip_diacritics: true,
+        };
+        let mut recorder = Recorder::with_normalization(&game, normalization);
+        recorder.record_guess("ｃｒａｎｅ");
+        recorder.record_guess("crème");
+
+        let text = recorder.script().to_string();
+        assert!(text.contains("\nnormalize strip-diacritics\n"));
+        let script = Script::parse(&text).unwrap();
+        assert_eq!(script.normalization, normalization);
+
+        let (game, results) = Game::replay_script(&script).unwrap();
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(game.guess_history.len(), 2);
+
+        // Without the normalization, the diacritic makes the guess invalid
+        let plain = Script {
+            normalization: Normalization::default(),
+            ..script
+        };
+        let (_, results) = Game::replay_script(&plain).unwrap();
+        assert_eq!(results[1], Err(GuessError::IncludesNonAscii));
+
+        assert_eq!(
+            Script::parse(&format!(
+                "answer DYSON\nrules {}\nnormalize strip-vowels",
+                Rules::default()
+            )),
+            Err(ReplayError::InvalidLine(
+                3,
+                "normalize strip-vowels".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,391 @@
+//! This module handles describing the rules that a game was played under, as data.
+//!
+//! Results are only comparable if they were produced under the same rules, so replays and
+//! leaderboards should record the [`Rules`] of each game and compare them before comparing the
+//! results.
+//!
+//! The [`Display`](fmt::Display) implementation and [`Rules::parse`] use a text format of
+//! space-separated `key=value` pairs, like this:
+//!
+//! ```text
+//! word-length=5 max-guesses=6 scoring=classic dictionary=e90ae6991a55f3ab min-opener-vowels=2 banned-openers=CRANE,SOARE
+//! ```
+//!
+//! The opener keys are left out when the game doesn't restrict the opener, and the
+//! `hard-mode=true` and `reject-repeated-guesses=true` keys are left out when those policies are
+//! off.
+//!
+//! The built-in scoring rules are also pinned by [`SCORING_TEST_VECTORS`], so that other
+//! implementations can prove that they score guesses the same way. See [`verify_scoring`].
+
+use super::{letters::Position, scoring::ScoringRules, valid_words, Game, MAX_GUESSES};
+use std::fmt;
+use thiserror::Error;
+
+/// The version of the built-in scoring rules.
+///
+/// This changes whenever [`SCORING_TEST_VECTORS`] changes, which only happens if the result of
+/// scoring a guess with the built-in rules changes.
+pub const SCORING_VERSION: u32 = 1;
+
+/// Answers, guesses, and the patterns that they score with each built-in [`ScoringRules`].
+///
+/// This is the text of a file that can be shipped alongside other implementations. Each line is
+/// the [`name`](ScoringRules::name) of the scoring rules, the answer, the guess, and the pattern,
+/// with one character for each letter: `C` for [`Correct`](Position::Correct), `W` for
+/// [`WrongPosition`](Position::WrongPosition), and `N` for [`NotInWord`](Position::NotInWord).
+/// Blank lines and lines starting with `#` are ignored.
+pub const SCORING_TEST_VECTORS: &str = include_str!("scoring_vectors.txt");
+
+/// A single line of [`SCORING_TEST_VECTORS`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScoringVector {
+    /// The [`name`](ScoringRules::name) of the scoring rules.
+    pub scoring: &'static str,
+
+    /// The answer.
+    pub answer: &'static str,
+
+    /// The guess.
+    pub guess: &'static str,
+
+    /// The position of each letter of the guess.
+    pub pattern: [Position; 5],
+}
+
+impl ScoringVector {
+    /// Parse a line of [`SCORING_TEST_VECTORS`], or return [`None`] if it isn't a vector.
+    fn parse(line: &'static str) -> Option<Self> {
+        let mut parts = line.split_whitespace();
+        let (scoring, answer, guess, pattern) =
+            (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let positions: Vec<Position> = pattern
+            .chars()
+            .map(|c| match c {
+                'C' => Some(Position::Correct),
+                'W' => Some(Position::WrongPosition),
+                'N' => Some(Position::NotInWord),
+                _ => None,
+            })
+            .collect::<Option<_>>()?;
+
+        Some(Self {
+            scoring,
+            answer,
+            guess,
+            pattern: positions.try_into().ok()?,
+        })
+    }
+}
+
+/// Get every vector in [`SCORING_TEST_VECTORS`].
+pub fn scoring_vectors() -> impl Iterator<Item = ScoringVector> {
+    SCORING_TEST_VECTORS
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter_map(ScoringVector::parse)
+}
+
+/// Check a scoring implementation against every vector in [`SCORING_TEST_VECTORS`].
+///
+/// The `score` function is given the name of the scoring rules, the answer, and the guess, and
+/// should return the pattern, or [`None`] if it can't score the guess.
+///
+/// # Errors
+///
+/// If the implementation gives the wrong pattern for any vector, we return the first one that it
+/// got wrong. Otherwise, we return the number of vectors that were checked.
+pub fn verify_scoring<F>(mut score: F) -> Result<usize, ScoringVector>
+where
+    F: FnMut(&str, &str, &str) -> Option<[Position; 5]>,
+{
+    let mut checked = 0;
+
+    for vector in scoring_vectors() {
+        if score(vector.scoring, vector.answer, vector.guess) != Some(vector.pattern) {
+            return Err(vector);
+        }
+        checked += 1;
+    }
+
+    Ok(checked)
+}
+
+/// An enum representing possible errors from parsing [`Rules`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RulesError {
+    /// This part of the text isn't a `key=value` pair with a known key and a valid value.
+    #[error("Invalid rule: {0:?}")]
+    InvalidRule(String),
+
+    /// The text doesn't have a value for this key, which is required.
+    #[error("Missing rule: {0}")]
+    MissingRule(&'static str),
+}
+
+/// The rules that a game is played under.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rules {
+    /// The length of each word, which is always 5 in this version.
+    pub word_length: usize,
+
+    /// The most guesses that a game can take. See [`MAX_GUESSES`].
+    pub max_guesses: usize,
+
+    /// The [`name`](ScoringRules::name) of the scoring rules.
+    pub scoring: String,
+
+    /// The version of the word lists. See [`valid_words::dictionary_version`].
+    pub dictionary_version: u64,
+
+    /// The minimum number of vowels in the opener from the game's [`policy`](Game::policy).
+    pub min_opener_vowels: Option<usize>,
+
+    /// The banned openers from the game's [`policy`](Game::policy), in alphabetical order.
+    pub banned_openers: Vec<String>,
+
+    /// Whether the game's [`policy`](Game::policy) is in
+    /// [`hard_mode`](super::policy::GamePolicy::hard_mode).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hard_mode: bool,
+
+    /// Whether the game's [`policy`](Game::policy)
+    /// [rejects repeated guesses](super::policy::GamePolicy::reject_repeated_guesses).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reject_repeated_guesses: bool,
+}
+
+impl Rules {
+    /// Get the rules of the given game.
+    ///
+    /// Only the rules that affect scoring and validation are included. Hooks and time-based rules
+    /// like [`min_guess_interval`](super::policy::GamePolicy::min_guess_interval) aren't, since
+    /// they don't change the result of a finished game.
+    pub fn for_game(game: &Game) -> Self {
+        let mut banned_openers: Vec<String> = game.policy.banned_openers.iter().cloned().collect();
+        banned_openers.sort();
+
+        Self {
+            scoring: game.scoring_rules.name().to_string(),
+            dictionary_version: game.word_list.dictionary_version(),
+            min_opener_vowels: game.policy.min_opener_vowels,
+            banned_openers,
+            hard_mode: game.policy.hard_mode,
+            reject_repeated_guesses: game.policy.reject_repeated_guesses,
+            ..Self::default()
+        }
+    }
+
+    /// Check whether a game with these rules can be played with this version of the library.
+    ///
+    /// That means the word length, the maximum number of guesses, and the dictionary version are
+    /// the same as the current ones, and the scoring rules are built in.
+    pub fn is_supported(&self) -> bool {
+        let current = Self::default();
+
+        self.word_length == current.word_length
+            && self.max_guesses == current.max_guesses
+            && self.dictionary_version == current.dictionary_version
+            && ScoringRules::from_name(&self.scoring).is_some()
+    }
+
+    /// Parse rules from the text format. See the [module documentation](self).
+    ///
+    /// # Errors
+    ///
+    /// If any part isn't a valid rule, or a required rule is missing, we return the appropriate
+    /// [`RulesError`] variant.
+    pub fn parse(text: &str) -> Result<Self, RulesError> {
+        let mut word_length = None;
+        let mut max_guesses = None;
+        let mut scoring = None;
+        let mut dictionary_version = None;
+        let mut min_opener_vowels = None;
+        let mut banned_openers = Vec::new();
+        let mut hard_mode = false;
+        let mut reject_repeated_guesses = false;
+
+        for part in text.split_whitespace() {
+            let invalid = || RulesError::InvalidRule(part.to_string());
+            let (key, value) = part.split_once('=').ok_or_else(invalid)?;
+
+            match key {
+                "word-length" => word_length = Some(value.parse().map_err(|_| invalid())?),
+                "max-guesses" => max_guesses = Some(value.parse().map_err(|_| invalid())?),
+                "scoring" => scoring = Some(value.to_string()),
+                "dictionary" => {
+                    dictionary_version =
+                        Some(u64::from_str_radix(value, 16).map_err(|_| invalid())?);
+                }
+                "min-opener-vowels" => {
+                    min_opener_vowels = Some(value.parse().map_err(|_| invalid())?);
+                }
+                "banned-openers" => {
+                    banned_openers = value.split(',').map(str::to_string).collect();
+                }
+                "hard-mode" => hard_mode = value.parse().map_err(|_| invalid())?,
+                "reject-repeated-guesses" => {
+                    reject_repeated_guesses = value.parse().map_err(|_| invalid())?;
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(Self {
+            word_length: word_length.ok_or(RulesError::MissingRule("word-length"))?,
+            max_guesses: max_guesses.ok_or(RulesError::MissingRule("max-guesses"))?,
+            scoring: scoring.ok_or(RulesError::MissingRule("scoring"))?,
+            dictionary_version: dictionary_version.ok_or(RulesError::MissingRule("dictionary"))?,
+            min_opener_vowels,
+            banned_openers,
+            hard_mode,
+            reject_repeated_guesses,
+        })
+    }
+}
+
+/// The default rules are the rules of a new [`Game`] in this version of the library.
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            word_length: 5,
+            max_guesses: MAX_GUESSES,
+            scoring: ScoringRules::default().name().to_string(),
+            dictionary_version: valid_words::dictionary_version(),
+            min_opener_vowels: None,
+            banned_openers: Vec::new(),
+            hard_mode: false,
+            reject_repeated_guesses: false,
+        }
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "word-length={} max-guesses={} scoring={} dictionary={:016x}",
+            self.word_length, self.max_guesses, self.scoring, self.dictionary_version
+        )?;
+        if let Some(vowels) = self.min_opener_vowels {
+            write!(f, " min-opener-vowels={vowels}")?;
+        }
+        if !self.banned_openers.is_empty() {
+            write!(f, " banned-openers={}", self.banned_openers.join(","))?;
+        }
+        if self.hard_mode {
+            f.write_str(" hard-mode=true")?;
+        }
+        if self.reject_repeated_guesses {
+            f.write_str(" reject-repeated-guesses=true")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_round_trip() {
+        let mut game = Game {
+            scoring_rules: ScoringRules::LenientDuplicates,
+            ..Game::new()
+        };
+        game.policy.banned_openers = ["SOARE", "CRANE"].map(String::from).into();
+        game.policy.min_opener_vowels = Some(2);
+        game.policy.hard_mode = true;
+
+        let rules = Rules::for_game(&game);
+        assert_eq!(rules.banned_openers, ["CRANE", "SOARE"]);
+        assert!(rules.is_supported());
+
+        let text = rules.to_string();
+        assert!(text.starts_with("word-length=5 max-guesses=6 scoring=lenient dictionary="));
+        assert!(text.ends_with(" min-opener-vowels=2 banned-openers=CRANE,SOARE hard-mode=true"));
+        assert_eq!(Rules::parse(&text), Ok(rules));
+
+        let default = Rules::for_game(&Game::new());
+        assert_eq!(default, Rules::default());
+        assert_eq!(Rules::parse(&default.to_string()), Ok(default));
+    }
+
+    #[test]
+    fn different_rules() {
+        let rules = Rules::default();
+
+        let old_dictionary = Rules {
+            dictionary_version: rules.dictionary_version ^ 1,
+            ..rules.clone()
+        };
+        assert_ne!(old_dictionary, rules);
+        assert!(!old_dictionary.is_supported());
+
+        let custom = Rules {
+            scoring: "custom".to_string(),
+            ..rules.clone()
+        };
+        assert!(!custom.is_supported());
+    }
+
+    #[test]
+    fn scoring_vectors_match_library() {
+        let header = format!("# Scoring test vectors, version {SCORING_VERSION}\n");
+        assert!(SCORING_TEST_VECTORS.starts_with(&header));
+
+        // Every line that isn't a comment must be a valid vector
+        let lines = SCORING_TEST_VECTORS
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+            .count();
+        assert_eq!(scoring_vectors().count(), lines);
+        assert!(lines >= 400);
+
+        for name in ["classic", "lenient"] {
+            assert!(scoring_vectors().any(|vector| vector.scoring == name));
+        }
+
+        let library = |scoring: &str, answer: &str, guess: &str| {
+            let rules = ScoringRules::from_name(scoring)?;
+            let word = crate::scoring::score_guess_with(answer, guess, &rules).ok()?;
+            Some(word.map(|letter| letter.position))
+        };
+        assert_eq!(verify_scoring(library), Ok(lines));
+
+        let broken = |_: &str, _: &str, _: &str| Some([Position::NotInWord; 5]);
+        assert_eq!(
+            verify_scoring(broken),
+            Err(scoring_vectors().next().unwrap())
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Rules::parse("word-length=5 max-guesses=6 scoring=classic"),
+            Err(RulesError::MissingRule("dictionary"))
+        );
+        assert_eq!(
+            Rules::parse("word-length=five"),
+            Err(RulesError::InvalidRule("word-length=five".to_string()))
+        );
+        assert_eq!(
+            Rules::parse("colour=true"),
+            Err(RulesError::InvalidRule("colour=true".to_string()))
+        );
+        assert_eq!(
+            Rules::parse("hard-mode=yes"),
+            Err(RulesError::InvalidRule("hard-mode=yes".to_string()))
+        );
+        assert_eq!(
+            Rules::parse("dictionary"),
+            Err(RulesError::InvalidRule("dictionary".to_string()))
+        );
+    }
+}
@@ -0,0 +1,654 @@
+//! This module handles scoring a guess against a target word.
+//!
+//! [`Game::make_guess`](super::Game::make_guess) uses [`score_guess_with`] internally, but it's also
+//! available here for tools that want to score words without a [`Game`](super::Game).
+//!
+//! Some Wordle variants score duplicate letters differently, so the rules to use are given by
+//! [`ScoringRules`].
+
+use super::{
+    letters::{Letter, Position},
+    messages::{English, Messages},
+    GuessError, Word,
+};
+use std::{fmt, sync::Arc};
+
+/// A custom way of scoring guesses, for variants that [`ScoringRules`] doesn't cover.
+pub trait ScoringRule {
+    /// Score the guess against the answer.
+    ///
+    /// Both words are guaranteed to be exactly 5 uppercase ASCII characters.
+    fn score(&self, answer: &str, guess: &str) -> Word;
+}
+
+/// The rules to use when scoring a guess.
+#[derive(Clone, Default)]
+pub enum ScoringRules {
+    /// The rules of the original Wordle, as described in
+    /// [`Game::make_guess`](super::Game::make_guess).
+    ///
+    /// A letter is only highlighted as many times as it appears in the answer.
+    #[default]
+    Classic,
+
+    /// Every letter that appears in the answer is highlighted, no matter how many times it appears
+    /// in the guess.
+    ///
+    /// A letter in the correct position is still [`Correct`](Position::Correct), and every other
+    /// instance of a letter in the answer is [`WrongPosition`](Position::WrongPosition).
+    LenientDuplicates,
+
+    /// Custom rules.
+    Custom(Arc<dyn ScoringRule + Send + Sync>),
+}
+
+impl fmt::Debug for ScoringRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Classic => write!(f, "Classic"),
+            Self::LenientDuplicates => write!(f, "LenientDuplicates"),
+            Self::Custom(_) => write!(f, "Custom(<rule>)"),
+        }
+    }
+}
+
+impl ScoringRules {
+    /// Get the name of these rules, which is `classic`, `lenient`, or `custom`.
+    ///
+    /// Every custom rule has the same name, since there's no way to tell them apart.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Classic => "classic",
+            Self::LenientDuplicates => "lenient",
+            Self::Custom(_) => "custom",
+        }
+    }
+
+    /// Get the rules with the given [`name`](ScoringRules::name), if they're built in.
+    ///
+    /// Custom rules can't be created from their name, so `custom` gives [`None`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "classic" => Some(Self::Classic),
+            "lenient" => Some(Self::LenientDuplicates),
+            _ => None,
+        }
+    }
+}
+
+/// Custom rules are only equal if they share the same rule.
+impl PartialEq for ScoringRules {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Classic, Self::Classic) => true,
+            (Self::LenientDuplicates, Self::LenientDuplicates) => true,
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// Rules are serialized as their [`name`](ScoringRules::name). Custom rules can't be serialized,
+/// since they couldn't be deserialized again.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ScoringRules {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Custom(_) => Err(serde::ser::Error::custom(
+                "custom scoring rules can't be serialized",
+            )),
+            _ => serializer.serialize_str(self.name()),
+        }
+    }
+}
+
+/// Rules are deserialized from their [`name`](ScoringRules::name). See
+/// [`from_name`](ScoringRules::from_name).
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ScoringRules {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Self::from_name(&name)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown scoring rules: {name:?}")))
+    }
+}
+
+/// Check that the given word is 5 ASCII characters, and return it in uppercase.
+fn check_word(word: &str) -> Result<String, GuessError> {
+    if !word.is_ascii() {
+        Err(GuessError::IncludesNonAscii)
+    } else if word.len() != 5 {
+        Err(GuessError::WrongWordLength)
+    } else {
+        Ok(word.to_ascii_uppercase())
+    }
+}
+
+/// Score the guess against the target word with the [`Classic`](ScoringRules::Classic) rules.
+///
+/// This returns an array of five [`Letter`]s, with positions calculated as described in
+/// [`Game::make_guess`](super::Game::make_guess). Both words are converted to uppercase.
+///
+/// Unlike [`Game::make_guess`](super::Game::make_guess), this doesn't check that either word is
+/// in the word list.
+///
+/// # Errors
+///
+/// If either word isn't exactly 5 ASCII characters, we return the appropriate [`GuessError`]
+/// variant.
+pub fn score_guess(answer: &str, guess: &str) -> Result<Word, GuessError> {
+    score_guess_with(answer, guess, &ScoringRules::Classic)
+}
+
+/// Score the guess against the target word with the given rules.
+///
+/// See [`score_guess`].
+///
+/// # Errors
+///
+/// See [`score_guess`].
+pub fn score_guess_with(
+    answer: &str,
+    guess: &str,
+    rules: &ScoringRules,
+) -> Result<Word, GuessError> {
+    let answer = check_word(answer)?;
+    let guess = check_word(guess)?;
+
+    Ok(match rules {
+        ScoringRules::Classic => score_classic(&answer, &guess)?,
+        ScoringRules::LenientDuplicates => score_lenient(&answer, &guess),
+        ScoringRules::Custom(rule) => rule.score(&answer, &guess),
+    })
+}
+
+/// Score the guess with the [`LenientDuplicates`](ScoringRules::LenientDuplicates) rules.
+///
+/// Both words must already be checked and uppercase.
+fn score_lenient(answer: &str, guess: &str) -> Word {
+    let mut word = [Letter::new('A', Position::NotInWord); 5];
+    for (letter, (c, expected)) in word.iter_mut().zip(guess.chars().zip(answer.chars())) {
+        *letter = Letter::simple_check_letter_pair(&c, &expected, answer)
+            .unwrap_or_else(|| Letter::new(c, Position::WrongPosition));
+    }
+    word
+}
+
+/// Check the given word like [`check_word`], and return it as uppercase bytes.
+fn check_word_bytes(word: &str) -> Result<[u8; 5], GuessError> {
+    check_word(word)?
+        .as_bytes()
+        .try_into()
+        .map_err(|_| GuessError::WrongWordLength)
+}
+
+/// Score the guess with the [`Classic`](ScoringRules::Classic) rules.
+///
+/// Both words must already be checked and uppercase.
+fn score_classic(answer: &str, guess: &str) -> Result<Word, GuessError> {
+    Ok(score_classic_bytes(
+        &check_word_bytes(answer)?,
+        &check_word_bytes(guess)?,
+        &mut [0; 128],
+    ))
+}
+
+/// Score the guess with the [`Classic`](ScoringRules::Classic) rules, without allocating.
+///
+/// Both words must already be checked and uppercase. The `remaining` buffer is indexed by ASCII
+/// code, so that words with characters other than letters are scored without panicking. It's
+/// overwritten, and only exists so that callers scoring lots of words can reuse it.
+fn score_classic_bytes(answer: &[u8; 5], guess: &[u8; 5], remaining: &mut [u8; 128]) -> Word {
+    let mut word = [Letter::new('A', Position::NotInWord); 5];
+
+    // Count the letters in the answer that aren't matched by a correct letter in the guess
+    remaining.fill(0);
+    for (&a, &g) in answer.iter().zip(guess) {
+        if a != g {
+            remaining[usize::from(a & 0x7f)] += 1;
+        }
+    }
+
+    for ((letter, &a), &g) in word.iter_mut().zip(answer).zip(guess) {
+        let count = &mut remaining[usize::from(g & 0x7f)];
+
+        let position = if a == g {
+            Position::Correct
+        } else if *count > 0 {
+            *count -= 1;
+            Position::WrongPosition
+        } else {
+            Position::NotInWord
+        };
+
+        *letter = Letter::new(g as char, position);
+    }
+
+    word
+}
+
+/// Score every guess against the same answer with the [`Classic`](ScoringRules::Classic) rules.
+///
+/// This gives the same results as calling [`score_guess`] for each guess, but it only checks the
+/// answer once and doesn't allocate for each guess. Analytics tools should use this rather than
+/// calling [`Game::make_guess`](super::Game::make_guess) in a loop.
+///
+/// # Errors
+///
+/// If any word isn't exactly 5 ASCII characters, we return the appropriate [`GuessError`]
+/// variant.
+pub fn score_many(answer: &str, guesses: &[&str]) -> Result<Vec<Word>, GuessError> {
+    let answer = check_word_bytes(answer)?;
+    let mut remaining = [0; 128];
+
+    guesses
+        .iter()
+        .map(|guess| {
+            let guess = check_word_bytes(guess)?;
+            Ok(score_classic_bytes(&answer, &guess, &mut remaining))
+        })
+        .collect()
+}
+
+/// The colours of every guess scored against every answer, as made by [`score_matrix`].
+///
+/// Each pattern is stored as a single byte, so this is compact enough to hold every answer against
+/// every valid guess.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternMatrix {
+    /// The number of guesses, which is the length of each row.
+    guesses: usize,
+
+    /// The encoded patterns, with one row per answer.
+    patterns: Vec<u8>,
+}
+
+impl PatternMatrix {
+    /// Encode the positions of the given word as a number below 243.
+    fn encode(word: &Word) -> u8 {
+        word.iter().rev().fold(0, |code, letter| {
+            code * 3
+                + match letter.position {
+                    Position::NotInWord => 0,
+                    Position::WrongPosition => 1,
+                    Position::Correct => 2,
+                }
+        })
+    }
+
+    /// Decode a number made by [`encode`](Self::encode) back into positions.
+    fn decode(mut code: u8) -> [Position; 5] {
+        let mut positions = [Position::NotInWord; 5];
+        for position in positions.iter_mut() {
+            *position = match code % 3 {
+                0 => Position::NotInWord,
+                1 => Position::WrongPosition,
+                _ => Position::Correct,
+            };
+            code /= 3;
+        }
+        positions
+    }
+
+    /// The number of answers, which is the number of rows.
+    pub fn answers(&self) -> usize {
+        self.patterns.len().checked_div(self.guesses).unwrap_or(0)
+    }
+
+    /// The number of guesses, which is the number of columns.
+    pub fn guesses(&self) -> usize {
+        self.guesses
+    }
+
+    /// Get the positions of the given guess scored against the given answer, using their indices
+    /// in the slices given to [`score_matrix`].
+    ///
+    /// If either index is out of bounds, this is [`None`].
+    pub fn pattern(&self, answer: usize, guess: usize) -> Option<[Position; 5]> {
+        self.pattern_code(answer, guess).map(Self::decode)
+    }
+
+    /// Get the pattern like [`pattern`](Self::pattern), but encoded as a number below 243.
+    ///
+    /// Two patterns are the same exactly when their codes are the same, so these are useful for
+    /// grouping answers by the pattern that a guess would give.
+    ///
+    /// If either index is out of bounds, this is [`None`].
+    pub fn pattern_code(&self, answer: usize, guess: usize) -> Option<u8> {
+        if guess >= self.guesses {
+            return None;
+        }
+
+        let index = answer.checked_mul(self.guesses)?.checked_add(guess)?;
+        self.patterns.get(index).copied()
+    }
+}
+
+/// Score every guess against every answer with the [`Classic`](ScoringRules::Classic) rules.
+///
+/// Like [`score_many`], this is the fast path for analytics tools. The whole matrix is stored in
+/// a single allocation.
+///
+/// # Errors
+///
+/// If any word isn't exactly 5 ASCII characters, we return the appropriate [`GuessError`]
+/// variant.
+pub fn score_matrix(answers: &[&str], guesses: &[&str]) -> Result<PatternMatrix, GuessError> {
+    let guess_bytes = guesses
+        .iter()
+        .map(|guess| check_word_bytes(guess))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut patterns = Vec::with_capacity(answers.len() * guesses.len());
+    let mut remaining = [0; 128];
+
+    for answer in answers {
+        let answer = check_word_bytes(answer)?;
+        patterns.extend(guess_bytes.iter().map(|guess| {
+            PatternMatrix::encode(&score_classic_bytes(&answer, guess, &mut remaining))
+        }));
+    }
+
+    Ok(PatternMatrix {
+        guesses: guesses.len(),
+        patterns,
+    })
+}
+
+/// The reason that a single tile in a scored guess got its colour.
+///
+/// The [`Display`](fmt::Display) implementation explains the reason in an English sentence. Use
+/// [`explain_with`](Explanation::explain_with) for other languages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Explanation {
+    /// The letter is in the answer in this position.
+    Correct {
+        /// The letter in the guess.
+        letter: char,
+    },
+
+    /// The letter is in the answer, but not in this position.
+    Misplaced {
+        /// The letter in the guess.
+        letter: char,
+
+        /// The number of times that this letter appears in the answer.
+        in_answer: usize,
+    },
+
+    /// The letter doesn't appear in the answer at all.
+    Absent {
+        /// The letter in the guess.
+        letter: char,
+    },
+
+    /// The letter is in the answer, but every instance of it is already accounted for by other
+    /// tiles, so this one is [`NotInWord`](Position::NotInWord).
+    ///
+    /// This is the case that people tend to dispute, so it includes all the counts involved.
+    Exhausted {
+        /// The letter in the guess.
+        letter: char,
+
+        /// The number of times that this letter appears in the answer.
+        in_answer: usize,
+
+        /// The number of tiles with this letter in the correct position anywhere in the guess.
+        correct: usize,
+
+        /// The number of tiles with this letter in the wrong position earlier in the guess.
+        earlier_misplaced: usize,
+    },
+}
+
+impl Explanation {
+    /// Explain the reason in a sentence from the given messages. See [`Messages::explain`].
+    pub fn explain_with(&self, messages: &impl Messages) -> String {
+        messages.explain(self)
+    }
+}
+
+/// This explains the reason with the [`English`] messages.
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.explain_with(&English))
+    }
+}
+
+/// Score the guess against the target word like [`score_guess`], and also explain why each tile
+/// got its colour.
+///
+/// This always uses the [`Classic`](ScoringRules::Classic) rules, since those are the ones that
+/// need explaining.
+///
+/// The explanations are in the same order as the letters in the guess.
+///
+/// # Errors
+///
+/// See [`score_guess`].
+pub fn score_guess_explained(
+    answer: &str,
+    guess: &str,
+) -> Result<(Word, Vec<Explanation>), GuessError> {
+    let word = score_guess(answer, guess)?;
+    let answer = check_word(answer)?;
+
+    let explanations = word
+        .iter()
+        .enumerate()
+        .map(|(index, letter)| {
+            let in_answer = answer.chars().filter(|&c| c == letter.letter).count();
+
+            match letter.position {
+                Position::Correct => Explanation::Correct {
+                    letter: letter.letter,
+                },
+                Position::WrongPosition => Explanation::Misplaced {
+                    letter: letter.letter,
+                    in_answer,
+                },
+                Position::NotInWord if in_answer == 0 => Explanation::Absent {
+                    letter: letter.letter,
+                },
+                Position::NotInWord => Explanation::Exhausted {
+                    letter: letter.letter,
+                    in_answer,
+                    correct: word
+                        .iter()
+                        .filter(|l| l.letter == letter.letter && l.position == Position::Correct)
+                        .count(),
+                    earlier_misplaced: word[..index]
+                        .iter()
+                        .filter(|l| {
+                            l.letter == letter.letter && l.position == Position::WrongPosition
+                        })
+                        .count(),
+                },
+            }
+        })
+        .collect();
+
+    Ok((word, explanations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_guess_checks_lengths() {
+        assert_eq!(score_guess("DYSON", "HI"), Err(GuessError::WrongWordLength));
+        assert_eq!(
+            score_guess("ABC", "DYSON"),
+            Err(GuessError::WrongWordLength)
+        );
+        assert_eq!(
+            score_guess("DYSON", "Złoty"),
+            Err(GuessError::IncludesNonAscii)
+        );
+    }
+
+    #[test]
+    fn lenient_duplicates() {
+        let rules = ScoringRules::LenientDuplicates;
+
+        assert_eq!(
+            score_guess_with("BLEEP", "EERIE", &rules),
+            Ok([
+                Letter::new('E', Position::WrongPosition),
+                Letter::new('E', Position::WrongPosition),
+                Letter::new('R', Position::NotInWord),
+                Letter::new('I', Position::NotInWord),
+                Letter::new('E', Position::WrongPosition),
+            ])
+        );
+        assert_eq!(
+            score_guess_with("DYSON", "DADDY", &rules),
+            Ok([
+                Letter::new('D', Position::Correct),
+                Letter::new('A', Position::NotInWord),
+                Letter::new('D', Position::WrongPosition),
+                Letter::new('D', Position::WrongPosition),
+                Letter::new('Y', Position::WrongPosition),
+            ])
+        );
+    }
+
+    #[test]
+    fn custom_rules() {
+        /// A silly rule where every letter is correct.
+        struct AllCorrect;
+
+        impl ScoringRule for AllCorrect {
+            fn score(&self, _answer: &str, guess: &str) -> Word {
+                let mut chars = guess.chars();
+                [(); 5].map(|_| Letter::new(chars.next().unwrap(), Position::Correct))
+            }
+        }
+
+        let rules = ScoringRules::Custom(Arc::new(AllCorrect));
+        assert_eq!(
+            score_guess_with("DYSON", "wordy", &rules).map(|word| word.map(|l| l.position)),
+            Ok([Position::Correct; 5])
+        );
+        assert_eq!(rules, rules.clone());
+        assert_ne!(rules, ScoringRules::Custom(Arc::new(AllCorrect)));
+
+        assert_eq!(rules.name(), "custom");
+        assert_eq!(ScoringRules::from_name("custom"), None);
+        for rules in [ScoringRules::Classic, ScoringRules::LenientDuplicates] {
+            assert_eq!(ScoringRules::from_name(rules.name()), Some(rules));
+        }
+    }
+
+    #[test]
+    fn explanations_for_duplicates() {
+        let (word, explanations) =
+            score_guess_explained("BLEEP", "EERIE").expect("EERIE should score against BLEEP");
+
+        assert_eq!(word.map(|l| l.position)[4], Position::NotInWord);
+        assert_eq!(
+            explanations,
+            vec![
+                Explanation::Misplaced {
+                    letter: 'E',
+                    in_answer: 2
+                },
+                Explanation::Misplaced {
+                    letter: 'E',
+                    in_answer: 2
+                },
+                Explanation::Absent { letter: 'R' },
+                Explanation::Absent { letter: 'I' },
+                Explanation::Exhausted {
+                    letter: 'E',
+                    in_answer: 2,
+                    correct: 0,
+                    earlier_misplaced: 2
+                },
+            ]
+        );
+        assert_eq!(
+            explanations[4].to_string(),
+            "This 'E' is not highlighted because the answer only has two 'E's, already accounted for by the earlier misplaced tiles"
+        );
+
+        let (_, explanations) =
+            score_guess_explained("DYSON", "DADDY").expect("DADDY should score against DYSON");
+
+        assert_eq!(explanations[0], Explanation::Correct { letter: 'D' });
+        assert_eq!(
+            explanations[2],
+            Explanation::Exhausted {
+                letter: 'D',
+                in_answer: 1,
+                correct: 1,
+                earlier_misplaced: 0
+            }
+        );
+        assert_eq!(
+            explanations[2].to_string(),
+            "This 'D' is not highlighted because the answer only has one 'D', already accounted for by the correct tiles"
+        );
+
+        let table = crate::messages::MessageTable::parse(
+            "explain-exhausted-correct = Ce {letter} n'est pas en couleur : la réponse n'a que {count} {letter}",
+        );
+        assert_eq!(
+            explanations[2].explain_with(&table),
+            "Ce D n'est pas en couleur : la réponse n'a que 1 D"
+        );
+        assert_eq!(
+            explanations[0].explain_with(&table),
+            explanations[0].to_string()
+        );
+    }
+
+    #[test]
+    fn score_many_matches_score_guess() {
+        let answers = ["DYSON", "SPEED", "ABBEY", "EERIE"];
+        let guesses = [
+            "EERIE", "DYSON", "SPEED", "ABBEY", "KEBAB", "ERROR", "geese",
+        ];
+
+        for answer in answers {
+            let expected: Vec<Word> = guesses
+                .iter()
+                .map(|guess| score_guess(answer, guess).unwrap())
+                .collect();
+            assert_eq!(score_many(answer, &guesses), Ok(expected));
+        }
+
+        assert_eq!(
+            score_many("DYSON", &["HELLO", "HI"]),
+            Err(GuessError::WrongWordLength)
+        );
+    }
+
+    #[test]
+    fn score_matrix_matches_score_guess() {
+        let answers = ["DYSON", "SPEED", "ABBEY", "EERIE"];
+        let guesses = ["EERIE", "DYSON", "SPEED", "KEBAB", "ERROR"];
+
+        let matrix = score_matrix(&answers, &guesses).unwrap();
+        assert_eq!(matrix.answers(), 4);
+        assert_eq!(matrix.guesses(), 5);
+
+        for (a, answer) in answers.iter().enumerate() {
+            for (g, guess) in guesses.iter().enumerate() {
+                let expected = score_guess(answer, guess).unwrap().map(|l| l.position);
+                assert_eq!(matrix.pattern(a, g), Some(expected));
+            }
+        }
+
+        assert_eq!(matrix.pattern_code(3, 1), Some(0));
+        assert_eq!(matrix.pattern_code(0, 1), Some(242));
+        assert_eq!(matrix.pattern_code(0, 5), None);
+        assert_eq!(matrix.pattern_code(usize::MAX, 1), None);
+        assert_eq!(score_matrix(&[], &guesses).unwrap().answers(), 0);
+        assert_eq!(
+            score_matrix(&["DYSON", "Złoty"], &guesses),
+            Err(GuessError::IncludesNonAscii)
+        );
+    }
+}
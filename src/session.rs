@@ -0,0 +1,375 @@
+//! This module handles logging long sessions of many boards, like endless play, in bounded memory.
+//!
+//! A [`SessionLog`] keeps every guess of the current board, but once a board is finished, its
+//! guesses are dropped and it's summarised as a [`BoardSummary`] and added to the running
+//! [`SessionStats`]. Only the most recent summaries are kept, so a bot or server can run a session
+//! for as long as it likes without the log growing.
+//!
+//! [`SessionStats`] can also be used on its own, for the classic stats of every game a player has
+//! finished. Record each game with [`SessionStats::record`] and
+//! [`BoardSummary::from_history`], and save the stats between games with the `serde` feature or
+//! the text format of [`SessionStats::parse`].
+
+use super::{is_won, Word, MAX_GUESSES};
+use std::{collections::VecDeque, fmt};
+
+/// The outcome of a finished board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoardSummary {
+    /// Whether the player guessed the answer.
+    pub won: bool,
+
+    /// The number of valid guesses that the player made.
+    pub guesses: usize,
+}
+
+impl BoardSummary {
+    /// Summarise a finished board with the given guesses, in order, like
+    /// [`Game::guess_history`](super::Game::guess_history).
+    ///
+    /// The board counts as won if its last guess is all
+    /// [`Correct`](super::letters::Position::Correct) and it took at most [`MAX_GUESSES`] guesses,
+    /// like in [`Game::share_string_with`](super::Game::share_string_with).
+    pub fn from_history(guesses: &[Word]) -> Self {
+        Self {
+            won: is_won(guesses),
+            guesses: guesses.len(),
+        }
+    }
+}
+
+/// The running totals of every board finished in a session.
+///
+/// All the counts saturate rather than overflowing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionStats {
+    /// The number of boards finished.
+    pub boards: u64,
+
+    /// The number of boards won.
+    pub wins: u64,
+
+    /// The total number of guesses made on every finished board.
+    pub total_guesses: u64,
+
+    /// The number of boards won in a row, up to and including the last one.
+    pub current_streak: u64,
+
+    /// The longest run of boards won in a row.
+    pub best_streak: u64,
+
+    /// The number of boards won with each number of guesses, where index 0 is a win in 1 guess.
+    pub distribution: [u64; MAX_GUESSES],
+}
+
+impl SessionStats {
+    /// Add a finished board to the totals.
+    ///
+    /// A summary that claims a win in 0 guesses or more than [`MAX_GUESSES`] is counted as a loss,
+    /// so that the wins always add up to the [`distribution`](SessionStats::distribution).
+    pub fn record(&mut self, summary: &BoardSummary) {
+        self.boards = self.boards.saturating_add(1);
+        self.total_guesses = self
+            .total_guesses
+            .saturating_add(u64::try_from(summary.guesses).unwrap_or(u64::MAX));
+
+        if summary.won && (1..=MAX_GUESSES).contains(&summary.guesses) {
+            self.wins = self.wins.saturating_add(1);
+            self.current_streak = self.current_streak.saturating_add(1);
+            self.best_streak = self.best_streak.max(self.current_streak);
+
+            if let Some(count) = summary
+                .guesses
+                .checked_sub(1)
+                .and_then(|index| self.distribution.get_mut(index))
+            {
+                *count = count.saturating_add(1);
+            }
+        } else {
+            self.current_streak = 0;
+        }
+    }
+
+    /// Get the fraction of finished boards that were won, or [`None`] if no boards are finished.
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.boards > 0).then(|| self.wins as f64 / self.boards as f64)
+    }
+
+    /// Parse stats from the text format of the [`Display`](fmt::Display) implementation, which is
+    /// space-separated `key=value` pairs, like this:
+    ///
+    /// ```text
+    /// boards=4 wins=3 total-guesses=10 current-streak=1 best-streak=2 distribution=2,1,0,0,0,0
+    /// ```
+    ///
+    /// Unknown keys and invalid values are ignored, and missing counts are 0, so that frontends
+    /// can always load whatever stats they saved.
+    pub fn parse(text: &str) -> Self {
+        let mut stats = Self::default();
+
+        for (key, value) in text
+            .split_whitespace()
+            .filter_map(|part| part.split_once('='))
+        {
+            let count = match key {
+                "boards" => &mut stats.boards,
+                "wins" => &mut stats.wins,
+                "total-guesses" => &mut stats.total_guesses,
+                "current-streak" => &mut stats.current_streak,
+                "best-streak" => &mut stats.best_streak,
+                "distribution" => {
+                    for (count, value) in stats.distribution.iter_mut().zip(value.split(',')) {
+                        *count = value.parse().unwrap_or(*count);
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            *count = value.parse().unwrap_or(*count);
+        }
+
+        stats
+    }
+}
+
+impl fmt::Display for SessionStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let distribution: Vec<String> = self.distribution.iter().map(u64::to_string).collect();
+
+        write!(
+            f,
+            "boards={} wins={} total-guesses={} current-streak={} best-streak={} distribution={}",
+            self.boards,
+            self.wins,
+            self.total_guesses,
+            self.current_streak,
+            self.best_streak,
+            distribution.join(",")
+        )
+    }
+}
+
+/// A log of a session of many boards, which keeps full detail only for the current board.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionLog {
+    /// Every valid guess made on the current board.
+    current: Vec<Word>,
+
+    /// The summaries of the most recently finished boards, oldest first.
+    recent: VecDeque<BoardSummary>,
+
+    /// The most summaries to keep in [`recent`](SessionLog::recent).
+    max_recent: usize,
+
+    /// The totals of every finished board, including ones that are no longer in
+    /// [`recent`](SessionLog::recent).
+    stats: SessionStats,
+}
+
+impl Default for SessionLog {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_RECENT)
+    }
+}
+
+impl SessionLog {
+    /// The number of recent summaries kept by [`SessionLog::default`].
+    pub const DEFAULT_MAX_RECENT: usize = 100;
+
+    /// Create an empty log that keeps the summaries of at most `max_recent` finished boards.
+    pub fn new(max_recent: usize) -> Self {
+        Self {
+            current: Vec::new(),
+            recent: VecDeque::new(),
+            max_recent,
+            stats: SessionStats::default(),
+        }
+    }
+
+    /// Record a valid guess on the current board.
+    pub fn record_guess(&mut self, word: Word) {
+        self.current.push(word);
+    }
+
+    /// Get every guess made on the current board so far.
+    pub fn current_board(&self) -> &[Word] {
+        &self.current
+    }
+
+    /// Finish the current board, summarise it, and start a new one.
+    ///
+    /// We return the summary of the finished board. See [`BoardSummary::from_history`].
+    pub fn finish_board(&mut self) -> BoardSummary {
+        let summary = BoardSummary::from_history(&self.current);
+
+        self.current.clear();
+        self.stats.record(&summary);
+
+        self.recent.push_back(summary);
+        while self.recent.len() > self.max_recent {
+            self.recent.pop_front();
+        }
+
+        summary
+    }
+
+    /// Get the summaries of the most recently finished boards, oldest first.
+    pub fn recent(&self) -> impl Iterator<Item = &BoardSummary> {
+        self.recent.iter()
+    }
+
+    /// Get the totals of every board finished in this session.
+    pub fn stats(&self) -> &SessionStats {
+        &self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Game;
+
+    /// Play the given guesses on a new board with the given answer, and finish it.
+    fn play(log: &mut SessionLog, answer: &str, guesses: &[&str]) -> BoardSummary {
+        let mut game = Game {
+            word: answer.to_string().into(),
+            ..Game::new()
+        };
+        for guess in guesses {
+            log.record_guess(game.make_guess(guess).unwrap());
+        }
+        log.finish_board()
+    }
+
+    #[test]
+    fn session_stats() {
+        let mut log = SessionLog::default();
+        assert_eq!(log.stats().win_rate(), None);
+
+        assert_eq!(
+            play(&mut log, "DYSON", &["CRANE", "DYSON"]),
+            BoardSummary {
+                won: true,
+                guesses: 2
+            }
+        );
+        play(&mut log, "DYSON", &["DYSON"]);
+        play(&mut log, "DYSON", &["CRANE"; 6]);
+        play(&mut log, "DYSON", &["DYSON"]);
+
+        let stats = log.stats();
+        assert_eq!(stats.boards, 4);
+        assert_eq!(stats.wins, 3);
+        assert_eq!(stats.total_guesses, 10);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.best_streak, 2);
+        assert_eq!(stats.distribution, [2, 1, 0, 0, 0, 0]);
+        assert_eq!(stats.win_rate(), Some(0.75));
+    }
+
+    #[test]
+    fn stats_without_a_log() {
+        let mut game = Game {
+            word: "DYSON".to_string().into(),
+            ..Game::new()
+        };
+        let mut stats = SessionStats::default();
+        stats.record(&BoardSummary::from_history(&game.guess_history));
+        assert_eq!(stats.current_streak, 0);
+
+        game.make_guess("CRANE").unwrap();
+        game.make_guess("DYSON").unwrap();
+        let summary = BoardSummary::from_history(&game.guess_history);
+        assert_eq!(
+            summary,
+            BoardSummary {
+                won: true,
+                guesses: 2
+            }
+        );
+
+        stats.record(&summary);
+        stats.record(&summary);
+        assert_eq!(stats.boards, 3);
+        assert_eq!(stats.current_streak, 2);
+        assert_eq!(stats.distribution, [0, 2, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn late_wins_are_losses() {
+        let mut game = Game::with_answer("DYSON").unwrap();
+        for _ in 0..MAX_GUESSES {
+            game.make_guess("CRANE").unwrap();
+        }
+        game.make_guess("DYSON").unwrap();
+
+        let summary = BoardSummary::from_history(&game.guess_history);
+        assert_eq!(
+            summary,
+            BoardSummary {
+                won: false,
+                guesses: 7
+            }
+        );
+
+        let mut stats = SessionStats::default();
+        stats.record(&summary);
+        stats.record(&BoardSummary {
+            won: true,
+            guesses: 7,
+        });
+        stats.record(&BoardSummary {
+            won: true,
+            guesses: 0,
+        });
+        assert_eq!(stats.boards, 3);
+        assert_eq!(stats.wins, 0);
+        assert_eq!(stats.current_streak, 0);
+        assert_eq!(stats.distribution.iter().sum::<u64>(), stats.wins);
+    }
+
+    #[test]
+    fn stats_text_round_trip() {
+        let mut log = SessionLog::default();
+        play(&mut log, "DYSON", &["CRANE", "DYSON"]);
+        play(&mut log, "DYSON", &["CRANE"; 6]);
+        play(&mut log, "DYSON", &["DYSON"]);
+
+        let stats = *log.stats();
+        let text = stats.to_string();
+        assert_eq!(
+            text,
+            "boards=3 wins=2 total-guesses=9 current-streak=1 best-streak=1 distribution=1,1,0,0,0,0"
+        );
+        assert_eq!(SessionStats::parse(&text), stats);
+
+        assert_eq!(SessionStats::parse(""), SessionStats::default());
+        let partial = SessionStats::parse("wins=x boards=2 colour=true distribution=0,3");
+        assert_eq!(partial.boards, 2);
+        assert_eq!(partial.wins, 0);
+        assert_eq!(partial.distribution, [0, 3, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn memory_is_bounded() {
+        let mut log = SessionLog::new(3);
+
+        for _ in 0..10 {
+            log.record_guess(Game::new().make_guess("CRANE").unwrap());
+            assert_eq!(log.current_board().len(), 1);
+            log.finish_board();
+            assert!(log.current_board().is_empty());
+        }
+
+        assert_eq!(log.recent().count(), 3);
+        assert_eq!(log.stats().boards, 10);
+
+        let mut empty = SessionLog::new(0);
+        assert!(!empty.finish_board().won);
+        assert_eq!(empty.recent().count(), 0);
+        assert_eq!(empty.stats().boards, 1);
+    }
+}
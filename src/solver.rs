@@ -0,0 +1,545 @@
+//! This module handles suggesting guesses, for hints and for benchmarking strategies.
+//!
+//! A [`Solver`] keeps the words in [`GOOD_WORDS`] that could still be the answer, and suggests the
+//! guess from [`VALID_WORDS`] that's expected to narrow them down the most. Every remaining
+//! candidate is assumed to be equally likely.
+//!
+//! The suggestions assume the [`Classic`](super::scoring::ScoringRules::Classic) rules, like the
+//! rest of the [`analysis`](super::analysis) module.
+//!
+//! Finding the best guess scores every valid word against every candidate, which can take a while.
+//! A [`BestGuessSearch`] does the same work a few words at a time, so that callers that can't
+//! block, like a browser's main thread, can spread it out or stop early with the best guess so
+//! far.
+//!
+//! # Ties
+//!
+//! Guesses are ranked so that the suggestions are the same on every platform. Guesses whose
+//! expected information is within a rounding error of each other are tied, and ties are broken
+//! in this order:
+//!
+//! 1. Guesses that could be the answer come first.
+//! 2. Then guesses in [`GOOD_WORDS`], which are common words, come before the rest of
+//!    [`VALID_WORDS`].
+//! 3. Then guesses come in alphabetical order.
+
+use super::{
+    analysis,
+    scoring::{self, PatternMatrix},
+    valid_words::{GOOD_WORDS, VALID_WORDS},
+    Word,
+};
+use std::collections::HashSet;
+
+/// The number of different patterns that a guess can be scored with.
+const PATTERNS: usize = 243;
+
+/// The number of guesses to score against the candidates at once in
+/// [`best_guess`](Solver::best_guess), which bounds the size of each [`PatternMatrix`].
+const GUESSES_PER_MATRIX: usize = 256;
+
+/// Entropies closer than this are treated as equal, so that rounding errors don't decide ties.
+const EPSILON: f64 = 1e-9;
+
+/// The [`best_guess`](Solver::best_guess) before any guesses have been recorded.
+///
+/// This is the slowest guess to find, since every candidate is left, so it's worked out ahead of
+/// time and returned straight away by a new [`Solver`].
+pub const BEST_OPENER: &str = "SOARE";
+
+/// A solver that narrows down the possible answers as guesses are scored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solver {
+    /// The words in [`GOOD_WORDS`] that are consistent with every recorded guess, in the same
+    /// order.
+    candidates: Vec<&'static str>,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Solver {
+    /// Create a solver where every word in [`GOOD_WORDS`] could be the answer.
+    pub fn new() -> Self {
+        Self {
+            candidates: GOOD_WORDS.to_vec(),
+        }
+    }
+
+    /// Create a solver that has recorded the given scored guesses, like
+    /// [`Game::guess_history`](super::Game::guess_history).
+    pub fn from_history(history: &[Word]) -> Self {
+        Self {
+            candidates: analysis::candidates(history).collect(),
+        }
+    }
+
+    /// Create a solver that has recorded every guess in the given
+    /// [`Constraints`](analysis::Constraints), like one made with
+    /// [`from_history`](Solver::from_history).
+    pub fn from_constraints(constraints: &analysis::Constraints) -> Self {
+        Self {
+            candidates: constraints.filter_candidates(),
+        }
+    }
+
+    /// Record a scored guess, and remove the candidates that wouldn't have given the same colours.
+    pub fn record(&mut self, word: &Word) {
+        self.candidates
+            .retain(|answer| analysis::is_consistent(answer, std::slice::from_ref(word)));
+    }
+
+    /// Get every word that could still be the answer, in the same order as [`GOOD_WORDS`].
+    ///
+    /// If this is empty, the recorded guesses weren't scored against a word in [`GOOD_WORDS`].
+    pub fn remaining_candidates(&self) -> &[&'static str] {
+        &self.candidates
+    }
+
+    /// Get the expected information in bits from playing the given guess, which is the entropy of
+    /// the patterns that it would be scored with against the remaining candidates.
+    ///
+    /// A guess that splits the candidates into many small groups gives more information. If no
+    /// candidates are left, or the guess isn't 5 ASCII characters, this is 0.
+    pub fn expected_information(&self, guess: &str) -> f64 {
+        information(&self.candidates, guess)
+    }
+
+    /// Suggest the guess from [`VALID_WORDS`] with the most
+    /// [`expected_information`](Solver::expected_information).
+    ///
+    /// Ties are broken like in the [module documentation](self). If there are at most 2
+    /// candidates left, we suggest the first one, since it might win straight away. If no
+    /// candidates are left, we return [`None`].
+    ///
+    /// Every valid word is scored against every candidate, so this would be slowest at the start
+    /// of a game, but then we return [`BEST_OPENER`] without searching. After one guess, far
+    /// fewer candidates are left, so the search is much faster.
+    pub fn best_guess(&self) -> Option<&'static str> {
+        let mut search = self.search();
+        search.step(VALID_WORDS.len());
+        search.best_so_far()
+    }
+
+    /// Suggest the best guess like [`best_guess`](Solver::best_guess), but only score up to the
+    /// given number of valid words, in the order of [`VALID_WORDS`], and return the best of them.
+    ///
+    /// This bounds the time that the search takes. Use a [`BestGuessSearch`] to bound it by a
+    /// clock instead.
+    pub fn best_guess_within(&self, max_guesses: usize) -> Option<&'static str> {
+        let mut search = self.search();
+        search.step(max_guesses);
+        search.best_so_far()
+    }
+
+    /// Start a search for the [`best_guess`](Solver::best_guess) that can be run a few words at a
+    /// time. See [`BestGuessSearch`].
+    pub fn search(&self) -> BestGuessSearch {
+        BestGuessSearch::new(self.candidates.clone())
+    }
+
+    /// Grade the given guess out of 100, by how much of the
+    /// [`expected_information`](Solver::expected_information) of the
+    /// [`best_guess`](Solver::best_guess) it would give.
+    ///
+    /// If only one candidate is left, then no guess gives any information, so the grade is 100 for
+    /// guessing that candidate and 0 for anything else. If no candidates are left, the grade is
+    /// always 0.
+    ///
+    /// This finds the best guess, so it's as slow as [`best_guess`](Solver::best_guess).
+    pub fn grade(&self, guess: &str) -> u8 {
+        let mut search = self.search();
+        search.step(VALID_WORDS.len());
+        search.grade(guess)
+    }
+
+    /// Grade each of the given scored guesses out of 100, where each guess is graded against the
+    /// candidates left before it was played, starting from this solver's candidates. See
+    /// [`grade`](Solver::grade).
+    ///
+    /// The result is as long as the history, so a finished game can be annotated row by row with
+    /// `Solver::new().grade_history(&game.guess_history)`, like with
+    /// [`candidate_counts`](analysis::candidate_counts). This solver isn't changed.
+    pub fn grade_history(&self, history: &[Word]) -> Vec<u8> {
+        let mut solver = self.clone();
+
+        history
+            .iter()
+            .map(|word| {
+                let guess: String = word.iter().map(|letter| letter.letter).collect();
+                let grade = solver.grade(&guess);
+                solver.record(word);
+                grade
+            })
+            .collect()
+    }
+}
+
+/// A search for the [`best_guess`](Solver::best_guess) of a [`Solver`], which scores the words in
+/// [`VALID_WORDS`] in order, a few at a time.
+///
+/// Call [`step`](BestGuessSearch::step) until it returns `true`, checking a clock or yielding to
+/// other work in between, and then [`best_so_far`](BestGuessSearch::best_so_far) is the same as
+/// [`Solver::best_guess`]. The search can also be stopped early, and then the best guess so far is
+/// the best of the words scored so far.
+///
+/// At most a few hundred words are scored against the candidates at once, so the memory used
+/// doesn't depend on how many words are scored in each step.
+#[derive(Clone, Debug)]
+pub struct BestGuessSearch {
+    /// The candidates of the solver that started the search.
+    candidates: Vec<&'static str>,
+
+    /// The index in [`VALID_WORDS`] of the next word to score.
+    next: usize,
+
+    /// The best guess so far.
+    best: Option<Ranked>,
+}
+
+/// A guess with everything that it's ranked by. See the [module documentation](self).
+#[derive(Clone, Copy, Debug)]
+struct Ranked {
+    /// The guess.
+    guess: &'static str,
+
+    /// The expected information of the guess in bits.
+    information: f64,
+
+    /// Whether the guess could be the answer.
+    is_candidate: bool,
+
+    /// Whether the guess is in [`GOOD_WORDS`].
+    is_common: bool,
+}
+
+impl Ranked {
+    /// Check whether this guess ranks above the other one.
+    fn beats(&self, other: &Self) -> bool {
+        if (self.information - other.information).abs() > EPSILON {
+            return self.information > other.information;
+        }
+
+        (other.is_candidate, other.is_common, self.guess)
+            < (self.is_candidate, self.is_common, other.guess)
+    }
+}
+
+impl BestGuessSearch {
+    /// Start a search among the given candidates.
+    ///
+    /// If there's no need to score any words, like with at most 2 candidates or with every
+    /// candidate left, then the search is finished straight away.
+    fn new(candidates: Vec<&'static str>) -> Self {
+        // The candidates are a subset of GOOD_WORDS, so they're all left if there are as many
+        let known = if candidates.len() <= 2 {
+            Some(candidates.first().copied())
+        } else if candidates.len() == GOOD_WORDS.len() {
+            Some(Some(BEST_OPENER))
+        } else {
+            None
+        };
+
+        Self {
+            best: known.flatten().map(|guess| Ranked {
+                guess,
+                information: 0.0,
+                is_candidate: true,
+                is_common: true,
+            }),
+            next: if known.is_some() {
+                VALID_WORDS.len()
+            } else {
+                0
+            },
+            candidates,
+        }
+    }
+
+    /// Score up to the given number of words, and return whether the search is finished.
+    ///
+    /// Ties are broken like in the [module documentation](self).
+    pub fn step(&mut self, guesses: usize) -> bool {
+        let end = self.next.saturating_add(guesses).min(VALID_WORDS.len());
+        let words = VALID_WORDS.get(self.next..end).unwrap_or_default();
+        self.next = end;
+
+        let candidates: HashSet<&str> = self.candidates.iter().copied().collect();
+        let common: HashSet<&str> = GOOD_WORDS.iter().copied().collect();
+
+        for guesses in words.chunks(GUESSES_PER_MATRIX) {
+            let Ok(matrix) = scoring::score_matrix(&self.candidates, guesses) else {
+                continue;
+            };
+
+            for (index, &guess) in guesses.iter().enumerate() {
+                let ranked = Ranked {
+                    guess,
+                    information: entropy(&matrix, index),
+                    is_candidate: candidates.contains(guess),
+                    is_common: common.contains(guess),
+                };
+
+                if self.best.is_none_or(|best| ranked.beats(&best)) {
+                    self.best = Some(ranked);
+                }
+            }
+        }
+
+        self.is_finished()
+    }
+
+    /// Check whether every word has been scored, or didn't need to be.
+    pub fn is_finished(&self) -> bool {
+        self.next >= VALID_WORDS.len()
+    }
+
+    /// Get the fraction of the words in [`VALID_WORDS`] that have been scored, from 0 to 1.
+    pub fn progress(&self) -> f64 {
+        self.next as f64 / VALID_WORDS.len() as f64
+    }
+
+    /// Get the best guess among the words scored so far, or [`None`] if none have been scored or
+    /// no candidates are left.
+    pub fn best_so_far(&self) -> Option<&'static str> {
+        self.best.map(|best| best.guess)
+    }
+
+    /// Grade the given guess out of 100 against the [`best_so_far`](BestGuessSearch::best_so_far),
+    /// like [`Solver::grade`].
+    ///
+    /// If the search isn't finished, the best guess so far might not be the best guess, so the
+    /// grade might be too high.
+    pub fn grade(&self, guess: &str) -> u8 {
+        let Some(best) = self.best_so_far() else {
+            return 0;
+        };
+
+        let best_information = information(&self.candidates, best);
+        if best_information < EPSILON {
+            return if best.eq_ignore_ascii_case(guess) {
+                100
+            } else {
+                0
+            };
+        }
+
+        let fraction = information(&self.candidates, guess) / best_information;
+        // The fraction is clamped, so the grade always fits in a u8
+        (fraction.clamp(0.0, 1.0) * 100.0).round() as u8
+    }
+}
+
+/// Calculate the expected information in bits from playing the given guess against the given
+/// candidates. See [`Solver::expected_information`].
+fn information(candidates: &[&str], guess: &str) -> f64 {
+    scoring::score_matrix(candidates, &[guess]).map_or(0.0, |matrix| entropy(&matrix, 0))
+}
+
+/// Calculate the entropy in bits of the patterns in the given column of the matrix.
+fn entropy(matrix: &PatternMatrix, guess: usize) -> f64 {
+    let mut counts = [0_usize; PATTERNS];
+    for answer in 0..matrix.answers() {
+        if let Some(count) = matrix
+            .pattern_code(answer, guess)
+            .and_then(|code| counts.get_mut(usize::from(code)))
+        {
+            *count += 1;
+        }
+    }
+
+    let total = matrix.answers() as f64;
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solver_narrows_candidates() {
+        let mut solver = Solver::new();
+        assert_eq!(solver.remaining_candidates(), GOOD_WORDS);
+
+        let history = [
+            scoring::score_guess("HUNCH", "CRANE").unwrap(),
+            scoring::score_guess("HUNCH", "MOIST").unwrap(),
+        ];
+        for word in &history {
+            solver.record(word);
+        }
+        assert_eq!(solver, Solver::from_history(&history));
+        assert_eq!(
+            solver,
+            Solver::from_constraints(&analysis::Constraints::from_history(&history))
+        );
+        assert!(solver.remaining_candidates().contains(&"HUNCH"));
+        assert!(solver.remaining_candidates().len() < 20);
+
+        let before = solver.remaining_candidates().len();
+        let guess = solver.best_guess().unwrap();
+        let mut after = solver.clone();
+        after.record(&scoring::score_guess("HUNCH", guess).unwrap());
+        assert!(after.remaining_candidates().len() < before);
+    }
+
+    #[test]
+    fn best_guess_maximises_information() {
+        let solver = Solver::from_history(&[
+            scoring::score_guess("SHALT", "STAIR").unwrap(),
+            scoring::score_guess("SHALT", "EERIE").unwrap(),
+        ]);
+        let best = solver.best_guess().unwrap();
+        let best_information = solver.expected_information(best);
+
+        assert!(best_information > 0.0);
+        for guess in solver.remaining_candidates() {
+            assert!(solver.expected_information(guess) <= best_information + EPSILON);
+        }
+    }
+
+    #[test]
+    fn best_opener_is_precomputed() {
+        let solver = Solver::new();
+        assert_eq!(solver.best_guess(), Some(BEST_OPENER));
+        assert!(VALID_WORDS.contains(&BEST_OPENER));
+        assert_eq!(solver.grade(BEST_OPENER), 100);
+
+        // Searching every valid word takes too long without optimisations, so check a sample
+        let best_information = solver.expected_information(BEST_OPENER);
+        let sample: Vec<&str> = VALID_WORDS.iter().copied().step_by(16).collect();
+        let matrix = scoring::score_matrix(GOOD_WORDS, &sample).unwrap();
+        for index in 0..sample.len() {
+            assert!(entropy(&matrix, index) <= best_information + EPSILON);
+        }
+    }
+
+    #[test]
+    fn search_in_steps() {
+        let solver = Solver::from_history(&[
+            scoring::score_guess("SHALT", "STAIR").unwrap(),
+            scoring::score_guess("SHALT", "EERIE").unwrap(),
+        ]);
+
+        let mut search = solver.search();
+        assert!(!search.is_finished());
+        assert_eq!(search.progress(), 0.0);
+        assert_eq!(search.best_so_far(), None);
+
+        assert!(!search.step(1000));
+        let partial = search.best_so_far().unwrap();
+        assert_eq!(solver.best_guess_within(1000), Some(partial));
+        assert!(VALID_WORDS[..1000].contains(&partial));
+        assert!(search.progress() > 0.0 && search.progress() < 1.0);
+        assert_eq!(search.grade(partial), 100);
+
+        while !search.step(1000) {}
+        assert_eq!(search.progress(), 1.0);
+        assert_eq!(search.best_so_far(), solver.best_guess());
+        assert_eq!(search.grade("FUZZY"), solver.grade("FUZZY"));
+        assert_eq!(solver.best_guess_within(0), None);
+
+        assert!(Solver::new().search().is_finished());
+        assert_eq!(Solver::new().search().best_so_far(), Some(BEST_OPENER));
+    }
+
+    #[test]
+    fn ties_are_broken_canonically() {
+        let search = |candidates: &[&'static str]| {
+            let mut search = BestGuessSearch::new(candidates.to_vec());
+            search.step(VALID_WORDS.len());
+            search
+        };
+
+        // Only a guess that isn't the answer can tell all four of these apart
+        let candidates = ["FIGHT", "LIGHT", "MIGHT", "NIGHT"];
+        let best = search(&candidates).best.unwrap();
+        assert!(!best.is_candidate);
+        assert!(best.information > 2.0 - EPSILON);
+
+        let tied: Vec<&str> = VALID_WORDS
+            .iter()
+            .copied()
+            .filter(|&guess| information(&candidates, guess) > best.information - EPSILON)
+            .collect();
+        assert!(tied.len() > 1);
+        let first_common = tied.iter().find(|guess| GOOD_WORDS.contains(guess));
+        assert_eq!(Some(&best.guess), first_common.or(tied.first()));
+
+        // The order of the candidates doesn't matter
+        let mut reversed = candidates;
+        reversed.reverse();
+        assert_eq!(search(&reversed).best_so_far(), Some(best.guess));
+
+        let earlier = Ranked {
+            guess: "AAAAA",
+            ..best
+        };
+        assert!(earlier.beats(&best));
+        assert!(!best.beats(&earlier));
+        let candidate = Ranked {
+            guess: "ZZZZZ",
+            is_candidate: true,
+            ..best
+        };
+        assert!(candidate.beats(&earlier));
+    }
+
+    #[test]
+    fn grades_compare_with_the_best_guess() {
+        let solver = Solver::from_history(&[
+            scoring::score_guess("SHALT", "STAIR").unwrap(),
+            scoring::score_guess("SHALT", "EERIE").unwrap(),
+        ]);
+        let best = solver.best_guess().unwrap();
+        assert_eq!(solver.grade(best), 100);
+        assert!(solver.grade("FUZZY") < 100);
+        assert_eq!(solver.grade("spurgs"), 0);
+
+        let solver = Solver::from_history(&[scoring::score_guess("HUNCH", "HUNCH").unwrap()]);
+        assert_eq!(solver.grade("hunch"), 100);
+        assert_eq!(solver.grade("CRANE"), 0);
+    }
+
+    #[test]
+    fn grade_every_guess() {
+        let opening = [scoring::score_guess("HUNCH", "CRANE").unwrap()];
+        let history = [
+            scoring::score_guess("HUNCH", "MOIST").unwrap(),
+            scoring::score_guess("HUNCH", "HUNCH").unwrap(),
+        ];
+
+        let solver = Solver::from_history(&opening);
+        let grades = solver.grade_history(&history);
+        let after_moist = Solver::from_history(&[opening[0], history[0]]);
+        assert_eq!(
+            grades,
+            vec![solver.grade("MOIST"), after_moist.grade("HUNCH")]
+        );
+        assert_eq!(solver, Solver::from_history(&opening));
+        assert!(solver.grade_history(&[]).is_empty());
+    }
+
+    #[test]
+    fn few_candidates() {
+        let solver = Solver::from_history(&[scoring::score_guess("HUNCH", "HUNCH").unwrap()]);
+        assert_eq!(solver.remaining_candidates(), ["HUNCH"]);
+        assert_eq!(solver.best_guess(), Some("HUNCH"));
+        assert_eq!(solver.expected_information("CRANE"), 0.0);
+
+        let solver = Solver::from_history(&[scoring::score_guess("DYSON", "DYSON").unwrap()]);
+        assert!(solver.remaining_candidates().is_empty());
+        assert_eq!(solver.best_guess(), None);
+        assert_eq!(solver.expected_information("CRANE"), 0.0);
+    }
+}
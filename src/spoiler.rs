@@ -0,0 +1,151 @@
+//! This module handles keeping the answer out of places where it shouldn't be, like logs, props,
+//! and share text.
+//!
+//! Values that contain the answer are wrapped in a [`SpoilerGuard`], which can only be read with
+//! [`reveal_for_display`](SpoilerGuard::reveal_for_display). That makes every place that shows the
+//! answer an explicit decision, and easy to find with grep.
+
+use std::fmt;
+
+/// A wrapper around a value that contains the answer, like [`Game::word`](super::Game::word).
+///
+/// The value can't be read without calling [`reveal_for_display`](SpoilerGuard::reveal_for_display)
+/// or [`into_revealed`](SpoilerGuard::into_revealed), and there's no
+/// [`Display`](fmt::Display) implementation. The [`Debug`](fmt::Debug) implementation hides the
+/// value unless the `reveal` feature is enabled.
+///
+/// With the `serde` feature, a guarded [`String`] is serialized obfuscated, so the answer can't be
+/// read at a glance from a saved game.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SpoilerGuard<T>(T);
+
+impl<T> SpoilerGuard<T> {
+    /// Wrap a value that contains the answer.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Get the value, so that it can be shown to the player.
+    ///
+    /// Only call this when the player should see the answer, like when the game is over.
+    pub fn reveal_for_display(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwrap the value, so that it can be shown to the player or stored.
+    ///
+    /// Like [`reveal_for_display`](SpoilerGuard::reveal_for_display), only call this when the
+    /// answer is meant to be seen.
+    pub fn into_revealed(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for SpoilerGuard<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SpoilerGuard<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if cfg!(feature = "reveal") {
+            f.debug_tuple("SpoilerGuard").field(&self.0).finish()
+        } else {
+            f.write_str("SpoilerGuard(<hidden>)")
+        }
+    }
+}
+
+/// The key that guarded strings are XORed with when they're serialized.
+///
+/// This isn't encryption, since the key is public. It just stops the answer being read at a glance
+/// from a saved game.
+#[cfg(feature = "serde")]
+const OBFUSCATION_KEY: &[u8] = b"wordle";
+
+/// XOR the bytes with [`OBFUSCATION_KEY`], repeating the key as needed. This is its own inverse.
+#[cfg(feature = "serde")]
+fn xor_with_key(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .zip(OBFUSCATION_KEY.iter().cycle())
+        .map(|(byte, key)| byte ^ key)
+        .collect()
+}
+
+/// The string is serialized as hex after being XORed with a fixed key, so that saved games don't
+/// contain the answer in plain text.
+#[cfg(feature = "serde")]
+impl serde::Serialize for SpoilerGuard<String> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let hex: String = xor_with_key(self.0.as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        serializer.serialize_str(&hex)
+    }
+}
+
+/// The string is deserialized from the obfuscated hex that it's serialized as.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SpoilerGuard<String> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let hex = String::deserialize(deserializer)?;
+        if !hex.is_ascii() || hex.len() % 2 != 0 {
+            return Err(D::Error::custom(format!(
+                "invalid obfuscated string: {hex:?}"
+            )));
+        }
+
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| D::Error::custom(format!("invalid obfuscated string: {hex:?}")))?;
+
+        String::from_utf8(xor_with_key(&bytes))
+            .map(Self)
+            .map_err(|_| D::Error::custom(format!("invalid obfuscated string: {hex:?}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spoiler_guard_reveals_explicitly() {
+        let guard = SpoilerGuard::new("DYSON".to_string());
+        assert_eq!(guard.reveal_for_display(), "DYSON");
+        assert_eq!(guard, SpoilerGuard::from("DYSON".to_string()));
+        assert_eq!(guard.into_revealed(), "DYSON");
+    }
+
+    #[test]
+    #[cfg(not(feature = "reveal"))]
+    fn debug_hides_value() {
+        let guard = SpoilerGuard::new("DYSON");
+        assert_eq!(format!("{guard:?}"), "SpoilerGuard(<hidden>)");
+        assert_eq!(format!("{guard:#?}"), "SpoilerGuard(<hidden>)");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_obfuscates_value() {
+        let guard = SpoilerGuard::new("DYSON".to_string());
+        let json = serde_json::to_string(&guard).unwrap();
+        assert!(!json.to_ascii_uppercase().contains("DYSON"));
+        assert_eq!(
+            serde_json::from_str::<SpoilerGuard<String>>(&json).unwrap(),
+            guard
+        );
+
+        assert!(serde_json::from_str::<SpoilerGuard<String>>("\"DYSON\"").is_err());
+        assert!(serde_json::from_str::<SpoilerGuard<String>>("\"3\"").is_err());
+        assert!(serde_json::from_str::<SpoilerGuard<String>>("\"é1\"").is_err());
+        assert!(serde_json::from_str::<SpoilerGuard<String>>("\"ff\"").is_err());
+    }
+}
@@ -0,0 +1,298 @@
+//! This module handles colour themes, so that every frontend and renderer draws positions the same
+//! way.
+//!
+//! A [`Theme`] gives a [`TileStyle`] for each [`Position`], which has the colour as RGB, as an
+//! emoji, as a CSS class name, and as an ANSI colour. The player's [`Motion`] preference says
+//! whether frontends should animate the board.
+
+use super::letters::Position;
+use std::fmt;
+
+/// A colour as red, green, and blue components.
+///
+/// The [`Display`](fmt::Display) implementation gives the CSS hex notation, like `#6aaa64`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// How to draw a tile or key with a certain [`Position`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TileStyle {
+    /// The background colour of the tile.
+    pub colour: Rgb,
+
+    /// The square emoji for the tile, used in share text.
+    pub emoji: char,
+
+    /// The CSS class name of the tile in the web app.
+    ///
+    /// These are the same in every theme, since the web app changes the colours of the classes
+    /// with [`Theme::css_class`] instead.
+    pub css_class: &'static str,
+
+    /// The colour of the letter in a terminal, as an index into the 256 colour ANSI palette.
+    pub ansi: u8,
+}
+
+/// A colour theme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Theme {
+    /// The name of the theme, in lowercase.
+    pub name: &'static str,
+
+    /// The CSS class name that the web app puts on the page to use this theme.
+    pub css_class: &'static str,
+
+    /// The background behind the board.
+    pub background: Rgb,
+
+    /// The text of letters on coloured tiles.
+    pub text: Rgb,
+
+    /// The colour of keys that haven't been guessed in a terminal, as an index into the 256
+    /// colour ANSI palette.
+    pub unguessed_ansi: u8,
+
+    /// Tiles that are [`Correct`](Position::Correct).
+    pub correct: TileStyle,
+
+    /// Tiles that are in the [`WrongPosition`](Position::WrongPosition).
+    pub wrong_position: TileStyle,
+
+    /// Tiles that are [`NotInWord`](Position::NotInWord).
+    pub not_in_word: TileStyle,
+}
+
+impl Theme {
+    /// The light colours of the original Wordle.
+    pub const CLASSIC: Self = Self {
+        name: "classic",
+        css_class: "light",
+        background: Rgb(0xff, 0xff, 0xff),
+        text: Rgb(0xff, 0xff, 0xff),
+        unguessed_ansi: 7,
+        correct: TileStyle {
+            colour: Rgb(0x6a, 0xaa, 0x64),
+            emoji: '🟩',
+            css_class: "correct",
+            ansi: 2,
+        },
+        wrong_position: TileStyle {
+            colour: Rgb(0xc9, 0xb4, 0x58),
+            emoji: '🟨',
+            css_class: "wrongposition",
+            ansi: 3,
+        },
+        not_in_word: TileStyle {
+            colour: Rgb(0x78, 0x7c, 0x7e),
+            emoji: '⬜',
+            css_class: "notinword",
+            ansi: 0,
+        },
+    };
+
+    /// The dark colours of the original Wordle.
+    pub const DARK: Self = Self {
+        name: "dark",
+        css_class: "dark",
+        background: Rgb(0x12, 0x12, 0x13),
+        text: Rgb(0xff, 0xff, 0xff),
+        unguessed_ansi: 15,
+        correct: TileStyle {
+            colour: Rgb(0x53, 0x8d, 0x4e),
+            ..Self::CLASSIC.correct
+        },
+        wrong_position: TileStyle {
+            colour: Rgb(0xb5, 0x9f, 0x3b),
+            ..Self::CLASSIC.wrong_position
+        },
+        not_in_word: TileStyle {
+            colour: Rgb(0x3a, 0x3a, 0x3c),
+            emoji: '⬛',
+            ansi: 8,
+            ..Self::CLASSIC.not_in_word
+        },
+    };
+
+    /// The high contrast colours of the original Wordle, with orange and blue instead of green
+    /// and yellow.
+    pub const HIGH_CONTRAST: Self = Self {
+        name: "high-contrast",
+        css_class: "high-contrast",
+        correct: TileStyle {
+            colour: Rgb(0xf5, 0x79, 0x3a),
+            emoji: '🟧',
+            ansi: 208,
+            ..Self::CLASSIC.correct
+        },
+        wrong_position: TileStyle {
+            colour: Rgb(0x85, 0xc0, 0xf9),
+            emoji: '🟦',
+            ansi: 117,
+            ..Self::CLASSIC.wrong_position
+        },
+        ..Self::CLASSIC
+    };
+
+    /// Colours from the Okabe-Ito palette, which can be told apart with every common kind of
+    /// colour blindness, with blue for correct and orange for the wrong position.
+    pub const COLOUR_BLIND: Self = Self {
+        name: "colour-blind",
+        css_class: "colour-blind",
+        correct: TileStyle {
+            colour: Rgb(0x00, 0x72, 0xb2),
+            emoji: '🟦',
+            ansi: 25,
+            ..Self::CLASSIC.correct
+        },
+        wrong_position: TileStyle {
+            colour: Rgb(0xe6, 0x9f, 0x00),
+            emoji: '🟧',
+            ansi: 214,
+            ..Self::CLASSIC.wrong_position
+        },
+        ..Self::CLASSIC
+    };
+
+    /// Every built-in theme.
+    pub const ALL: [Self; 4] = [
+        Self::CLASSIC,
+        Self::DARK,
+        Self::HIGH_CONTRAST,
+        Self::COLOUR_BLIND,
+    ];
+
+    /// Get the built-in theme with the given name, if there is one.
+    pub fn by_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|theme| theme.name == name)
+    }
+
+    /// Get the style of a tile with the given position.
+    pub fn tile(&self, position: Position) -> &TileStyle {
+        match position {
+            Position::Correct => &self.correct,
+            Position::WrongPosition => &self.wrong_position,
+            Position::NotInWord => &self.not_in_word,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
+
+/// The player's preference for animations, like tiles flipping and rows shaking or bouncing.
+///
+/// With reduced motion, frontends should change tiles instantly, with at most a brief change of
+/// colour to draw attention to them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Motion {
+    /// Follow the system preference, like `prefers-reduced-motion` in a browser.
+    #[default]
+    System,
+
+    /// Always animate.
+    Full,
+
+    /// Never animate.
+    Reduced,
+}
+
+impl Motion {
+    /// The CSS class name that the web app puts on the page when motion is reduced.
+    pub const REDUCED_CSS_CLASS: &'static str = "reduced-motion";
+
+    /// Every motion preference, in the order that settings should cycle through them.
+    pub const ALL: [Self; 3] = [Self::System, Self::Full, Self::Reduced];
+
+    /// Get the name of this preference, in lowercase, for settings files and storage.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::System => "system",
+            Self::Full => "full",
+            Self::Reduced => "reduced",
+        }
+    }
+
+    /// Get the preference with the given name, if there is one. See [`name`](Motion::name).
+    pub fn by_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|motion| motion.name() == name)
+    }
+
+    /// Get the preference that comes after this one in [`ALL`](Motion::ALL), wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Self::System => Self::Full,
+            Self::Full => Self::Reduced,
+            Self::Reduced => Self::System,
+        }
+    }
+
+    /// Check whether animations should be reduced, given whether the system prefers reduced
+    /// motion.
+    pub fn is_reduced(self, system_prefers_reduced: bool) -> bool {
+        match self {
+            Self::System => system_prefers_reduced,
+            Self::Full => false,
+            Self::Reduced => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgb_css_notation() {
+        assert_eq!(Rgb(0x6a, 0xaa, 0x64).to_string(), "#6aaa64");
+        assert_eq!(Rgb(0, 0x0f, 0xff).to_string(), "#000fff");
+    }
+
+    #[test]
+    fn themes_are_distinct() {
+        for theme in Theme::ALL {
+            assert_eq!(Theme::by_name(theme.name), Some(theme));
+
+            let positions = [
+                Position::Correct,
+                Position::WrongPosition,
+                Position::NotInWord,
+            ];
+            for a in positions {
+                for b in positions {
+                    if a != b {
+                        assert_ne!(theme.tile(a).colour, theme.tile(b).colour);
+                        assert_ne!(theme.tile(a).emoji, theme.tile(b).emoji);
+                        assert_ne!(theme.tile(a).ansi, theme.tile(b).ansi);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(Theme::by_name("sepia"), None);
+        assert_eq!(Theme::DARK.tile(Position::Correct).css_class, "correct");
+    }
+
+    #[test]
+    fn motion_preferences() {
+        for motion in Motion::ALL {
+            assert_eq!(Motion::by_name(motion.name()), Some(motion));
+            assert_eq!(motion.next().next().next(), motion);
+        }
+        assert_eq!(Motion::by_name("none"), None);
+
+        assert!(Motion::System.is_reduced(true));
+        assert!(!Motion::System.is_reduced(false));
+        assert!(!Motion::Full.is_reduced(true));
+        assert!(Motion::Reduced.is_reduced(false));
+    }
+}
@@ -0,0 +1,133 @@
+//! This module handles scoring time attack games, where players are ranked by both the number of
+//! guesses they needed and how long they took.
+//!
+//! Every frontend and leaderboard needs to rank players the same way, so the formula is specified
+//! exactly here:
+//!
+//! 1. A lost game scores 0.
+//! 2. A won game scores `1000 * (7 - guesses)`, so a win in 1 guess scores 6000 and a win in 6
+//!    guesses scores 1000.
+//! 3. A won game also gets a time bonus of `600 - seconds`, where `seconds` is the elapsed time in
+//!    whole seconds, rounded down. The bonus can't be negative, so games longer than 10 minutes
+//!    get no bonus.
+//!
+//! The bonus is always less than 1000, so a win in fewer guesses always beats a win in more
+//! guesses, no matter how long it took. Time only decides between games with the same number of
+//! guesses.
+//!
+//! [`TIME_ATTACK_TEST_VECTORS`] can be used to check other implementations of this formula
+//! against this one.
+
+use std::{cmp::Reverse, time::Duration};
+
+/// The most guesses that a game can take. This is the same as [`crate::MAX_GUESSES`].
+#[deprecated(note = "use wordle::MAX_GUESSES")]
+pub const MAX_GUESSES: usize = crate::MAX_GUESSES;
+
+/// The number of seconds after which there's no time bonus.
+const TIME_LIMIT_SECONDS: u64 = 600;
+
+/// Inputs and outputs of [`time_attack_score`], as the number of guesses (or [`None`] for a lost
+/// game), the elapsed milliseconds, and the score.
+pub const TIME_ATTACK_TEST_VECTORS: [(Option<usize>, u64, u32); 6] = [
+    (Some(1), 0, 6600),
+    (Some(3), 45_999, 4555),
+    (Some(6), 599_999, 1001),
+    (Some(6), 600_000, 1000),
+    (Some(2), 3_600_000, 5000),
+    (None, 30_000, 0),
+];
+
+/// Compute the time attack score for a game. See the [module documentation](self) for the
+/// formula.
+///
+/// `guesses` is the number of guesses it took to win, or [`None`] if the game was lost. Wins with
+/// 0 guesses or more than [`MAX_GUESSES`](crate::MAX_GUESSES) are impossible, so they score 0
+/// like a loss.
+pub fn time_attack_score(guesses: Option<usize>, elapsed: Duration) -> u32 {
+    match guesses {
+        Some(guesses @ 1..=crate::MAX_GUESSES) => {
+            let base = 1000 * (crate::MAX_GUESSES + 1 - guesses) as u32;
+            let bonus = TIME_LIMIT_SECONDS.saturating_sub(elapsed.as_secs()) as u32;
+            base + bonus
+        }
+        _ => 0,
+    }
+}
+
+/// A key for sorting time attack results, where better results sort first.
+///
+/// Results are ordered by their [`time_attack_score`], highest first, and then by their exact
+/// elapsed time, fastest first, so that results within the same second are still ranked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LeaderboardKey {
+    /// The score, reversed so that higher scores sort first.
+    score: Reverse<u32>,
+
+    /// The exact elapsed time.
+    elapsed: Duration,
+}
+
+impl LeaderboardKey {
+    /// Create the leaderboard key for a game, with the same arguments as [`time_attack_score`].
+    pub fn new(guesses: Option<usize>, elapsed: Duration) -> Self {
+        Self {
+            score: Reverse(time_attack_score(guesses, elapsed)),
+            elapsed,
+        }
+    }
+
+    /// Get the time attack score of this result.
+    pub fn score(&self) -> u32 {
+        self.score.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_attack_test_vectors() {
+        for (guesses, millis, score) in TIME_ATTACK_TEST_VECTORS {
+            assert_eq!(
+                time_attack_score(guesses, Duration::from_millis(millis)),
+                score,
+                "time_attack_score({guesses:?}, {millis}ms)"
+            );
+        }
+    }
+
+    #[test]
+    fn impossible_wins_score_zero() {
+        assert_eq!(time_attack_score(Some(0), Duration::ZERO), 0);
+        assert_eq!(time_attack_score(Some(7), Duration::ZERO), 0);
+    }
+
+    #[test]
+    fn leaderboard_keys_rank_results() {
+        let mut keys = [
+            LeaderboardKey::new(Some(4), Duration::from_millis(10_500)),
+            LeaderboardKey::new(None, Duration::from_secs(5)),
+            LeaderboardKey::new(Some(3), Duration::from_secs(500)),
+            LeaderboardKey::new(Some(4), Duration::from_millis(10_200)),
+            LeaderboardKey::new(Some(4), Duration::from_secs(60)),
+        ];
+        keys.sort();
+
+        let ranked: Vec<(u32, u128)> = keys
+            .iter()
+            .map(|key| (key.score(), key.elapsed.as_millis()))
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![
+                (4100, 500_000),
+                (3590, 10_200),
+                (3590, 10_500),
+                (3540, 60_000),
+                (0, 5_000),
+            ]
+        );
+    }
+}
@@ -6,13 +6,60 @@ pub const ALPHABET: [char; 26] = [
     'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
 ];
 
+/// A word from [`GOOD_WORDS`] to use if a word has to be chosen from an empty list, so that
+/// choosing a word never needs to panic.
+pub(crate) const FALLBACK_WORD: &str = "CIGAR";
+
+/// Get a hash of [`VALID_WORDS`] and [`GOOD_WORDS`], which changes whenever either list does.
+///
+/// Results from different versions of the word lists aren't necessarily comparable, so this is
+/// recorded in [`Rules`](super::rules::Rules). The hash is 64-bit FNV-1a over every word in both
+/// lists, with a `\n` after each word and an extra `\n` between the lists, so other
+/// implementations can compute it too.
+pub fn dictionary_version() -> u64 {
+    hash_word_lists(VALID_WORDS.iter().copied(), GOOD_WORDS.iter().copied())
+}
+
+/// Hash a list of valid guesses and a list of answers in the same way as [`dictionary_version`].
+pub(crate) fn hash_word_lists<'a>(
+    guesses: impl Iterator<Item = &'a str>,
+    answers: impl Iterator<Item = &'a str>,
+) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let bytes = guesses
+        .chain([""])
+        .chain(answers)
+        .flat_map(|word| word.bytes().chain([b'\n']));
+
+    bytes.fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Get all the words in [`VALID_WORDS`] that start with the given prefix, in alphabetical order.
+///
+/// The prefix is case-insensitive, and the words are returned in uppercase. An empty prefix
+/// matches every word.
+///
+/// This uses a binary search, since [`VALID_WORDS`] is sorted. Use
+/// [`WordLists::complete`](super::word_list::WordLists::complete) to complete guesses from the
+/// word list of a game.
+pub fn complete(prefix: &str) -> impl Iterator<Item = &'static str> {
+    super::word_list::complete_sorted(VALID_WORDS, prefix)
+}
+
 /// These are all the words that are considered valid guesses according to the original Wordle source code.
 ///
+/// The list is sorted alphabetically. Its length isn't part of the public API, since the list may
+/// change between versions.
+///
 /// I have also included "DYSON" as a valid word, as a sort of Easter egg, since that's my name.
 ///
 /// This list contains basically all 5 letter words in English, so it's mostly words that
 /// you've never heard of, like ABMHO, IMMIX, TYIYN, and WAQFS.
-pub const VALID_WORDS: [&str; 12973] = [
+pub static VALID_WORDS: &[&str] = &[
     "AAHED", "AALII", "AARGH", "AARTI", "ABACA", "ABACI", "ABACK", "ABACS", "ABAFT", "ABAKA",
     "ABAMP", "ABAND", "ABASE", "ABASH", "ABASK", "ABATE", "ABAYA", "ABBAS", "ABBED", "ABBES",
     "ABBEY", "ABBOT", "ABCEE", "ABEAM", "ABEAR", "ABELE", "ABERS", "ABETS", "ABHOR", "ABIDE",
@@ -1314,7 +1361,9 @@ pub const VALID_WORDS: [&str; 12973] = [
 ];
 
 /// These are all the words that the original Wordle could make the player guess as a target word.
-pub const GOOD_WORDS: [&str; 2315] = [
+///
+/// Like [`VALID_WORDS`], its length isn't part of the public API.
+pub static GOOD_WORDS: &[&str] = &[
     "ABACK", "ABASE", "ABATE", "ABBEY", "ABBOT", "ABHOR", "ABIDE", "ABLED", "ABODE", "ABORT",
     "ABOUT", "ABOVE", "ABUSE", "ABYSS", "ACORN", "ACRID", "ACTOR", "ACUTE", "ADAGE", "ADAPT",
     "ADEPT", "ADMIN", "ADMIT", "ADOBE", "ADOPT", "ADORE", "ADORN", "ADULT", "AFFIX", "AFIRE",
@@ -1548,3 +1597,30 @@ pub const GOOD_WORDS: [&str; 2315] = [
     "WRIST", "WRITE", "WRONG", "WROTE", "WRUNG", "WRYLY", "YACHT", "YEARN", "YEAST", "YIELD",
     "YOUNG", "YOUTH", "ZEBRA", "ZESTY", "ZONAL",
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_words_sorted() {
+        assert!(VALID_WORDS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn fallback_word_is_good() {
+        assert!(GOOD_WORDS.contains(&FALLBACK_WORD));
+    }
+
+    #[test]
+    fn complete_prefixes() {
+        assert_eq!(
+            complete("abac").collect::<Vec<_>>(),
+            vec!["ABACA", "ABACI", "ABACK", "ABACS"]
+        );
+        assert_eq!(complete("DYSON").collect::<Vec<_>>(), vec!["DYSON"]);
+        assert_eq!(complete("QXZ").count(), 0);
+        assert_eq!(complete("Öst").count(), 0);
+        assert_eq!(complete("").count(), VALID_WORDS.len());
+    }
+}
@@ -0,0 +1,140 @@
+//! This module handles checking guesses before they're scored, so that every frontend and server
+//! runs exactly the same checks.
+//!
+//! A [`GuessValidator`] holds the word lists and [`GamePolicy`] to check against, and checks each
+//! guess against the [`Constraints`] of the guesses before it. [`Game::validate_guess`] runs the
+//! same checks with the game's own settings.
+
+use super::{
+    analysis::Constraints, policy::GamePolicy, word_list::WordLists, Game, GuessError, Word,
+};
+
+/// A reusable set of checks for guesses.
+///
+/// Every guess must be 5 ASCII letters and be accepted by one of the
+/// [`word_lists`](GuessValidator::word_lists). The [`policy`](GuessValidator::policy) can restrict
+/// the first guess, turn on hard mode, and reject repeated guesses. The time-based rules of the
+/// policy, like [`min_guess_interval`](GamePolicy::min_guess_interval), need the game's clock, so
+/// they're only checked by [`Game::make_guess`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GuessValidator {
+    /// The word lists that a guess can come from, tried in order, so a guess is accepted if any of
+    /// them accepts it. If this is empty, the [`Builtin`](WordLists::Builtin) list is used.
+    pub word_lists: Vec<WordLists>,
+
+    /// The policy to check guesses against.
+    pub policy: GamePolicy,
+}
+
+impl GuessValidator {
+    /// Create a validator with the word list and policy of the given game.
+    pub fn for_game(game: &Game) -> Self {
+        Self {
+            word_lists: vec![game.word_list.clone()],
+            policy: game.policy.clone(),
+        }
+    }
+
+    /// Check whether the guess is valid after the guesses recorded in the constraints.
+    ///
+    /// The guess doesn't have to be uppercase.
+    ///
+    /// # Errors
+    ///
+    /// If the guess is invalid, we return the appropriate [`GuessError`] variant. If no word list
+    /// accepts it, the error is from the first list.
+    pub fn validate(&self, guess: &str, before: &Constraints) -> Result<(), GuessError> {
+        check_guess(&self.word_lists, &self.policy, guess, before.history())
+    }
+}
+
+/// Check the guess against the word lists and policy, after the given scored guesses. See
+/// [`GuessValidator`].
+pub(crate) fn check_guess(
+    word_lists: &[WordLists],
+    policy: &GamePolicy,
+    guess: &str,
+    history: &[Word],
+) -> Result<(), GuessError> {
+    match word_lists.split_first() {
+        None => WordLists::Builtin.check_guess(guess)?,
+        Some((first, rest)) => {
+            if let Err(error) = first.check_guess(guess) {
+                if !rest.iter().any(|list| list.check_guess(guess).is_ok()) {
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    let guess = guess.to_ascii_uppercase();
+    if history.is_empty() {
+        policy.check_opener(&guess)?;
+    }
+    policy.check_history(&guess, history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{scoring, word_list::CustomWordList};
+    use std::sync::Arc;
+
+    #[test]
+    fn word_lists_are_chained() {
+        let before = Constraints::default();
+        let custom = WordLists::Custom(Arc::new(CustomWordList::new(&["ZZZZZ"], &[]).unwrap()));
+
+        let validator = GuessValidator::default();
+        assert_eq!(validator.validate("crane", &before), Ok(()));
+        assert_eq!(
+            validator.validate("ZZZZZ", &before),
+            Err(GuessError::InvalidWord)
+        );
+        assert_eq!(
+            validator.validate("CRAN", &before),
+            Err(GuessError::WrongWordLength)
+        );
+
+        let validator = GuessValidator {
+            word_lists: vec![custom, WordLists::Builtin],
+            ..GuessValidator::default()
+        };
+        assert_eq!(validator.validate("zzzzz", &before), Ok(()));
+        assert_eq!(validator.validate("CRANE", &before), Ok(()));
+        assert_eq!(
+            validator.validate("QQQQQ", &before),
+            Err(GuessError::InvalidWord)
+        );
+    }
+
+    #[test]
+    fn hard_mode_and_repeats() {
+        let before = Constraints::from_history(&[scoring::score_guess("DYSON", "DOSES").unwrap()]);
+
+        let mut validator = GuessValidator::default();
+        assert_eq!(validator.validate("DOSES", &before), Ok(()));
+        assert_eq!(validator.validate("CRANE", &before), Ok(()));
+
+        validator.policy.reject_repeated_guesses = true;
+        assert_eq!(
+            validator.validate("doses", &before),
+            Err(GuessError::RepeatedGuess)
+        );
+
+        validator.policy.hard_mode = true;
+        assert_eq!(
+            validator.validate("CRANE", &before),
+            Err(GuessError::MissingCorrectLetter('D', 1))
+        );
+        assert_eq!(
+            validator.validate("DUSTY", &before),
+            Err(GuessError::MissingLetter('O'))
+        );
+        assert_eq!(validator.validate("DISCO", &before), Ok(()));
+        assert_eq!(validator.validate("dyson", &before), Ok(()));
+
+        // Hard mode doesn't restrict the first guess
+        assert_eq!(validator.validate("CRANE", &Constraints::default()), Ok(()));
+    }
+}
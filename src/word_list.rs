@@ -0,0 +1,378 @@
+//! This module handles supplying word lists at runtime, like themed lists, regional dialects, or
+//! restricted vocabularies, instead of [`GOOD_WORDS`] and [`VALID_WORDS`].
+//!
+//! Implement [`WordList`] for a list of your own, or use [`CustomWordList`], and then start a game
+//! with [`Game::with_word_list`](super::Game::with_word_list).
+
+use super::{
+    valid_words::{self, ALPHABET, GOOD_WORDS, VALID_WORDS},
+    GuessError,
+};
+use std::{fmt, sync::Arc};
+use thiserror::Error;
+
+/// A list of answers and accepted guesses.
+pub trait WordList {
+    /// Get every word that can be chosen as the answer, in uppercase.
+    fn answers(&self) -> &[String];
+
+    /// Check whether the given guess is accepted. The guess is always 5 uppercase ASCII
+    /// characters.
+    fn is_accepted(&self, guess: &str) -> bool;
+
+    /// Get a hash of the list, which changes whenever the list does.
+    ///
+    /// This is recorded as the [`dictionary_version`](super::rules::Rules::dictionary_version)
+    /// of games that use this list, so that their results aren't compared with results from
+    /// other lists.
+    fn dictionary_version(&self) -> u64;
+
+    /// Get every accepted guess that starts with the given prefix, in uppercase and in
+    /// alphabetical order.
+    ///
+    /// The prefix is case-insensitive, and an empty prefix matches every word. By default, only
+    /// the [`answers`](WordList::answers) are completed, since there's no way to list every
+    /// accepted guess. Lists that know their accepted guesses should complete those instead.
+    fn complete(&self, prefix: &str) -> Vec<String> {
+        let mut answers = self.answers().to_vec();
+        answers.sort();
+        answers.dedup();
+
+        complete_sorted(&answers, prefix)
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Get every word in the sorted list that starts with the given prefix, like
+/// [`valid_words::complete`].
+///
+/// This uses a binary search, so the words must be in uppercase and sorted.
+pub(crate) fn complete_sorted<'a, S: AsRef<str>>(
+    sorted: &'a [S],
+    prefix: &str,
+) -> impl Iterator<Item = &'a str> {
+    let prefix = prefix.to_ascii_uppercase();
+    let start = sorted.partition_point(|word| word.as_ref() < prefix.as_str());
+
+    sorted[start..]
+        .iter()
+        .map(AsRef::as_ref)
+        .take_while(move |word| word.starts_with(&prefix))
+}
+
+/// The word lists used by a game.
+#[derive(Clone, Default)]
+pub enum WordLists {
+    /// The built-in lists, where the answers come from [`GOOD_WORDS`] and the accepted guesses
+    /// are [`VALID_WORDS`].
+    #[default]
+    Builtin,
+
+    /// Custom lists.
+    Custom(Arc<dyn WordList + Send + Sync>),
+}
+
+impl fmt::Debug for WordLists {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Builtin => write!(f, "Builtin"),
+            Self::Custom(_) => write!(f, "Custom(<list>)"),
+        }
+    }
+}
+
+/// Custom lists are only equal if they share the same list.
+impl PartialEq for WordLists {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Builtin, Self::Builtin) => true,
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl WordLists {
+    /// Check that the guess is 5 ASCII characters and accepted by these lists.
+    ///
+    /// The guess doesn't have to be uppercase.
+    ///
+    /// # Errors
+    ///
+    /// If the guess is invalid, we return the appropriate [`GuessError`] variant.
+    pub fn check_guess(&self, guess: &str) -> Result<(), GuessError> {
+        let guess = guess.to_ascii_uppercase();
+
+        if !guess.is_ascii() {
+            return Err(GuessError::IncludesNonAscii);
+        } else if guess.len() != 5 {
+            return Err(GuessError::WrongWordLength);
+        }
+
+        let accepted = match self {
+            Self::Builtin => VALID_WORDS.contains(&guess.as_str()),
+            Self::Custom(list) => list.is_accepted(&guess),
+        };
+        if accepted {
+            Ok(())
+        } else {
+            Err(GuessError::InvalidWord)
+        }
+    }
+
+    /// Get every accepted guess that starts with the given prefix. See [`WordList::complete`].
+    pub fn complete(&self, prefix: &str) -> Vec<String> {
+        match self {
+            Self::Builtin => valid_words::complete(prefix).map(str::to_string).collect(),
+            Self::Custom(list) => list.complete(prefix),
+        }
+    }
+
+    /// Get the hash of these lists. See [`WordList::dictionary_version`].
+    pub fn dictionary_version(&self) -> u64 {
+        match self {
+            Self::Builtin => valid_words::dictionary_version(),
+            Self::Custom(list) => list.dictionary_version(),
+        }
+    }
+}
+
+/// The built-in lists are serialized as `"builtin"`. Custom lists can't be serialized, since they
+/// couldn't be deserialized again.
+#[cfg(feature = "serde")]
+impl serde::Serialize for WordLists {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Builtin => serializer.serialize_str("builtin"),
+            Self::Custom(_) => Err(serde::ser::Error::custom(
+                "custom word lists can't be serialized",
+            )),
+        }
+    }
+}
+
+/// Only `"builtin"` can be deserialized.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for WordLists {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        match name.as_str() {
+            "builtin" => Ok(Self::Builtin),
+            _ => Err(serde::de::Error::custom(format!(
+                "unknown word lists: {name:?}"
+            ))),
+        }
+    }
+}
+
+/// An enum representing possible errors from creating a [`CustomWordList`].
+#[derive(Debug, Error, PartialEq)]
+pub enum WordListError {
+    /// A word list needs at least one answer.
+    #[error("A word list needs at least one answer")]
+    NoAnswers,
+
+    /// One of the words isn't 5 ASCII letters.
+    #[error("{0:?} can't be in a word list: {1}")]
+    InvalidWord(String, #[source] GuessError),
+}
+
+/// A [`WordList`] made from lists of words given at runtime.
+///
+/// Every answer is also accepted as a guess.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomWordList {
+    /// The answers, in uppercase and in the order they were given.
+    answers: Vec<String>,
+
+    /// The accepted guesses, including the answers, in uppercase and sorted, with no duplicates.
+    guesses: Vec<String>,
+}
+
+impl CustomWordList {
+    /// Create a word list with the given answers and extra accepted guesses.
+    ///
+    /// The words can be in any case. Unlike [`AnswerPool`](super::pools::AnswerPool)s, the words
+    /// don't have to be in [`VALID_WORDS`].
+    ///
+    /// # Errors
+    ///
+    /// If there are no answers, we return [`WordListError::NoAnswers`]. If any word isn't
+    /// exactly 5 ASCII letters, we return [`WordListError::InvalidWord`].
+    pub fn new(answers: &[&str], guesses: &[&str]) -> Result<Self, WordListError> {
+        if answers.is_empty() {
+            return Err(WordListError::NoAnswers);
+        }
+
+        let check = |&word: &&str| {
+            let upper = word.to_ascii_uppercase();
+            let error = if !upper.is_ascii() {
+                Some(GuessError::IncludesNonAscii)
+            } else if upper.len() != 5 {
+                Some(GuessError::WrongWordLength)
+            } else if !upper.chars().all(|c| ALPHABET.contains(&c)) {
+                Some(GuessError::InvalidWord)
+            } else {
+                None
+            };
+
+            match error {
+                Some(error) => Err(WordListError::InvalidWord(word.to_string(), error)),
+                None => Ok(upper),
+            }
+        };
+
+        let answers: Vec<String> = answers.iter().map(check).collect::<Result<_, _>>()?;
+        let mut guesses: Vec<String> = guesses.iter().map(check).collect::<Result<_, _>>()?;
+        guesses.extend(answers.iter().cloned());
+        guesses.sort();
+        guesses.dedup();
+
+        Ok(Self { answers, guesses })
+    }
+
+    /// Create a word list with the built-in answers from [`GOOD_WORDS`], which only accepts
+    /// guesses that are answers.
+    ///
+    /// This is for restricted vocabularies, where obscure words from [`VALID_WORDS`] shouldn't be
+    /// allowed as guesses.
+    pub fn answers_only() -> Self {
+        let answers: Vec<String> = GOOD_WORDS.iter().map(|word| word.to_string()).collect();
+        let mut guesses = answers.clone();
+        guesses.sort();
+        guesses.dedup();
+
+        Self { answers, guesses }
+    }
+}
+
+impl WordList for CustomWordList {
+    fn answers(&self) -> &[String] {
+        &self.answers
+    }
+
+    fn is_accepted(&self, guess: &str) -> bool {
+        self.guesses
+            .binary_search_by(|word| word.as_str().cmp(guess))
+            .is_ok()
+    }
+
+    fn complete(&self, prefix: &str) -> Vec<String> {
+        complete_sorted(&self.guesses, prefix)
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// This is hashed in the same way as [`valid_words::dictionary_version`], so a list with the
+    /// same words as the built-in lists has the same version.
+    fn dictionary_version(&self) -> u64 {
+        valid_words::hash_word_lists(
+            self.guesses.iter().map(String::as_str),
+            self.answers.iter().map(String::as_str),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Game;
+
+    #[test]
+    fn custom_word_list() {
+        let list = CustomWordList::new(&["frost", "Sleet"], &["SNOWY", "frost"]).unwrap();
+        assert_eq!(list.answers(), ["FROST", "SLEET"]);
+        assert!(list.is_accepted("SNOWY"));
+        assert!(list.is_accepted("SLEET"));
+        assert!(!list.is_accepted("CRANE"));
+        assert_ne!(list.dictionary_version(), valid_words::dictionary_version());
+
+        let lists = WordLists::Custom(Arc::new(list));
+        assert_eq!(lists.check_guess("snowy"), Ok(()));
+        assert_eq!(lists.check_guess("CRANE"), Err(GuessError::InvalidWord));
+        assert_eq!(lists.check_guess("SNOW"), Err(GuessError::WrongWordLength));
+        assert_eq!(WordLists::Builtin.check_guess("CRANE"), Ok(()));
+
+        assert_eq!(CustomWordList::new(&[], &[]), Err(WordListError::NoAnswers));
+        assert_eq!(
+            CustomWordList::new(&["FROST"], &["SN0WY"]),
+            Err(WordListError::InvalidWord(
+                "SN0WY".to_string(),
+                GuessError::InvalidWord
+            ))
+        );
+        assert_eq!(
+            CustomWordList::new(&["FRÖST"], &[]),
+            Err(WordListError::InvalidWord(
+                "FRÖST".to_string(),
+                GuessError::IncludesNonAscii
+            ))
+        );
+    }
+
+    #[test]
+    fn games_use_their_word_list() {
+        let list = CustomWordList::new(&["FROST"], &["SNOWY"]).unwrap();
+        let version = list.dictionary_version();
+        let mut game = Game::with_word_list(list);
+
+        assert_eq!(game.word.reveal_for_display(), "FROST");
+        assert_eq!(game.validate_guess("CRANE"), Err(GuessError::InvalidWord));
+        assert!(game.make_guess("SNOWY").is_ok());
+        assert!(game.make_guess("FROST").is_ok());
+
+        let rules = game.rules();
+        assert_eq!(rules.dictionary_version, version);
+        assert!(!rules.is_supported());
+        assert!(Game::new().rules().is_supported());
+    }
+
+    #[test]
+    fn lists_complete_guesses() {
+        let list = CustomWordList::new(&["FROST", "FRESH"], &["snowy", "frost", "FRAME"]).unwrap();
+        assert_eq!(list.complete("fr"), ["FRAME", "FRESH", "FROST"]);
+        assert_eq!(list.complete("S"), ["SNOWY"]);
+        assert_eq!(list.complete("FROSTY"), Vec::<String>::new());
+        assert_eq!(list.complete("").len(), 4);
+
+        /// A list that only knows its answers, so it uses the default completion.
+        struct Answers(Vec<String>);
+        impl WordList for Answers {
+            fn answers(&self) -> &[String] {
+                &self.0
+            }
+
+            fn is_accepted(&self, _guess: &str) -> bool {
+                true
+            }
+
+            fn dictionary_version(&self) -> u64 {
+                0
+            }
+        }
+
+        let answers = Answers(
+            ["SLEET", "SNOWY", "FROST", "SNOWY"]
+                .map(String::from)
+                .to_vec(),
+        );
+        assert_eq!(answers.complete("s"), ["SLEET", "SNOWY"]);
+        assert_eq!(answers.complete("").len(), 3);
+
+        let lists = WordLists::Custom(Arc::new(list));
+        assert_eq!(lists.complete("FRO"), ["FROST"]);
+        assert_eq!(
+            WordLists::Builtin.complete("abac"),
+            ["ABACA", "ABACI", "ABACK", "ABACS"]
+        );
+    }
+
+    #[test]
+    fn answers_only_restricts_guesses() {
+        let list = CustomWordList::answers_only();
+        assert_eq!(list.answers().len(), GOOD_WORDS.len());
+        assert!(list.is_accepted("CRANE"));
+        assert!(!list.is_accepted("ABMHO"));
+    }
+}
@@ -0,0 +1,756 @@
+//! This is a snapshot of the public API of the crate.
+//!
+//! Every public item is named here with its full signature, so removing an item, changing a
+//! signature, or adding an enum variant or struct field that would break downstream code will
+//! stop this file compiling. If a change here is intentional, it's a breaking change and needs a
+//! version bump.
+
+#![allow(clippy::type_complexity)]
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    ops::RangeInclusive,
+    time::Duration,
+};
+use wordle::{
+    analysis::{self, Constraints, Grade, Knowledge, QueryError},
+    analytics::{Analytics, GameFinished, GameStarted, GuessMade, NoAnalytics},
+    board::{BoardState, CursorError},
+    coop::{CoopError, CoopGame, CoopGuess},
+    daily,
+    keyboard::{Keyboard, Layout},
+    letters::{Letter, LetterCount, Position},
+    messages::{English, MessageTable, Messages},
+    normalize::{self, Normalization},
+    phrase::PhraseGame,
+    policy::{GamePolicy, GuessBudget, SuspiciousEntryHook, VOWELS},
+    pools::{AnswerPool, AnswerPools, PoolError},
+    puzzle::PuzzleId,
+    replay::{Recorder, ReplayError, Script, ScriptGuess},
+    rules::{self, Rules, RulesError, ScoringVector},
+    scoring::{self, Explanation, PatternMatrix, ScoringRule, ScoringRules},
+    session::{BoardSummary, SessionLog, SessionStats},
+    solver::{self, BestGuessSearch, Solver},
+    spoiler::SpoilerGuard,
+    theme::{Motion, Rgb, Theme, TileStyle},
+    time_attack::{self, LeaderboardKey},
+    valid_words,
+    validator::GuessValidator,
+    word_list::{CustomWordList, WordList, WordListError, WordLists},
+    AnswerError, Game, GuessError, Word,
+};
+
+#[test]
+fn prelude() {
+    #[allow(unused_imports)]
+    use wordle::prelude::{
+        BoardState, CursorError, English, Game, GamePolicy, GuessBudget, GuessError, Letter,
+        LetterCount, Messages, Position, ScoringRules, Word,
+    };
+}
+
+#[test]
+fn game() {
+    let _: fn() -> Game = Game::new;
+    let _: fn(&str) -> Result<Game, AnswerError> = Game::with_answer;
+    let _: Result<Game, AnswerError> = Game::try_from("CRANE");
+    let _: fn(u64) -> Game = Game::daily;
+    let _: fn(u64, Option<&str>, &AnswerPools) -> Game = Game::daily_with_pools;
+    let _: fn(u64, Option<&str>, &AnswerPools) -> Game = Game::new_with_pools;
+    let _: fn(GamePolicy) -> Game = Game::with_policy;
+    let _: fn(&str) -> Result<(), GuessError> = Game::is_valid_guess;
+    let _: fn(&Game, &str) -> Result<(), GuessError> = Game::validate_guess;
+    let _: fn(&mut Game, &str) -> Result<Word, GuessError> = Game::make_guess;
+    let _: fn(&mut Game, &str, Duration) -> Result<Word, GuessError> = Game::make_timed_guess;
+    let _: fn(&mut Game, &str, &mut GuessBudget) -> Result<Word, GuessError> =
+        Game::make_guess_limited;
+    let _: fn(&Game) -> Rules = Game::rules;
+    let _: fn(&Game) -> Knowledge = Game::knowledge;
+    let _: fn(&Game, bool) -> Option<String> = Game::share_string;
+    let _: fn(&Game, bool, &Theme) -> Option<String> = Game::share_string_with;
+    let _: fn(&Script) -> Result<(Game, Vec<Result<Word, GuessError>>), ReplayError> =
+        Game::replay_script;
+
+    let game = Game::new();
+    let _: &SpoilerGuard<String> = &game.word;
+    let _: &Keyboard = &game.keyboard;
+    let _: &HashMap<char, LetterCount> = &game.letter_counts;
+    let _: &GamePolicy = &game.policy;
+    let _: &ScoringRules = &game.scoring_rules;
+    let _: &WordLists = &game.word_list;
+    let _: &PuzzleId = &game.puzzle;
+    let _: &Vec<Word> = &game.guess_history;
+
+    let _: fn(&GuessError) -> &'static str = |error| match error {
+        GuessError::IncludesNonAscii => "",
+        GuessError::InvalidWord => "",
+        GuessError::WrongWordLength => "",
+        GuessError::TooFast(_interval) => "",
+        GuessError::TooManyAttempts => "",
+        GuessError::BannedOpener => "",
+        GuessError::TooFewVowels(_count) => "",
+        GuessError::RepeatedGuess => "",
+        GuessError::MissingCorrectLetter(_letter, _position) => "",
+        GuessError::MissingLetter(_letter) => "",
+    };
+    let _: fn(&AnswerError) -> &'static str = |error| match error {
+        AnswerError::InvalidWord(_word, _error) => "",
+    };
+}
+
+#[test]
+fn keyboard() {
+    let _: fn(&Layout) -> &'static str = |layout| match layout {
+        Layout::Qwerty => "",
+        Layout::Azerty => "",
+    };
+    let _: [Layout; 2] = Layout::ALL;
+    let _: fn(Layout) -> &'static str = Layout::name;
+    let _: fn(&str) -> Option<Layout> = Layout::by_name;
+    let _: fn(Layout) -> [&'static str; 3] = Layout::rows;
+
+    let _: fn() -> Keyboard = Keyboard::new;
+    let _: fn(&[Word]) -> Keyboard = Keyboard::from_history;
+    let _: fn(&Keyboard, char) -> Option<Option<Position>> = Keyboard::get;
+    let _: fn(&Keyboard, char) -> Option<Position> = Keyboard::best_position;
+    let _: fn(&mut Keyboard, &Word) = Keyboard::merge;
+    let keyboard = Keyboard::default();
+    let _: Vec<(char, Option<Position>)> = keyboard.iter().collect();
+    let _: Vec<Vec<(char, Option<Position>)>> = keyboard
+        .iter_rows(Layout::Qwerty)
+        .map(Iterator::collect)
+        .collect();
+}
+
+#[test]
+fn letters() {
+    let _: fn(char, Position) -> Letter = Letter::new;
+    let Letter {
+        letter: _,
+        position: _,
+    } = Letter::new('A', Position::Correct);
+
+    let _: fn(&Position) -> &'static str = |position| match position {
+        Position::NotInWord => "",
+        Position::WrongPosition => "",
+        Position::Correct => "",
+    };
+
+    let _: fn(&Word, char) -> Option<LetterCount> = LetterCount::from_guess;
+    let _: fn(LetterCount, LetterCount) -> LetterCount = LetterCount::merge;
+    let LetterCount {
+        at_least: _,
+        exact: _,
+    }: LetterCount = LetterCount {
+        at_least: 1,
+        exact: false,
+    };
+}
+
+#[test]
+fn analysis() {
+    let _: Vec<&'static str> = analysis::candidates(&[]).collect();
+    let _: fn(&[Word]) -> [f64; 26] = analysis::letter_probabilities;
+    let _: fn(&[Word]) -> Vec<usize> = analysis::candidate_counts;
+
+    let Knowledge {
+        correct: _,
+        excluded: _,
+        required: _,
+    } = Knowledge {
+        correct: [None::<char>; 5],
+        excluded: Default::default(),
+        required: BTreeMap::<char, usize>::new(),
+    };
+    let _: [BTreeSet<char>; 5] = Knowledge::default().excluded;
+    let _: fn(&[Word]) -> Knowledge = Knowledge::from_history;
+    let _: fn(&str) -> Result<Knowledge, QueryError> = Knowledge::parse_query;
+    let _: fn(&Knowledge) -> String = Knowledge::to_regex;
+    let _: fn(&Knowledge) -> String = Knowledge::to_query_string;
+    let _: fn(&Knowledge, &str) -> bool = Knowledge::matches;
+    let _: Vec<&'static str> = Knowledge::default().candidates().collect();
+
+    let _: fn(&[Word]) -> Constraints = Constraints::from_history;
+    let _: fn(&mut Constraints, Word) = Constraints::record;
+    let _: fn(&Constraints) -> &[Word] = Constraints::history;
+    let _: fn(&Constraints, &str) -> bool = Constraints::matches;
+    let _: fn(&Constraints) -> Knowledge = Constraints::knowledge;
+    let _: fn(&Constraints) -> Vec<&'static str> = Constraints::filter_candidates;
+
+    let _: fn(&Constraints, &str) -> Grade = analysis::grade_guess;
+    let _: fn(u8) -> Grade = Grade::from_score;
+    let _: String = Grade::A.to_string();
+    match Grade::A {
+        Grade::A | Grade::B | Grade::C | Grade::D | Grade::F => {}
+    }
+
+    match QueryError::InvalidPattern(String::new()) {
+        QueryError::InvalidPattern(_) | QueryError::InvalidRequired(_) => {}
+    }
+}
+
+#[test]
+fn analytics() {
+    fn assert_analytics<A: Analytics>() {}
+    assert_analytics::<NoAnalytics>();
+
+    let _: fn(&NoAnalytics, &GameStarted) = Analytics::game_started;
+    let _: fn(&NoAnalytics, &GuessMade) = Analytics::guess_made;
+    let _: fn(&NoAnalytics, &GameFinished) = Analytics::game_finished;
+
+    let _ = GameStarted { day: None::<u64> };
+    let _: fn(&Game) -> GameStarted = GameStarted::from_game;
+    let _: fn(usize, &Word) -> GuessMade = GuessMade::from_word;
+    let _: fn(&Game) -> Option<GuessMade> = GuessMade::from_game;
+    let _: fn(&Game) -> Option<GameFinished> = GameFinished::from_game;
+    let GuessMade {
+        guess_number: _,
+        pattern: _,
+    } = GuessMade {
+        guess_number: 1_usize,
+        pattern: [Position::Correct; 5],
+    };
+    let _ = GameFinished {
+        won: true,
+        guesses: 1_usize,
+    };
+    let _: fn(&[Word]) -> BoardSummary = BoardSummary::from_history;
+}
+
+#[test]
+fn board() {
+    let _: fn(usize, usize) -> BoardState = BoardState::new;
+    let _: fn(&BoardState) -> usize = BoardState::current_row;
+    let _: fn(&BoardState) -> usize = BoardState::current_column;
+    let _: fn(&BoardState) -> &[char] = BoardState::current_guess;
+    let _: fn(&BoardState) -> bool = BoardState::is_row_full;
+    let _: fn(&BoardState) -> bool = BoardState::is_board_full;
+    let _: fn(&mut BoardState, char) -> Result<(), CursorError> = BoardState::push;
+    let _: fn(&BoardState) -> bool = BoardState::is_locked;
+    let _: fn(&mut BoardState) = BoardState::lock;
+    let _: fn(&mut BoardState) = BoardState::unlock;
+    let _: fn(&mut BoardState) -> Option<char> = BoardState::pop;
+    let _: fn(&mut BoardState) -> bool = BoardState::clear;
+    let _: fn(&BoardState) -> Result<String, CursorError> = BoardState::guess;
+    let _: fn(&mut BoardState) -> Result<(), CursorError> = BoardState::next_row;
+    let _ = BoardState::default();
+
+    let _: fn(&CursorError) -> &'static str = |error| match error {
+        CursorError::InvalidCharacter => "",
+        CursorError::RowFull => "",
+        CursorError::RowIncomplete => "",
+        CursorError::NoRowsLeft => "",
+        CursorError::Locked => "",
+    };
+}
+
+#[test]
+fn coop() {
+    let _: fn(Game, Vec<String>) -> Result<CoopGame, CoopError> = CoopGame::new;
+    let _: fn(&CoopGame) -> &Game = CoopGame::game;
+    let _: fn(&CoopGame) -> &[String] = CoopGame::players;
+    let _: fn(&CoopGame) -> &str = CoopGame::current_player;
+    let _: fn(&CoopGame) -> &[CoopGuess] = CoopGame::history;
+    let _: fn(&mut CoopGame, &str, &str) -> Result<Word, CoopError> = CoopGame::make_guess;
+
+    let _: fn(&CoopGame, bool) -> Option<String> = CoopGame::share_string;
+    let _: fn(&CoopGame, bool, &Theme) -> Option<String> = CoopGame::share_string_with;
+
+    let game = CoopGame::new(Game::new(), vec!["A".to_string()]).unwrap();
+    let _: Vec<&CoopGuess> = game.guesses_by("A").collect();
+
+    let _: fn(&CoopGuess) -> (&String, &Word) = |CoopGuess { player, word }| (player, word);
+    let _: fn(&CoopError) -> &'static str = |error| match error {
+        CoopError::NoPlayers => "",
+        CoopError::UnknownPlayer(_player) => "",
+        CoopError::NotYourTurn(_player) => "",
+        CoopError::Guess(_error) => "",
+    };
+}
+
+#[test]
+fn daily() {
+    let _: [(u64, u64); 5] = daily::HASH_TEST_VECTORS;
+    let _: [(u64, &str); 5] = daily::DAILY_WORD_TEST_VECTORS;
+    let _: fn(u64) -> u64 = daily::splitmix64;
+    let _: fn(u64) -> u64 = daily::epoch_day;
+    let _: fn(u64) -> usize = daily::daily_word_index;
+    let _: fn(u64) -> &'static str = daily::daily_word;
+}
+
+#[test]
+fn messages() {
+    fn assert_messages<M: Messages>() {}
+    assert_messages::<English>();
+    assert_messages::<MessageTable>();
+
+    let _: fn(&English) -> String = Messages::welcome;
+    let _: fn(&English, &str) -> String = Messages::win;
+    let _: fn(&English) -> String = Messages::out_of_guesses;
+    let _: fn(&English, &str) -> String = Messages::loss;
+    let _: fn(&English, &GuessError) -> String = Messages::guess_error;
+    let _: fn(&English, char, Position) -> String = Messages::describe_letter;
+    let _: fn(&English, Position, &str) -> String = Messages::describe_keys;
+    let _: fn(&English, &Explanation) -> String = Messages::explain;
+    let _: fn(&English) -> String = Messages::language;
+    let _: fn(&English) -> String = Messages::show_answer;
+    let _: fn(&English, &str) -> String = Messages::answer_was;
+    let _: fn(&str) -> MessageTable = MessageTable::parse;
+}
+
+#[test]
+fn normalize() {
+    let Normalization {
+        strip_apostrophes: _,
+        strip_diacritics: _,
+    } = Normalization::default();
+    let _: fn(char, &Normalization) -> Option<char> = normalize::normalize_letter;
+    let _: fn(&str) -> Result<String, GuessError> = normalize::normalize_guess;
+    let _: fn(&str, &Normalization) -> Result<String, GuessError> = normalize::normalize_guess_with;
+}
+
+#[test]
+fn phrase() {
+    let _: fn([Game; 2]) -> PhraseGame = PhraseGame::new;
+    let _: fn(&PhraseGame) -> &[Game; 2] = PhraseGame::segments;
+    let _: fn(&PhraseGame) -> SpoilerGuard<String> = PhraseGame::answer;
+    let _: fn(&mut PhraseGame, &str) -> Result<[Word; 2], GuessError> = PhraseGame::make_guess;
+    let _ = PhraseGame::default();
+}
+
+#[test]
+fn policy() {
+    let _: [char; 5] = VOWELS;
+    let _: Option<SuspiciousEntryHook> = Some(std::sync::Arc::new(|_: &str, _: Duration| {}));
+
+    let GamePolicy {
+        min_guess_interval: _,
+        suspicious_entry_threshold: _,
+        on_suspicious_entry: _,
+        banned_openers: _,
+        min_opener_vowels: _,
+        hard_mode: _,
+        reject_repeated_guesses: _,
+    } = GamePolicy {
+        min_guess_interval: None::<Duration>,
+        suspicious_entry_threshold: None::<Duration>,
+        on_suspicious_entry: None::<SuspiciousEntryHook>,
+        banned_openers: HashSet::<String>::new(),
+        min_opener_vowels: None::<usize>,
+        hard_mode: false,
+        reject_repeated_guesses: false,
+    };
+    let _: fn(&GamePolicy, Duration) -> bool = GamePolicy::is_suspicious_entry;
+    let _: fn(&GamePolicy, &str) -> Result<(), GuessError> = GamePolicy::check_opener;
+    let _: fn(&GamePolicy, &str, &[Word]) -> Result<(), GuessError> = GamePolicy::check_history;
+
+    let _: fn(u32, Duration) -> GuessBudget = GuessBudget::new;
+    let _: fn(u32) -> GuessBudget = GuessBudget::per_minute;
+    let _: fn(&mut GuessBudget) -> bool = GuessBudget::try_take;
+    let _: fn(&mut GuessBudget) -> u32 = GuessBudget::remaining;
+}
+
+#[test]
+fn pools() {
+    let _: fn(&str, &[&str], RangeInclusive<u64>) -> Result<AnswerPool, PoolError> =
+        AnswerPool::new;
+    let _: fn(AnswerPool, &[&str]) -> AnswerPool = AnswerPool::in_regions;
+    let _: fn(&AnswerPool) -> &str = AnswerPool::name;
+    let _: fn(&AnswerPool) -> &[String] = AnswerPool::words;
+    let _: fn(&AnswerPool, u64, Option<&str>) -> bool = AnswerPool::is_active;
+
+    let _: fn() -> AnswerPools = AnswerPools::new;
+    let _: fn(&mut AnswerPools, AnswerPool) = AnswerPools::register;
+    let _: for<'a> fn(&'a AnswerPools, u64, Option<&str>) -> Option<&'a AnswerPool> =
+        AnswerPools::active;
+    let _: for<'a> fn(&'a AnswerPools, u64, Option<&str>) -> &'a str = AnswerPools::daily_word;
+    let _: for<'a> fn(&'a AnswerPools, u64, Option<&str>) -> &'a str = AnswerPools::random_word;
+
+    let _: fn(&PoolError) -> &'static str = |error| match error {
+        PoolError::Empty => "",
+        PoolError::InvalidWord(_word, _error) => "",
+    };
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde() {
+    fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+
+    assert_serde::<Game>();
+    assert_serde::<Word>();
+    assert_serde::<Letter>();
+    assert_serde::<Position>();
+    assert_serde::<LetterCount>();
+    assert_serde::<GamePolicy>();
+    assert_serde::<ScoringRules>();
+    assert_serde::<PuzzleId>();
+    assert_serde::<SpoilerGuard<String>>();
+    assert_serde::<BoardState>();
+    assert_serde::<Script>();
+    assert_serde::<ScriptGuess>();
+    assert_serde::<Normalization>();
+    assert_serde::<Rules>();
+    assert_serde::<BoardSummary>();
+    assert_serde::<SessionStats>();
+    assert_serde::<SessionLog>();
+    assert_serde::<Knowledge>();
+    assert_serde::<Keyboard>();
+    assert_serde::<Layout>();
+    assert_serde::<Constraints>();
+    assert_serde::<Motion>();
+    assert_serde::<CoopGuess>();
+    assert_serde::<CoopGame>();
+    assert_serde::<PhraseGame>();
+    assert_serde::<WordLists>();
+}
+
+#[test]
+fn spoiler() {
+    let _: fn(String) -> SpoilerGuard<String> = SpoilerGuard::new;
+    let _: fn(&SpoilerGuard<String>) -> &String = SpoilerGuard::reveal_for_display;
+    let _: fn(SpoilerGuard<String>) -> String = SpoilerGuard::into_revealed;
+    let _: SpoilerGuard<String> = String::new().into();
+}
+
+#[test]
+fn puzzle() {
+    let _: fn(&str) -> Option<PuzzleId> = PuzzleId::parse;
+    let _: fn(&PuzzleId) -> bool = PuzzleId::is_identified;
+    let _: fn() -> PuzzleId = PuzzleId::default;
+
+    let _: fn(&PuzzleId) -> &'static str = |id| match id {
+        PuzzleId::Daily(_day) => "",
+        PuzzleId::Custom => "",
+    };
+    let _ = PuzzleId::Daily(0_u64);
+}
+
+#[test]
+fn replay() {
+    let _: fn(&Game) -> Script = Script::for_game;
+    let _: fn(&str) -> Result<Script, ReplayError> = Script::parse;
+    let _: fn(&Script) -> Result<Game, ReplayError> = Script::new_game;
+
+    let Script {
+        answer: _,
+        puzzle: _,
+        rules: _,
+        normalization: _,
+        guesses: _,
+    }: Script = Script::for_game(&Game::new());
+    let ScriptGuess {
+        millis: _,
+        guess: _,
+    }: ScriptGuess = ScriptGuess {
+        millis: 0_u128,
+        guess: String::new(),
+    };
+
+    let _: fn(&Game) -> Recorder = Recorder::new;
+    let _: fn(&Game, Normalization) -> Recorder = Recorder::with_normalization;
+    let _: fn(&mut Recorder, &str) = Recorder::record_guess;
+    let _: fn(&Recorder) -> &Script = Recorder::script;
+
+    let _: fn(&ReplayError) -> &'static str = |error| match error {
+        ReplayError::MissingAnswer => "",
+        ReplayError::MissingRules => "",
+        ReplayError::UnsupportedRules(_rules) => "",
+        ReplayError::InvalidRules(_line, _error) => "",
+        ReplayError::InvalidPuzzle(_line, _text) => "",
+        ReplayError::InvalidLine(_line, _text) => "",
+    };
+}
+
+#[test]
+fn rules() {
+    let _: fn(&Game) -> Rules = Rules::for_game;
+    let _: fn(&Rules) -> bool = Rules::is_supported;
+    let _: fn(&str) -> Result<Rules, RulesError> = Rules::parse;
+    let _: fn() -> Rules = Rules::default;
+
+    let Rules {
+        word_length: _,
+        max_guesses: _,
+        scoring: _,
+        dictionary_version: _,
+        min_opener_vowels: _,
+        banned_openers: _,
+        hard_mode: _,
+        reject_repeated_guesses: _,
+    }: Rules = Rules::default();
+
+    let _: fn(&RulesError) -> &'static str = |error| match error {
+        RulesError::InvalidRule(_rule) => "",
+        RulesError::MissingRule(_key) => "",
+    };
+
+    let _: u32 = rules::SCORING_VERSION;
+    let _: &str = rules::SCORING_TEST_VECTORS;
+    let ScoringVector {
+        scoring: _,
+        answer: _,
+        guess: _,
+        pattern: _,
+    }: ScoringVector = rules::scoring_vectors().next().unwrap();
+    let _: [Position; 5] = rules::scoring_vectors().next().unwrap().pattern;
+    let _: Result<usize, ScoringVector> =
+        rules::verify_scoring(|_: &str, _: &str, _: &str| None::<[Position; 5]>);
+}
+
+#[cfg(feature = "render")]
+#[test]
+fn render() {
+    use wordle::render::{
+        ansi::{self, AnsiOptions},
+        html::{self, HtmlOptions},
+        svg::{self, SvgOptions},
+        text,
+    };
+
+    let AnsiOptions {
+        colour: _,
+        theme: _,
+    } = AnsiOptions::default();
+    let _: fn() -> AnsiOptions = AnsiOptions::from_env;
+    let _: fn(&Word, &AnsiOptions) -> String = ansi::render_guess;
+    let _: fn(&Keyboard, Layout, &AnsiOptions) -> String = ansi::render_keyboard;
+    let _: fn(&[Word], &AnsiOptions) -> String = ansi::render_board;
+    let _: fn(&[Word], usize, &AnsiOptions) -> String = ansi::render_grid;
+
+    let SvgOptions {
+        theme: _,
+        hide_letters: _,
+    } = SvgOptions::default();
+    let _: fn(&[Word], &SvgOptions) -> String = svg::render_board;
+
+    let HtmlOptions {
+        theme: _,
+        hide_answer: _,
+    } = HtmlOptions::default();
+    let _: fn(&[Word], &str, &HtmlOptions) -> String = html::render_figure;
+    let _: fn(&[Word], &str, &HtmlOptions) -> String = html::render_page;
+    let _: fn(&[Word], &str, &HtmlOptions, &English) -> String = html::render_figure_with;
+    let _: fn(&[Word], &str, &HtmlOptions, &English) -> String = html::render_page_with;
+
+    let _: fn(&Word) -> String = text::describe_guess;
+    let _: fn(&Word, &English) -> String = text::describe_guess_with;
+    let _: fn(&Keyboard) -> String = text::describe_keyboard;
+    let _: fn(&Keyboard, &MessageTable) -> String = text::describe_keyboard_with;
+}
+
+#[test]
+fn scoring() {
+    struct Rule;
+    impl ScoringRule for Rule {
+        fn score(&self, answer: &str, guess: &str) -> Word {
+            scoring::score_guess(answer, guess).unwrap()
+        }
+    }
+
+    let _: fn(&ScoringRules) -> &'static str = |rules| match rules {
+        ScoringRules::Classic => "",
+        ScoringRules::LenientDuplicates => "",
+        ScoringRules::Custom(_rule) => "",
+    };
+    let _ = ScoringRules::Custom(std::sync::Arc::new(Rule));
+    let _: fn(&ScoringRules) -> &'static str = ScoringRules::name;
+    let _: fn(&str) -> Option<ScoringRules> = ScoringRules::from_name;
+
+    let _: fn(&str, &str) -> Result<Word, GuessError> = scoring::score_guess;
+    let _: fn(&str, &str, &ScoringRules) -> Result<Word, GuessError> = scoring::score_guess_with;
+    let _: fn(&str, &[&str]) -> Result<Vec<Word>, GuessError> = scoring::score_many;
+    let _: fn(&[&str], &[&str]) -> Result<PatternMatrix, GuessError> = scoring::score_matrix;
+    let _: fn(&str, &str) -> Result<(Word, Vec<Explanation>), GuessError> =
+        scoring::score_guess_explained;
+    let _: fn(&Explanation, &English) -> String = Explanation::explain_with;
+
+    let _: fn(&PatternMatrix) -> usize = PatternMatrix::answers;
+    let _: fn(&PatternMatrix) -> usize = PatternMatrix::guesses;
+    let _: fn(&PatternMatrix, usize, usize) -> Option<[Position; 5]> = PatternMatrix::pattern;
+    let _: fn(&PatternMatrix, usize, usize) -> Option<u8> = PatternMatrix::pattern_code;
+
+    let _: fn(&Explanation) -> &'static str = |explanation| match *explanation {
+        Explanation::Correct { letter: _ } => "",
+        Explanation::Misplaced {
+            letter: _,
+            in_answer: _,
+        } => "",
+        Explanation::Absent { letter: _ } => "",
+        Explanation::Exhausted {
+            letter: _,
+            in_answer: _,
+            correct: _,
+            earlier_misplaced: _,
+        } => "",
+    };
+}
+
+#[test]
+fn session() {
+    let _ = BoardSummary {
+        won: true,
+        guesses: 1_usize,
+    };
+    let SessionStats {
+        boards: _,
+        wins: _,
+        total_guesses: _,
+        current_streak: _,
+        best_streak: _,
+        distribution: _,
+    } = SessionStats {
+        boards: 0_u64,
+        wins: 0_u64,
+        total_guesses: 0_u64,
+        current_streak: 0_u64,
+        best_streak: 0_u64,
+        distribution: [0_u64; 6],
+    };
+    let _: fn(&mut SessionStats, &BoardSummary) = SessionStats::record;
+    let _: fn(&SessionStats) -> Option<f64> = SessionStats::win_rate;
+
+    let _: usize = SessionLog::DEFAULT_MAX_RECENT;
+    let _: fn(usize) -> SessionLog = SessionLog::new;
+    let _: fn(&mut SessionLog, Word) = SessionLog::record_guess;
+    let _: fn(&SessionLog) -> &[Word] = SessionLog::current_board;
+    let _: fn(&mut SessionLog) -> BoardSummary = SessionLog::finish_board;
+    let _: fn(&SessionLog) -> &SessionStats = SessionLog::stats;
+    let _: fn(&str) -> SessionStats = SessionStats::parse;
+    let _: String = SessionStats::default().to_string();
+    let _: Vec<&BoardSummary> = SessionLog::default().recent().collect();
+}
+
+#[test]
+fn solver() {
+    let _: fn() -> Solver = Solver::new;
+    let _: fn(&[Word]) -> Solver = Solver::from_history;
+    let _: fn(&Constraints) -> Solver = Solver::from_constraints;
+    let _: fn(&mut Solver, &Word) = Solver::record;
+    let _: fn(&Solver) -> &[&'static str] = Solver::remaining_candidates;
+    let _: fn(&Solver, &str) -> f64 = Solver::expected_information;
+    let _: fn(&Solver) -> Option<&'static str> = Solver::best_guess;
+    let _: fn(&Solver, &str) -> u8 = Solver::grade;
+    let _: fn(&Solver, &[Word]) -> Vec<u8> = Solver::grade_history;
+    let _: &str = solver::BEST_OPENER;
+    let _: fn(&Solver, usize) -> Option<&'static str> = Solver::best_guess_within;
+    let _: fn(&Solver) -> BestGuessSearch = Solver::search;
+    let _: fn(&mut BestGuessSearch, usize) -> bool = BestGuessSearch::step;
+    let _: fn(&BestGuessSearch) -> bool = BestGuessSearch::is_finished;
+    let _: fn(&BestGuessSearch) -> f64 = BestGuessSearch::progress;
+    let _: fn(&BestGuessSearch) -> Option<&'static str> = BestGuessSearch::best_so_far;
+    let _: fn(&BestGuessSearch, &str) -> u8 = BestGuessSearch::grade;
+}
+
+#[test]
+fn theme() {
+    let Theme {
+        name: _,
+        css_class: _,
+        background: _,
+        text: _,
+        unguessed_ansi: _,
+        correct: _,
+        wrong_position: _,
+        not_in_word: _,
+    }: Theme = Theme::CLASSIC;
+    let TileStyle {
+        colour: Rgb(_, _, _),
+        emoji: _,
+        css_class: _,
+        ansi: _,
+    }: TileStyle = Theme::DARK.correct;
+    let _: [Theme; 4] = [
+        Theme::CLASSIC,
+        Theme::DARK,
+        Theme::HIGH_CONTRAST,
+        Theme::COLOUR_BLIND,
+    ];
+    let _: [Theme; 4] = Theme::ALL;
+    let _: fn(&str) -> Option<Theme> = Theme::by_name;
+    let _: fn(&Theme, Position) -> &TileStyle = Theme::tile;
+
+    let _: fn(Motion) -> &'static str = |motion| match motion {
+        Motion::System => "",
+        Motion::Full => "",
+        Motion::Reduced => "",
+    };
+    let _: &str = Motion::REDUCED_CSS_CLASS;
+    let _: [Motion; 3] = Motion::ALL;
+    let _: fn(Motion) -> &'static str = Motion::name;
+    let _: fn(&str) -> Option<Motion> = Motion::by_name;
+    let _: fn(Motion) -> Motion = Motion::next;
+    let _: fn(Motion, bool) -> bool = Motion::is_reduced;
+    let _: Motion = Motion::default();
+}
+
+#[test]
+#[allow(deprecated)]
+fn time_attack() {
+    let _: usize = time_attack::MAX_GUESSES;
+    let _: usize = wordle::MAX_GUESSES;
+    let _: [(Option<usize>, u64, u32); 6] = time_attack::TIME_ATTACK_TEST_VECTORS;
+    let _: fn(Option<usize>, Duration) -> u32 = time_attack::time_attack_score;
+    let _: fn(Option<usize>, Duration) -> LeaderboardKey = LeaderboardKey::new;
+    let _: fn(&LeaderboardKey) -> u32 = LeaderboardKey::score;
+}
+
+#[test]
+fn valid_words() {
+    let _: [char; 26] = valid_words::ALPHABET;
+    let _: &'static [&'static str] = valid_words::VALID_WORDS;
+    let _: &'static [&'static str] = valid_words::GOOD_WORDS;
+    let _: Vec<&'static str> = valid_words::complete("AB").collect();
+    let _: fn() -> u64 = valid_words::dictionary_version;
+}
+
+#[test]
+fn validator() {
+    let GuessValidator {
+        word_lists: _,
+        policy: _,
+    } = GuessValidator {
+        word_lists: Vec::<WordLists>::new(),
+        policy: GamePolicy::default(),
+    };
+    let _: fn(&Game) -> GuessValidator = GuessValidator::for_game;
+    let _: fn(&GuessValidator, &str, &Constraints) -> Result<(), GuessError> =
+        GuessValidator::validate;
+}
+
+#[test]
+fn word_list() {
+    struct List;
+    impl WordList for List {
+        fn answers(&self) -> &[String] {
+            &[]
+        }
+
+        fn is_accepted(&self, _guess: &str) -> bool {
+            false
+        }
+
+        fn dictionary_version(&self) -> u64 {
+            0
+        }
+    }
+
+    let _: fn(List) -> Game = Game::with_word_list;
+    let _: fn(WordLists) -> &'static str = |lists| match lists {
+        WordLists::Builtin => "",
+        WordLists::Custom(_list) => "",
+    };
+    let _ = WordLists::Custom(std::sync::Arc::new(List));
+    let _: WordLists = WordLists::default();
+    let _: fn(&WordLists, &str) -> Result<(), GuessError> = WordLists::check_guess;
+    let _: fn(&WordLists) -> u64 = WordLists::dictionary_version;
+    let _: fn(&WordLists, &str) -> Vec<String> = WordLists::complete;
+    let _: Vec<String> = List.complete("AB");
+
+    let _: fn(&[&str], &[&str]) -> Result<CustomWordList, WordListError> = CustomWordList::new;
+    let _: fn() -> CustomWordList = CustomWordList::answers_only;
+    let _: fn(WordListError) -> &'static str = |error| match error {
+        WordListError::NoAnswers => "",
+        WordListError::InvalidWord(_word, _error) => "",
+    };
+}
@@ -0,0 +1,447 @@
+//! These tests feed hostile input through every public function that takes input, to check that
+//! the library returns errors rather than panicking. See the crate documentation.
+
+use std::time::Duration;
+use wordle::{
+    analysis::{self, Constraints, Knowledge},
+    board::BoardState,
+    coop::CoopGame,
+    daily,
+    keyboard::{Keyboard, Layout},
+    letters::{Letter, LetterCount, Position},
+    messages::MessageTable,
+    normalize::{self, Normalization},
+    phrase::PhraseGame,
+    policy::GuessBudget,
+    pools::{AnswerPool, AnswerPools},
+    puzzle::PuzzleId,
+    replay::Script,
+    rules::Rules,
+    scoring::{self, ScoringRules},
+    session::{BoardSummary, SessionStats},
+    solver::Solver,
+    theme::{Motion, Theme},
+    time_attack::{self, LeaderboardKey},
+    valid_words,
+    validator::GuessValidator,
+    word_list::{CustomWordList, WordList, WordLists},
+    Game, Word,
+};
+
+/// Strings that shouldn't be accepted as guesses or answers, but mustn't cause a panic either.
+const HOSTILE_STRINGS: &[&str] = &[
+    "",
+    " ",
+    "     ",
+    "\0\0\0\0\0",
+    "\n\n\n\n\n",
+    "12345",
+    "A1CDE",
+    "1ACDE",
+    "[{<>}",
+    "ÄÖÜSS",
+    "ÉCLAT",
+    "E\u{301}CLAT",
+    "🦀🦀🦀🦀🦀",
+    "ＤＹＳＯＮ",
+    "DYSON\n",
+    " DYSON",
+    "DY SON",
+    "DYSONS",
+    "D",
+    "\u{feff}DYSON",
+    "\u{202e}NOSYD",
+    "guess 0 DYSON",
+    "answer",
+    "rules = {word}",
+];
+
+/// Every hostile string, plus some very long ones.
+fn hostile_strings() -> Vec<String> {
+    let mut strings: Vec<String> = HOSTILE_STRINGS.iter().map(|s| s.to_string()).collect();
+    strings.push("A".repeat(100_000));
+    strings.push("é".repeat(100_000));
+    strings.push("DYSON ".repeat(10_000));
+    strings
+}
+
+/// Words with letters that no valid guess could contain.
+fn hostile_words() -> Vec<Word> {
+    ['\0', '1', '<', 'é', '🦀', 'a', '\u{202e}']
+        .into_iter()
+        .flat_map(|c| {
+            [
+                [Letter::new(c, Position::Correct); 5],
+                [Letter::new(c, Position::WrongPosition); 5],
+                [Letter::new(c, Position::NotInWord); 5],
+            ]
+        })
+        .collect()
+}
+
+#[test]
+fn games() {
+    let rules = [ScoringRules::Classic, ScoringRules::LenientDuplicates];
+
+    for input in hostile_strings() {
+        let _ = Game::is_valid_guess(&input);
+        assert!(Game::with_answer(&input).is_err());
+        assert!(Game::try_from(input.as_str()).is_err());
+
+        for scoring_rules in rules.clone() {
+            let mut game = Game::new();
+            game.word = "DYSON".to_string().into();
+            game.scoring_rules = scoring_rules;
+            game.policy.min_opener_vowels = Some(usize::MAX);
+            game.policy.banned_openers.insert(input.clone());
+            game.policy.hard_mode = true;
+            game.policy.reject_repeated_guesses = true;
+
+            let _ = game.validate_guess(&input);
+            let before = Constraints::from_history(&hostile_words());
+            let _ = GuessValidator::for_game(&game).validate(&input, &before);
+            assert!(game.make_guess(&input).is_err());
+            assert!(game.make_timed_guess(&input, Duration::ZERO).is_err());
+            assert!(game.make_timed_guess(&input, Duration::MAX).is_err());
+            assert!(game
+                .make_guess_limited(&input, &mut GuessBudget::new(0, Duration::ZERO))
+                .is_err());
+            assert!(game
+                .make_guess_limited(&input, &mut GuessBudget::per_minute(u32::MAX))
+                .is_err());
+
+            // A hostile answer
+            game.word = input.clone().into();
+            game.policy = Default::default();
+            let _ = game.make_guess("DYSON");
+            let _ = game.make_guess(&input);
+            let _ = game.share_string(false);
+        }
+    }
+
+    let _ = Game::daily(u64::MAX).share_string(true);
+}
+
+#[test]
+fn scoring() {
+    let strings = hostile_strings();
+    let all: Vec<&str> = strings
+        .iter()
+        .map(String::as_str)
+        .chain(["DYSON"])
+        .collect();
+
+    for answer in &all {
+        for guess in &all {
+            let _ = scoring::score_guess(answer, guess);
+            let _ = scoring::score_guess_with(answer, guess, &ScoringRules::LenientDuplicates);
+            let _ = scoring::score_guess_explained(answer, guess);
+        }
+        let _ = scoring::score_many(answer, &all);
+    }
+
+    // Characters that aren't letters are still scored if they're ASCII
+    let word = scoring::score_guess("1ACDE", "A1CDE").unwrap();
+    assert_eq!(word[0].position, Position::WrongPosition);
+    assert_eq!(word[1].position, Position::WrongPosition);
+    assert_eq!(
+        scoring::score_many("1ACDE", &["A1CDE"]).unwrap(),
+        vec![word]
+    );
+
+    let _ = scoring::score_matrix(&all, &all);
+    let matrix = scoring::score_matrix(&["DYSON", "[{<>}"], &["12345"]).unwrap();
+    for index in [0, 1, 2, usize::MAX] {
+        let _ = matrix.pattern(index, index);
+        let _ = matrix.pattern_code(usize::MAX, index);
+    }
+    assert_eq!(matrix.pattern(2, 0), None);
+}
+
+#[test]
+fn analysis_and_rendering() {
+    let words = hostile_words();
+
+    let _ = analysis::candidates(&words).count();
+    let _ = analysis::letter_probabilities(&words);
+    let _ = analysis::candidate_counts(&words);
+    let mut constraints = Constraints::from_history(&words);
+    constraints.record(words[0]);
+    let _ = constraints.filter_candidates();
+    for input in hostile_strings() {
+        let _ = analysis::grade_guess(&constraints, &input);
+    }
+    let _ = BoardSummary::from_history(&words);
+
+    let mut solver = Solver::from_history(&words);
+    let _ = solver.best_guess();
+    let _ = solver.best_guess_within(usize::MAX);
+    let mut search = solver.search();
+    while !search.step(usize::MAX) {}
+    for input in hostile_strings() {
+        let _ = search.grade(&input);
+    }
+    for word in &words {
+        solver.record(word);
+    }
+    assert!(solver.remaining_candidates().is_empty());
+    for input in hostile_strings() {
+        let _ = Solver::new().expected_information(&input);
+    }
+    let knowledge = Knowledge::from_history(&words);
+    let _ = knowledge.to_query_string();
+    let _ = knowledge.candidates().count();
+
+    for word in &words {
+        for c in ['\0', 'é', '🦀'] {
+            let _ = LetterCount::from_guess(word, c);
+        }
+    }
+
+    let mut keyboard = Keyboard::from_history(&words);
+    for word in &words {
+        keyboard.merge(word);
+        for letter in word {
+            let _ = keyboard.get(letter.letter);
+            let _ = keyboard.best_position(letter.letter);
+        }
+    }
+
+    #[cfg(feature = "render")]
+    {
+        use wordle::render::{ansi, html, svg, text};
+
+        for options in [
+            ansi::AnsiOptions::default(),
+            ansi::AnsiOptions {
+                colour: false,
+                theme: Theme::DARK,
+            },
+        ] {
+            let _ = ansi::render_board(&words, &options);
+            let _ = ansi::render_grid(&words, 100, &options);
+            for layout in Layout::ALL {
+                let _ = ansi::render_keyboard(&keyboard, layout, &options);
+            }
+        }
+
+        let _ = svg::render_board(&words, &svg::SvgOptions::default());
+        for hide_answer in [false, true] {
+            let options = html::HtmlOptions {
+                theme: Theme::DARK,
+                hide_answer,
+            };
+            for answer in HOSTILE_STRINGS {
+                let page = html::render_page(&words, answer, &options);
+                assert!(!page.contains("<>"));
+            }
+        }
+        for word in &words {
+            let _ = text::describe_guess(word);
+        }
+        let _ = text::describe_keyboard(&keyboard);
+        let _ = text::describe_keyboard_with(&keyboard, &MessageTable::parse("keys-correct = {"));
+    }
+}
+
+#[test]
+fn text_input() {
+    let everything = Normalization {
+        strip_apostrophes: true,
+        strip_diacritics: true,
+    };
+
+    for input in hostile_strings() {
+        let _ = normalize::normalize_guess(&input);
+        let _ = normalize::normalize_guess_with(&input, &everything);
+        for c in input.chars().take(100) {
+            let _ = normalize::normalize_letter(c, &everything);
+        }
+
+        let _ = valid_words::complete(&input).count();
+        let _ = Theme::by_name(&input);
+        let _ = Motion::by_name(&input);
+        let _ = Layout::by_name(&input);
+        let _ = PuzzleId::parse(&input);
+        let _ = Knowledge::parse_query(&input);
+        let _ = Knowledge::parse_query(&format!("..... +{input}"));
+        let _ = Knowledge::default().matches(&input);
+        let _ = Constraints::from_history(&hostile_words()).matches(&input);
+        let _ = MessageTable::parse(&input);
+
+        if let Ok(script) = Script::parse(&input) {
+            let _ = Game::replay_script(&script);
+        }
+        let rules = Rules::default();
+        let _ = Script::parse(&format!("answer {input}\nrules {rules}\nguess 1 {input}"))
+            .map(|script| Game::replay_script(&script));
+        let _ = Script::parse(&format!("answer DYSON\npuzzle {input}\nrules {rules}"))
+            .map(|script| Game::replay_script(&script));
+        let _ = Script::parse(&format!("answer DYSON\nrules {input}"))
+            .map(|script| Game::replay_script(&script));
+        let _ = Script::parse(&format!("answer DYSON\nrules {rules} scoring={input}"))
+            .map(|script| Game::replay_script(&script));
+        let _ = Rules::parse(&format!("max-guesses={input} banned-openers={input}"));
+
+        let mut phrase = PhraseGame::default();
+        assert!(phrase.make_guess(&input).is_err());
+
+        let mut coop = CoopGame::new(Game::new(), vec![input.clone(), "B".to_string()]).unwrap();
+        let _ = coop.make_guess(&input, &input);
+        let _ = coop.make_guess("B", &input);
+        let _ = coop.current_player();
+    }
+
+    assert!(CoopGame::new(Game::new(), Vec::new()).is_err());
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn saved_coop_games() {
+    let mut coop = CoopGame::new(Game::new(), vec!["A".to_string(), "B".to_string()]).unwrap();
+    coop.make_guess("A", "CRANE").unwrap();
+    let json = serde_json::to_value(&coop).unwrap();
+    assert_eq!(
+        serde_json::from_value::<CoopGame>(json.clone()).unwrap(),
+        coop
+    );
+
+    let malformed = [
+        ("players", serde_json::json!([])),
+        ("current_player", serde_json::json!(2)),
+        ("current_player", serde_json::json!(usize::MAX)),
+        ("history", serde_json::json!([])),
+    ];
+    for (field, value) in malformed {
+        let mut json = json.clone();
+        json[field] = value;
+        assert!(serde_json::from_value::<CoopGame>(json).is_err());
+    }
+
+    let mut unknown_player = json.clone();
+    unknown_player["history"][0]["player"] = serde_json::json!("C");
+    assert!(serde_json::from_value::<CoopGame>(unknown_player).is_err());
+
+    let mut changed_word = json.clone();
+    changed_word["history"][0]["word"][0]["letter"] = serde_json::json!("X");
+    assert!(serde_json::from_value::<CoopGame>(changed_word).is_err());
+}
+
+#[test]
+fn board() {
+    for (rows, row_length) in [(0, 0), (0, 5), (6, 0), (1, 1)] {
+        let mut board = BoardState::new(rows, row_length);
+
+        for c in ['\0', 'é', '🦀', 'a', 'Z', '1'] {
+            let _ = board.push(c);
+            let _ = board.guess();
+            let _ = board.next_row();
+        }
+        let _ = board.pop();
+        let _ = board.pop();
+        let _ = board.clear();
+        let _ = board.next_row();
+        let _ = board.is_board_full();
+    }
+}
+
+#[test]
+fn numbers() {
+    for day in [0, 1, u64::MAX / 2, u64::MAX] {
+        let _ = daily::daily_word(day);
+        let _ = daily::daily_word_index(day);
+        let _ = daily::epoch_day(day);
+    }
+
+    for guesses in [None, Some(0), Some(7), Some(usize::MAX)] {
+        for elapsed in [Duration::ZERO, Duration::MAX] {
+            let _ = time_attack::time_attack_score(guesses, elapsed);
+            let _ = LeaderboardKey::new(guesses, elapsed).score();
+        }
+    }
+
+    for (capacity, refill) in [
+        (0, Duration::ZERO),
+        (u32::MAX, Duration::ZERO),
+        (u32::MAX, Duration::MAX),
+    ] {
+        let mut budget = GuessBudget::new(capacity, refill);
+        let _ = budget.try_take();
+        let _ = budget.remaining();
+    }
+    let _ = GuessBudget::per_minute(0).try_take();
+
+    let mut stats = SessionStats {
+        boards: u64::MAX,
+        total_guesses: u64::MAX,
+        ..SessionStats::default()
+    };
+    for guesses in [0, 7, usize::MAX] {
+        for won in [false, true] {
+            stats.record(&BoardSummary { won, guesses });
+        }
+    }
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn pools() {
+    for input in hostile_strings() {
+        let _ = AnswerPool::new(&input, &[&input], 0..=u64::MAX);
+    }
+    let _ = AnswerPool::new("Empty", &[], u64::MAX..=0);
+
+    let mut pools = AnswerPools::new();
+    pools.register(
+        AnswerPool::new("Reversed", &["DYSON"], u64::MAX..=0)
+            .unwrap()
+            .in_regions(&["", "🦀"]),
+    );
+    pools.register(AnswerPool::new("Everything", &["CRANE"], 0..=u64::MAX).unwrap());
+
+    for day in [0, u64::MAX] {
+        for region in [None, Some(""), Some("🦀")] {
+            let _ = pools.daily_word(day, region);
+            let _ = pools.random_word(day, region);
+            let _ = Game::daily_with_pools(day, region, &pools);
+            let _ = Game::new_with_pools(day, region, &pools);
+        }
+    }
+}
+
+/// A word list whose answers are hostile strings, and which accepts every guess.
+struct HostileList(Vec<String>);
+
+impl WordList for HostileList {
+    fn answers(&self) -> &[String] {
+        &self.0
+    }
+
+    fn is_accepted(&self, _guess: &str) -> bool {
+        true
+    }
+
+    fn dictionary_version(&self) -> u64 {
+        u64::MAX
+    }
+}
+
+#[test]
+fn word_lists() {
+    for input in hostile_strings() {
+        let _ = CustomWordList::new(&[&input], &[&input]);
+        let _ = CustomWordList::new(&["DYSON"], &[&input]);
+        let _ = WordLists::Builtin.check_guess(&input);
+        let _ = WordLists::Builtin.complete(&input);
+        let _ = HostileList(vec![input.clone()]).complete(&input);
+
+        let mut game = Game::with_word_list(HostileList(vec![input.clone()]));
+        let _ = game.validate_guess(&input);
+        let _ = game.make_guess(&input);
+        let _ = game.make_guess("DYSON");
+        let _ = game.rules();
+    }
+
+    let mut game = Game::with_word_list(HostileList(Vec::new()));
+    let _ = game.make_guess("DYSON");
+}
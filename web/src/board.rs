@@ -1,33 +1,10 @@
-//! This module handles components for the game board itself - the 6 rows of 5 letter words.
+//! This module handles components for the game board itself - one row of 5 letters for each guess.
 
 use gloo_utils::window;
 use js_sys::{Function, Promise};
-use wordle::prelude::*;
+use wordle::{analysis::Grade, prelude::*, theme::Theme};
 use yew::{classes, function_component, html, Html, Properties};
 
-/// Get the inner size of the window, returned as `Option<(width, height)>`.
-fn get_window_size() -> Option<(i32, i32)> {
-    let width = match window().inner_width() {
-        Ok(val) => val.as_f64()? as i32,
-        Err(_) => return None,
-    };
-    let height = match window().inner_height() {
-        Ok(val) => val.as_f64()? as i32,
-        Err(_) => return None,
-    };
-
-    Some((width, height))
-}
-
-#[doc(hidden)]
-fn min(a: i32, b: i32) -> i32 {
-    use std::cmp::Ordering;
-    match a.cmp(&b) {
-        Ordering::Less => a,
-        _ => b,
-    }
-}
-
 /// An enum to represent the state of a [`LetterComp`].
 ///
 /// This is needed because each letter on the board can be blank, a [`Letter`] with a position, or
@@ -60,11 +37,7 @@ struct LetterProps {
 #[function_component(LetterComp)]
 fn letter_comp(props: &LetterProps) -> Html {
     fn position_to_class(letter: Letter) -> &'static str {
-        match letter.position {
-            Position::NotInWord => "notinword",
-            Position::WrongPosition => "wrongposition",
-            Position::Correct => "correct",
-        }
+        Theme::default().tile(letter.position).css_class
     }
 
     match props.letter {
@@ -102,6 +75,22 @@ enum RowPropState {
     Empty,
 }
 
+/// The analysis of a single guess, shown as badges beside its row once the game is over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RowAnalysis {
+    /// The number of possible answers left after this guess. See
+    /// [`candidate_counts`](wordle::analysis::candidate_counts).
+    pub candidates_left: usize,
+
+    /// The solver's grade of this guess out of 100, or [`None`] if it hasn't been graded yet. This
+    /// is shown as a letter [`Grade`]. See [`Solver::grade`](wordle::solver::Solver::grade).
+    pub grade: Option<u8>,
+
+    /// How much of the search for the best guess has been done while grading this guess, in
+    /// percent. See [`BestGuessSearch::progress`](wordle::solver::BestGuessSearch::progress).
+    pub progress: u8,
+}
+
 /// The props for [`RowComp`].
 #[derive(Clone, PartialEq, Properties)]
 struct RowProps {
@@ -110,6 +99,10 @@ struct RowProps {
 
     /// Whether or not this row should shake.
     should_shake: bool,
+
+    /// The analysis of this row's guess, shown as badges on the row.
+    #[prop_or_default]
+    analysis: Option<RowAnalysis>,
 }
 
 /// A component for a single row in the board, with 5 letters.
@@ -135,6 +128,20 @@ fn row_comp(props: &RowProps) -> Html {
             <LetterComp letter={get_letter(2)} delay=500 />
             <LetterComp letter={get_letter(3)} delay=750 />
             <LetterComp letter={get_letter(4)} delay=1000 />
+            if let Some(RowAnalysis { candidates_left, grade, progress }) = props.analysis {
+                if let Some(grade) = grade {
+                    <div class="row-grade" title={format!("The solver grades this guess {grade} out of 100")}>
+                        { Grade::from_score(grade) }
+                    </div>
+                } else {
+                    <div class="row-grade grading" title={format!("The solver is grading this guess ({progress}% done)")}>
+                        { format!("{progress}%") }
+                    </div>
+                }
+                <div class="row-annotation" title={format!("{candidates_left} possible answers left after this guess")}>
+                    {candidates_left}
+                </div>
+            }
         </>
     };
 
@@ -185,6 +192,24 @@ fn row_comp(props: &RowProps) -> Html {
     }
 }
 
+/// The props for [`ExampleRowComp`].
+#[derive(Clone, PartialEq, Properties)]
+pub struct ExampleRowProps {
+    /// The scored word to show in this row.
+    pub word: Word,
+}
+
+/// A component for a single scored row outside of the board.
+///
+/// This is used to show example guesses in the [`HelpModal`](super::help::HelpModal), and
+/// renders exactly like a row on the real board.
+#[function_component(ExampleRowComp)]
+pub fn example_row_comp(props: &ExampleRowProps) -> Html {
+    html! {
+        <RowComp state={RowPropState::Concrete(props.word)} should_shake={false} />
+    }
+}
+
 /// The props for [`BoardComp`].
 #[derive(Clone, PartialEq, Properties)]
 pub struct BoardProps {
@@ -195,28 +220,36 @@ pub struct BoardProps {
     ///
     /// This guess is managed by the [`Model`](super::Model) component, which acts as a bridge
     /// between this board and the [`KeyboardComp`](super::keyboard::KeyboardComp).
-    pub current_guess: Option<Vec<char>>,
+    pub current_guess: Vec<char>,
 
     /// Whether the user has hit enter on a bad guess.
     ///
     /// This prop is used to make the row shake.
     pub bad_guess: bool,
+
+    /// The analysis of each previous guess, if it should be shown.
+    #[prop_or_default]
+    pub analysis: Option<Vec<RowAnalysis>>,
 }
 
-/// A component to represent the whole board with all 6 rows.
+/// A component to represent the whole board, with a row for each of the [`MAX_GUESSES`].
 ///
-/// The HTML is just 6 [`RowComp`]s wrapped in a div.
+/// The HTML is just a [`RowComp`] for each row, wrapped in a div.
 #[function_component(BoardComp)]
 pub fn board_comp(props: &BoardProps) -> Html {
     let get_row = |index: usize| -> Html {
         if let Some(letters) = props.guesses.get(index) {
+            let analysis = props
+                .analysis
+                .as_ref()
+                .and_then(|rows| rows.get(index).copied());
+
             html! {
-                <RowComp state={RowPropState::Concrete(*letters)} should_shake={false} />
+                <RowComp state={RowPropState::Concrete(*letters)} should_shake={false} {analysis} />
             }
         } else if index == props.guesses.len() {
             let should_shake = props.bad_guess;
-            let state =
-                RowPropState::CurrentGuess(props.current_guess.clone().unwrap_or_else(Vec::new));
+            let state = RowPropState::CurrentGuess(props.current_guess.clone());
 
             html! {
                 <RowComp {state} {should_shake} />
@@ -228,23 +261,9 @@ pub fn board_comp(props: &BoardProps) -> Html {
         }
     };
 
-    let style = if let Some((width, height)) = get_window_size() {
-        let height = min(height - 260, 420);
-        let width = min(width, 5 * height / 6);
-        let height = min(height, 6 * width / 5);
-        format!("width: {width}px; height: {height}px;")
-    } else {
-        String::new()
-    };
-
     html! {
-        <div {style} class="board">
-            {get_row(0)}
-            {get_row(1)}
-            {get_row(2)}
-            {get_row(3)}
-            {get_row(4)}
-            {get_row(5)}
+        <div class="board">
+            { for (0..MAX_GUESSES).map(get_row) }
         </div>
     }
 }
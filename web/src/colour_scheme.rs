@@ -0,0 +1,91 @@
+//! This module handles the colour scheme setting, which can be light, dark, or follow the system.
+
+use gloo_utils::{body, window};
+use web_sys::MediaQueryList;
+use wordle::theme::Theme;
+
+/// The media query that matches when the system prefers a dark colour scheme.
+const PREFERS_DARK_QUERY: &str = "(prefers-color-scheme: dark)";
+
+/// The player's choice of colour scheme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColourScheme {
+    /// Always use [`Theme::CLASSIC`].
+    Light,
+
+    /// Always use [`Theme::DARK`].
+    Dark,
+
+    /// Use [`Theme::DARK`] when the system prefers a dark colour scheme, and [`Theme::CLASSIC`]
+    /// otherwise, updating whenever the system preference changes.
+    System,
+}
+
+impl ColourScheme {
+    /// Parse the value stored in `localStorage` by [`storage_value`](ColourScheme::storage_value).
+    pub fn from_storage_value(value: &str) -> Option<Self> {
+        match value {
+            "light" => Some(Self::Light),
+            "dark" => Some(Self::Dark),
+            "system" => Some(Self::System),
+            _ => None,
+        }
+    }
+
+    /// Get the value to store in `localStorage` for this colour scheme.
+    pub fn storage_value(self) -> &'static str {
+        match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+            Self::System => "system",
+        }
+    }
+
+    /// Get the colour scheme that the header button switches to next.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Light => Self::Dark,
+            Self::Dark => Self::System,
+            Self::System => Self::Light,
+        }
+    }
+
+    /// Get a description of this colour scheme for the header button.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Light => "Colour scheme: light",
+            Self::Dark => "Colour scheme: dark",
+            Self::System => "Colour scheme: follow system",
+        }
+    }
+
+    /// Check whether this colour scheme is currently dark.
+    pub fn is_dark(self) -> bool {
+        match self {
+            Self::Light => false,
+            Self::Dark => true,
+            Self::System => prefers_dark_query().is_some_and(|query| query.matches()),
+        }
+    }
+
+    /// Set the theme class on the body of the HTML to match this colour scheme.
+    pub fn apply(self) -> Option<()> {
+        let class_list = body().class_list();
+
+        let (old, new) = if self.is_dark() {
+            (Theme::CLASSIC.css_class, Theme::DARK.css_class)
+        } else {
+            (Theme::DARK.css_class, Theme::CLASSIC.css_class)
+        };
+
+        class_list.remove_1(old).ok()?;
+        class_list.add_1(new).ok()
+    }
+}
+
+/// Get the media query list for whether the system prefers a dark colour scheme.
+///
+/// Listen for its `change` event to find out when the system preference changes.
+pub fn prefers_dark_query() -> Option<MediaQueryList> {
+    window().match_media(PREFERS_DARK_QUERY).ok().flatten()
+}
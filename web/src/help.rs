@@ -0,0 +1,113 @@
+//! This module handles the "how to play" modal that is shown to new players.
+
+use crate::board::ExampleRowComp;
+use web_sys::{HtmlInputElement, MouseEvent};
+use wordle::prelude::*;
+use yew::{function_component, html, use_node_ref, Callback, Html, Properties};
+
+/// An example guess to show in the [`HelpModal`], with an explanation of its colours.
+struct Example {
+    /// The target word to score the guess against.
+    answer: &'static str,
+
+    /// The guess to score.
+    guess: &'static str,
+
+    /// An explanation of why the letters are coloured the way they are.
+    explanation: &'static str,
+}
+
+/// The examples shown in the [`HelpModal`].
+///
+/// These get scored by the real [`Game::make_guess`], so they always match what the player will
+/// actually see during a game.
+const EXAMPLES: [Example; 4] = [
+    Example {
+        answer: "DYSON",
+        guess: "DUSTY",
+        explanation: "D and S are in the word and in the correct spot. Y is in the word but in the wrong spot. U and T are not in the word at all.",
+    },
+    Example {
+        answer: "DYSON",
+        guess: "WORDY",
+        explanation: "O, D, and Y are all in the word, but in the wrong spots.",
+    },
+    Example {
+        answer: "DYSON",
+        guess: "DADDY",
+        explanation: "The answer only has one D, and the first D is in the correct spot, so the other two D's are grey.",
+    },
+    Example {
+        answer: "BLEEP",
+        guess: "EERIE",
+        explanation: "The answer has two E's that haven't been found yet, so only the first two E's are yellow. The last E is grey.",
+    },
+];
+
+/// Score the given example guess against its answer.
+fn score_example(example: &Example) -> Word {
+    let mut game = Game::new();
+    game.word = example.answer.to_string().into();
+
+    game.make_guess(example.guess)
+        .unwrap_or_else(|_| panic!("Example guess {:?} should be valid", example.guess))
+}
+
+/// The props for [`HelpModal`].
+#[derive(Clone, PartialEq, Properties)]
+pub struct HelpModalProps {
+    /// The callback to run when the modal is closed.
+    ///
+    /// The bool is whether the player has asked to not see the modal again.
+    pub on_close: Callback<bool>,
+}
+
+/// A modal that explains the rules of the game with example rows.
+///
+/// Each example row is rendered with [`ExampleRowComp`], so the tiles look and animate just like
+/// the real board.
+#[function_component(HelpModal)]
+pub fn help_modal(props: &HelpModalProps) -> Html {
+    let checkbox = use_node_ref();
+
+    let onclick = {
+        let checkbox = checkbox.clone();
+        let on_close = props.on_close.clone();
+        Callback::from(move |_: MouseEvent| {
+            let dont_show_again = checkbox
+                .cast::<HtmlInputElement>()
+                .is_some_and(|input| input.checked());
+            on_close.emit(dont_show_again);
+        })
+    };
+
+    let examples: Html = EXAMPLES
+        .iter()
+        .map(|example| {
+            html! {
+                <div class="example">
+                    <ExampleRowComp word={score_example(example)} />
+                    <p>{ example.explanation }</p>
+                </div>
+            }
+        })
+        .collect();
+
+    html! {
+        <div class="modal-container">
+            <div class="modal help-modal">
+                <h2>{ "How to play" }</h2>
+                <p>{ format!("Guess the word in {MAX_GUESSES} tries. Each guess must be a valid 5 letter word.") }</p>
+                <p>{ "After each guess, the colours of the tiles show how close your guess was to the word." }</p>
+                {examples}
+                <div class="modal-footer">
+                    <label>
+                        <input type="checkbox" ref={checkbox} />
+                        { "Don't show this again" }
+                    </label>
+                    <button class="modal-close-button" {onclick}>{ "Play" }</button>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -1,10 +1,15 @@
 //! This module handles components for the keyboard display at the bottom of the screen.
 
 use super::{Model, ModelMsg};
+use gloo_timers::callback::Timeout;
 use std::collections::HashMap;
 use web_sys::MouseEvent;
-use wordle::letters::Position;
-use yew::{classes, html, html::Scope, Component, Context, Html, Properties};
+use wordle::{
+    keyboard::{Keyboard, Layout},
+    letters::{LetterCount, Position},
+    theme::Theme,
+};
+use yew::{classes, html, html::Scope, Callback, Component, Context, Html, Properties};
 
 /// Get the parent scope from the given component context.
 ///
@@ -29,6 +34,9 @@ struct KeyProps {
     ///
     /// Colours are chosen by classic Wordle rules.
     position: Option<Position>,
+
+    /// What's known about how many times this letter appears in the word.
+    count: Option<LetterCount>,
 }
 
 impl Component for KeyComp {
@@ -47,22 +55,34 @@ impl Component for KeyComp {
     /// The button will have an appropriate class for its position, and will have a callback to
     /// send a message to the parent component ([`KeyboardComp`]) to add this letter when the
     /// button is clicked.
+    ///
+    /// If the letter is known to appear more than once, or exactly some number of times, then
+    /// the count is shown as a superscript, like "2+" or "1".
     fn view(&self, ctx: &Context<Self>) -> Html {
         fn position_to_class(position: Option<Position>) -> &'static str {
-            match position {
-                None => "",
-                Some(position) => match position {
-                    Position::NotInWord => "notinword",
-                    Position::WrongPosition => "wrongposition",
-                    Position::Correct => "correct",
-                },
-            }
+            position.map_or("", |position| Theme::default().tile(position).css_class)
         }
 
         let parent: Scope<KeyboardComp> = get_parent(ctx);
         let letter = ctx.props().letter;
         let position = ctx.props().position;
 
+        let count = match ctx.props().count {
+            Some(LetterCount {
+                at_least,
+                exact: true,
+            }) if at_least >= 1 => {
+                html! { <sup class="letter-count">{ at_least }</sup> }
+            }
+            Some(LetterCount {
+                at_least,
+                exact: false,
+            }) if at_least >= 2 => {
+                html! { <sup class="letter-count">{ format!("{at_least}+") }</sup> }
+            }
+            _ => html! {},
+        };
+
         let onclick = parent.callback(move |event: MouseEvent| {
             if event.detail() == 0 {
                 ModelMsg::DoNothing
@@ -72,7 +92,7 @@ impl Component for KeyComp {
         });
 
         html! {
-            <button class={classes!("keyboard-key", position_to_class(position))} {onclick}>{ ctx.props().letter }</button>
+            <button class={classes!("keyboard-key", position_to_class(position))} {onclick}>{ ctx.props().letter }{count}</button>
         }
     }
 }
@@ -105,30 +125,100 @@ impl Component for EnterKeyComp {
     }
 }
 
+/// How long in milliseconds the backspace key needs to be held down to clear the whole guess.
+const LONG_PRESS_DURATION: u32 = 500;
+
 /// A component for the backspace key on the keyboard.
-struct BackspaceKeyComp {}
+///
+/// A normal click on this key deletes a single letter, but a long press clears the whole guess.
+struct BackspaceKeyComp {
+    /// The timer for the current long press, if the key is being held down.
+    ///
+    /// Dropping this cancels the long press.
+    long_press_timeout: Option<Timeout>,
+
+    /// Whether the current press has already triggered a long press.
+    ///
+    /// If it has, then we ignore the click event that the browser sends when the key is released.
+    long_pressed: bool,
+}
+
+/// An enum of messages that can be sent to [`BackspaceKeyComp`].
+enum BackspaceKeyMsg {
+    /// The user has started pressing the key.
+    PressStart,
+
+    /// The user has stopped pressing the key, or moved their pointer off of it.
+    PressEnd,
+
+    /// The key has been held down for [`LONG_PRESS_DURATION`] milliseconds.
+    LongPress,
+
+    /// The key has been clicked.
+    Click,
+}
 
 impl Component for BackspaceKeyComp {
-    /// This component accepts no messages.
-    type Message = ();
+    type Message = BackspaceKeyMsg;
 
     /// This component has no props.
     type Properties = ();
 
-    /// Create an empty struct.
+    /// Create a struct with no long press in progress.
     fn create(_ctx: &Context<Self>) -> Self {
-        Self {}
+        Self {
+            long_press_timeout: None,
+            long_pressed: false,
+        }
+    }
+
+    /// Handle presses and clicks, sending [`ModelMsg::SendBackspace`] or
+    /// [`ModelMsg::ClearCurrentGuess`] to the parent component ([`KeyboardComp`]) as appropriate.
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let parent: Scope<KeyboardComp> = get_parent(ctx);
+
+        match msg {
+            BackspaceKeyMsg::PressStart => {
+                self.long_pressed = false;
+                self.long_press_timeout = Some(Timeout::new(LONG_PRESS_DURATION, {
+                    let link = ctx.link().clone();
+                    move || link.send_message(BackspaceKeyMsg::LongPress)
+                }));
+            }
+            BackspaceKeyMsg::PressEnd => {
+                self.long_press_timeout = None;
+            }
+            BackspaceKeyMsg::LongPress => {
+                self.long_press_timeout = None;
+                self.long_pressed = true;
+                parent.send_message(ModelMsg::ClearCurrentGuess);
+            }
+            BackspaceKeyMsg::Click => {
+                if self.long_pressed {
+                    self.long_pressed = false;
+                } else {
+                    parent.send_message(ModelMsg::SendBackspace);
+                }
+            }
+        };
+
+        false
     }
 
     /// Return the HTML button for this key.
     ///
-    /// The button has an appropriate class and a callback to send [`ModelMsg::SendBackspace`] to
-    /// the parent component ([`KeyboardComp`]) when it's clicked.
+    /// The button has an appropriate class and callbacks to track presses, so that it can
+    /// distinguish between a normal click and a long press.
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let parent: Scope<KeyboardComp> = get_parent(ctx);
-        let onclick = parent.callback(move |_| ModelMsg::SendBackspace);
+        let link = ctx.link();
+        let onclick = link.callback(|_| BackspaceKeyMsg::Click);
+        let onpointerdown = link.callback(|_| BackspaceKeyMsg::PressStart);
+        let onpointerup = link.callback(|_| BackspaceKeyMsg::PressEnd);
+        let onpointerleave = link.callback(|_| BackspaceKeyMsg::PressEnd);
+        let oncontextmenu = Callback::from(|event: MouseEvent| event.prevent_default());
+
         html! {
-            <button class="keyboard-key special-key" {onclick}>
+            <button class="keyboard-key special-key" {onclick} {onpointerdown} {onpointerup} {onpointerleave} {oncontextmenu}>
                 <svg viewBox="0 0 24 24" height="24" width="24">
                     <path fill="var(--color-tone-1)" d="M22 3H7c-.69 0-1.23.35-1.59.88L0 12l5.41 8.11c.36.53.9.89 1.59.89h15c1.1 0 2-.9 2-2V5c0-1.1-.9-2-2-2zm0 16H7.07L2.4 12l4.66-7H22v14zm-11.59-2L14 13.41 17.59 17 19 15.59 15.41 12 19 8.41 17.59 7 14 10.59 10.41 7 9 8.41 12.59 12 9 15.59z" />
                 </svg>
@@ -143,8 +233,12 @@ pub struct KeyboardComp {}
 /// The props for [`KeyboardComp`].
 #[derive(Clone, PartialEq, Properties)]
 pub struct KeyboardProps {
-    /// Map each letter on the keyboard to an optional position so that we can colour it properly.
-    pub map: HashMap<char, Option<Position>>,
+    /// The best position of each letter on the keyboard, so that we can colour it properly.
+    pub map: Keyboard,
+
+    /// Map letters to what's known about how many times they appear in the word, so that we can
+    /// annotate their keys.
+    pub counts: HashMap<char, LetterCount>,
 }
 
 impl Component for KeyboardComp {
@@ -169,57 +263,39 @@ impl Component for KeyboardComp {
     /// Return the HTML div for the keyboard.
     ///
     /// The keyboard is QWERTY and has enter in the bottom left and backspace in the bottom right,
-    /// just like classic Wordle.
+    /// just like classic Wordle. The keys come from [`Keyboard::iter_rows`].
     ///
     /// This component uses [`KeyComp`], [`EnterKeyComp`], and [`BackspaceKeyComp`] to build the
     /// keyboard in HTML div elements.
+    #[allow(clippy::let_unit_value)]
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let get_key = |letter: char| -> Html {
-            let position = *ctx.props().map.get(&letter).unwrap_or_else(|| {
-                panic!("We should have a position value for character {:?}", letter)
-            });
+        let mut rows = ctx.props().map.iter_rows(Layout::Qwerty).map(|row| {
+            row.map(|(letter, position)| {
+                let count = ctx.props().counts.get(&letter).copied();
 
-            html! {
-                <KeyComp {letter} {position} />
-            }
-        };
+                html! {
+                    <KeyComp {letter} {position} {count} />
+                }
+            })
+            .collect::<Html>()
+        });
+        let top = rows.next().unwrap_or_default();
+        let middle = rows.next().unwrap_or_default();
+        let bottom = rows.next().unwrap_or_default();
 
         html! {
             <div class="keyboard">
                 <div class="keyboard-row">
-                    {get_key('Q')}
-                    {get_key('W')}
-                    {get_key('E')}
-                    {get_key('R')}
-                    {get_key('T')}
-                    {get_key('Y')}
-                    {get_key('U')}
-                    {get_key('I')}
-                    {get_key('O')}
-                    {get_key('P')}
+                    {top}
                 </div>
                 <div class="keyboard-row">
                     <div class="keyboard-spacer" />
-                    {get_key('A')}
-                    {get_key('S')}
-                    {get_key('D')}
-                    {get_key('F')}
-                    {get_key('G')}
-                    {get_key('H')}
-                    {get_key('J')}
-                    {get_key('K')}
-                    {get_key('L')}
+                    {middle}
                     <div class="keyboard-spacer" />
                 </div>
                 <div class="keyboard-row">
                     <EnterKeyComp />
-                    {get_key('Z')}
-                    {get_key('X')}
-                    {get_key('C')}
-                    {get_key('V')}
-                    {get_key('B')}
-                    {get_key('N')}
-                    {get_key('M')}
+                    {bottom}
                     <BackspaceKeyComp />
                 </div>
             </div>
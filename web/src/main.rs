@@ -2,76 +2,174 @@
 //! [`yew`](https://docs.rs/yew/0.19.3/yew/).
 
 mod board;
+mod colour_scheme;
+mod help;
 mod keyboard;
 mod misc;
+mod motion;
+mod recovery;
+mod sound;
+mod stats;
+mod viewport;
 
-use crate::{board::BoardComp, keyboard::KeyboardComp, misc::ShowCorrectGuess};
+use crate::{
+    board::{BoardComp, RowAnalysis},
+    colour_scheme::{prefers_dark_query, ColourScheme},
+    help::HelpModal,
+    keyboard::KeyboardComp,
+    misc::ShowCorrectGuess,
+    sound::Sounds,
+    stats::StatsModal,
+};
 use gloo_events::EventListener;
 use gloo_timers::callback::Timeout;
-use gloo_utils::{body, document, window};
-use std::{cell::RefCell, collections::HashMap};
+use gloo_utils::{document, window};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
 use web_sys::{
     wasm_bindgen::{JsCast, UnwrapThrowExt},
-    KeyboardEvent, MouseEvent,
+    Blob, BlobPropertyBag, CompositionEvent, Element, HtmlAnchorElement, KeyboardEvent, MouseEvent,
+    TouchEvent, Url,
+};
+use wordle::{
+    analysis,
+    analytics::{Analytics, GameFinished, GameStarted, GuessMade, NoAnalytics},
+    normalize::{normalize_letter, Normalization},
+    prelude::*,
+    render::{
+        html::{render_page, HtmlOptions},
+        text::describe_guess,
+    },
+    session::SessionStats,
+    solver::{BestGuessSearch, Solver},
+    theme::{Motion, Theme},
+    valid_words::ALPHABET,
 };
-use wordle::{prelude::*, valid_words::ALPHABET};
 use yew::{html, Component, Context, Html};
 
-/// Get the value of the `wordleDarkMode` key in `localStorage`.
-fn storage_get_dark_mode() -> Option<bool> {
+/// How to normalize keys pressed on a physical or virtual keyboard.
+const KEY_NORMALIZATION: Normalization = Normalization {
+    strip_apostrophes: true,
+    strip_diacritics: true,
+};
+
+/// How many words to score in each step of grading a row of the analysis, between which the
+/// browser can handle input and draw the page. See [`ModelMsg::GradeNextRow`].
+const GUESSES_PER_GRADING_STEP: usize = 512;
+
+/// The `localStorage` key for the [`ColourScheme`].
+const COLOUR_SCHEME_KEY: &str = "wordleColourScheme";
+
+/// The `localStorage` key for whether dark mode is enabled, from before the colour scheme could
+/// follow the system preference.
+///
+/// This is only read to migrate the old setting. See [`stored_colour_scheme`].
+const DARK_MODE_KEY: &str = "wordleDarkMode";
+
+/// The `localStorage` key for whether the player has asked to not see the help modal again.
+const HIDE_HELP_KEY: &str = "wordleHideHelp";
+
+/// The `localStorage` key for whether sound effects are muted.
+const MUTED_KEY: &str = "wordleMuted";
+
+/// The `localStorage` key for the [`Motion`] preference.
+const MOTION_KEY: &str = "wordleMotion";
+
+/// Get the string value of the given key in `localStorage`.
+fn storage_get(key: &str) -> Option<String> {
     let storage = window().local_storage().unwrap_or(None)?;
-    match storage.get_item("wordleDarkMode") {
-        Err(_) => None,
-        Ok(opt_str) => match opt_str {
-            None => None,
-            Some(value) => {
-                if value == "true" {
-                    Some(true)
-                } else if value == "false" {
-                    Some(false)
-                } else {
-                    None
-                }
-            }
-        },
-    }
+    storage.get_item(key).unwrap_or(None)
 }
 
-/// Set the value of the `wordleDarkMode` key in `localStorage`.
-fn storage_set_dark_mode(dark_mode: bool) -> Option<()> {
+/// Set the string value of the given key in `localStorage`.
+fn storage_set(key: &str, value: &str) -> Option<()> {
     let storage = window().local_storage().unwrap_or(None)?;
-    match storage.set_item("wordleDarkMode", &dark_mode.to_string()) {
+    match storage.set_item(key, value) {
         Err(_) => None,
         Ok(_) => Some(()),
     }
 }
 
-/// Set dark mode on the body of the HTML by adding or removing the "dark" class.
-fn set_dark_mode(dark_mode: bool) -> Option<()> {
-    let class_list = body().class_list();
+/// Remove the given key from `localStorage`.
+fn storage_remove(key: &str) -> Option<()> {
+    let storage = window().local_storage().unwrap_or(None)?;
+    storage.remove_item(key).ok()
+}
 
-    let a: &str;
-    let b: &str;
+/// Get the boolean value of the given key in `localStorage`.
+fn storage_get_bool(key: &str) -> Option<bool> {
+    match storage_get(key)?.as_str() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
 
-    if dark_mode {
-        a = "light";
-        b = "dark";
-    } else {
-        a = "dark";
-        b = "light";
-    };
+/// Set the boolean value of the given key in `localStorage`.
+fn storage_set_bool(key: &str, value: bool) -> Option<()> {
+    storage_set(key, &value.to_string())
+}
 
-    if class_list.contains(a) {
-        if class_list.remove_1(a).is_err() {
-            return None;
-        };
-    };
+/// Get the [`ColourScheme`] stored in `localStorage`.
+///
+/// If there isn't one, then we use the old dark mode setting if the player set it, and follow the
+/// system preference otherwise.
+fn stored_colour_scheme() -> ColourScheme {
+    if let Some(scheme) = storage_get(COLOUR_SCHEME_KEY)
+        .as_deref()
+        .and_then(ColourScheme::from_storage_value)
+    {
+        return scheme;
+    }
+
+    match storage_get_bool(DARK_MODE_KEY) {
+        Some(true) => ColourScheme::Dark,
+        Some(false) => ColourScheme::Light,
+        None => ColourScheme::System,
+    }
+}
+
+/// Vibrate the device briefly, if the browser supports the Vibration API.
+///
+/// This is used as haptic feedback when the user submits an invalid guess.
+fn vibrate() {
+    window().navigator().vibrate_with_duration(200);
+}
 
-    if class_list.add_1(b).is_err() {
-        return None;
-    };
+/// Make the browser download a file with the given name, MIME type, and contents.
+///
+/// This creates a temporary object URL for the contents and clicks a link to it.
+fn download(filename: &str, mime_type: &str, contents: &str) -> Option<()> {
+    let parts = js_sys::Array::of1(&contents.into());
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options).ok()?;
+    let url = Url::create_object_url_with_blob(&blob).ok()?;
 
-    Some(())
+    let link: HtmlAnchorElement = document().create_element("a").ok()?.dyn_into().ok()?;
+    link.set_href(&url);
+    link.set_download(filename);
+    link.click();
+
+    Url::revoke_object_url(&url).ok()
+}
+
+/// The minimum horizontal distance in pixels that a touch has to travel across the board to count
+/// as a swipe.
+const SWIPE_THRESHOLD: i32 = 50;
+
+/// Get the coordinates of the first touch point that changed in the given touch event.
+///
+/// The event must have come from inside the board, otherwise we return [`None`].
+fn board_touch_coords(event: &TouchEvent) -> Option<(i32, i32)> {
+    let target: Element = event.target()?.dyn_into().ok()?;
+    target.closest(".board").ok()??;
+
+    let touch = event.changed_touches().get(0)?;
+    Some((touch.client_x(), touch.client_y()))
 }
 
 /// The root component of the app.
@@ -79,17 +177,22 @@ struct Model {
     /// The Wordle game itself.
     game: Game,
 
-    /// The keyboard map. See [`Game::keyboard`].
+    /// The keyboard. See [`Game::keyboard`].
     ///
     /// This needs to be a separate member attribute so that the virtual keyboard can be colored
     /// after a delay, rather than immediately after the guess.
-    map: HashMap<char, Option<Position>>,
+    map: Keyboard,
+
+    /// The known letter counts. See [`Game::letter_counts`].
+    ///
+    /// Like [`map`](Model::map), this is updated after a delay.
+    counts: HashMap<char, LetterCount>,
 
     /// A list of previously guessed words.
     guesses: Vec<Word>,
 
-    /// The guess which is currently being typed.
-    current_guess: Option<Vec<char>>,
+    /// The state of the board while typing, including the guess which is currently being typed.
+    board: BoardState,
 
     /// Whether the game has been correctly guessed.
     guessed_correct: bool,
@@ -97,6 +200,31 @@ struct Model {
     /// Whether we should show the correct guess.
     show_correct_guess: bool,
 
+    /// The player for sound effects.
+    sounds: Sounds,
+
+    /// Whether sound effects are muted.
+    ///
+    /// This is persisted in `localStorage`, and defaults to the user's reduced motion preference,
+    /// since users who want fewer animations typically don't want unexpected sounds either.
+    muted: bool,
+
+    /// The player's choice of colour scheme.
+    ///
+    /// This is persisted in `localStorage`. See [`stored_colour_scheme`].
+    colour_scheme: ColourScheme,
+
+    /// The player's choice of whether to animate the board.
+    ///
+    /// This is persisted in `localStorage`, and follows the system preference by default. See
+    /// [`motion`].
+    motion: Motion,
+
+    /// Whether we should show the [`HelpModal`].
+    ///
+    /// This is true on startup unless the player has asked to not see it again.
+    show_help: bool,
+
     /// The event listener for keyboard events.
     ///
     /// We need to keep this in the struct to avoid it being dropped from the DOM and being
@@ -105,10 +233,71 @@ struct Model {
     /// [`Model::rendered`].
     kbd_listener: Option<EventListener>,
 
+    /// The event listeners for touch events.
+    ///
+    /// These are kept alive for the same reason as [`kbd_listener`](Model::kbd_listener), and
+    /// are also initialised in [`Model::rendered`].
+    touch_listeners: Vec<EventListener>,
+
+    /// The event listeners for IME composition events.
+    ///
+    /// These are kept alive for the same reason as [`kbd_listener`](Model::kbd_listener), and
+    /// are also initialised in [`Model::rendered`].
+    composition_listeners: Vec<EventListener>,
+
+    /// The event listener for changes to the system colour scheme preference.
+    ///
+    /// This is kept alive for the same reason as [`kbd_listener`](Model::kbd_listener), and is
+    /// also initialised in [`Model::rendered`].
+    colour_scheme_listener: Option<EventListener>,
+
+    /// The event listener for changes to the system reduced motion preference.
+    ///
+    /// This is kept alive for the same reason as [`kbd_listener`](Model::kbd_listener), and is
+    /// also initialised in [`Model::rendered`].
+    motion_listener: Option<EventListener>,
+
+    /// The event listener for changes to the size of the visual viewport, which keeps the board
+    /// and keyboard visible when mobile browsers show or hide their toolbars or on-screen keyboard.
+    ///
+    /// This is kept alive for the same reason as [`kbd_listener`](Model::kbd_listener), and is
+    /// also initialised in [`Model::rendered`]. See [`viewport`].
+    viewport_listener: Option<EventListener>,
+
+    /// Where anonymised game events are sent.
+    ///
+    /// This is [`NoAnalytics`], so nothing is collected. A deployment that wants metrics can
+    /// swap in its own [`Analytics`] implementation here.
+    analytics: Box<dyn Analytics>,
+
+    /// The coordinates where the current touch on the board started, if there is one.
+    ///
+    /// This is used to detect swipes. See [`ModelMsg::BoardTouchEnd`].
+    touch_start: Option<(i32, i32)>,
+
     /// Whether the user has just submitted a bad guess - meaning the guess row should shake.
     ///
     /// The bool is wrapped in a [`RefCell`] to allow it to be mutated in [`view()`](Model::view).
     bad_guess: RefCell<bool>,
+
+    /// The player's stats over every finished game, which are saved in `localStorage`.
+    ///
+    /// See [`stats`].
+    stats: SessionStats,
+
+    /// Whether the [`StatsModal`] should be shown.
+    show_stats: bool,
+
+    /// The number of possible answers left after each guess and the solver's grade of it, if the
+    /// player has asked to see the analysis of the finished game.
+    ///
+    /// The grades are filled in one row at a time by [`ModelMsg::GradeNextRow`]. See
+    /// [`analysis::candidate_counts`] and [`Solver::grade`].
+    analysis: Option<Vec<RowAnalysis>>,
+
+    /// The index of the row of the [`analysis`](Model::analysis) that's being graded, and the
+    /// search for the best guess to grade it against, if a search is in progress.
+    grading: Option<(usize, BestGuessSearch)>,
 }
 
 /// An enum of messages that can be sent to the model.
@@ -126,7 +315,10 @@ pub enum ModelMsg {
     /// This should only be used internally.
     ForceUpdate,
 
-    /// Update [`self.map`](Model::map) and re-render.
+    /// Update [`self.map`](Model::map) and [`self.counts`](Model::counts) and re-render.
+    ///
+    /// This is sent once the last guess has been revealed, so it also unlocks the board. See
+    /// [`BoardState::lock`].
     UpdateMap,
 
     /// Show the correct guess.
@@ -137,14 +329,73 @@ pub enum ModelMsg {
     /// Make a guess with the given string. This will call [`Game::make_guess`].
     MakeGuess(String),
 
-    /// Toggle dark mode for the whole HTML body.
+    /// Switch to the next [`ColourScheme`] and apply it to the whole HTML body.
+    ///
+    /// See [`ColourScheme::next`].
+    CycleColourScheme,
+
+    /// The system colour scheme preference has changed, so apply it again if we're following it.
+    SystemColourSchemeChanged,
+
+    /// Switch to the next [`Motion`] preference and apply it to the whole HTML body.
+    ///
+    /// See [`Motion::next`].
+    CycleMotion,
+
+    /// The system reduced motion preference has changed, so apply it again if we're following it.
+    SystemMotionChanged,
+
+    /// Toggle whether sound effects are muted.
+    ToggleMute,
+
+    /// Play the jingle for winning the game.
+    ///
+    /// This is a message to allow a delay so that the jingle plays once the tiles are revealed.
+    PlayWinSound,
+
+    /// Download the finished game as a static HTML page. See [`render_page`].
+    ExportGame,
+
+    /// Copy the share text of the finished game to the clipboard. See [`Game::share_string_with`].
+    ShareGame,
+
+    /// Show or hide the grade of each guess and the number of possible answers left after it,
+    /// once the game is over.
+    ///
+    /// See [`Model::analysis`].
+    ToggleAnalysis,
+
+    /// Take a step of grading the first row of the analysis that hasn't been graded yet, and then
+    /// send this message again if there are rows left.
+    ///
+    /// Grading a row can take a moment, so each step only scores [`GUESSES_PER_GRADING_STEP`]
+    /// words. This gives the browser a chance to handle input and draw the page between steps,
+    /// rather than freezing until every row is graded. See [`Model::grading`].
+    GradeNextRow,
+
+    /// Show the [`StatsModal`].
+    ShowStats,
+
+    /// Close the [`StatsModal`].
+    CloseStats,
+
+    /// Show the [`HelpModal`].
+    ShowHelp,
+
+    /// Close the [`HelpModal`].
     ///
-    /// See [`set_dark_mode`].
-    ToggleDarkMode,
+    /// The bool is whether the player has asked to not see it again on startup.
+    CloseHelp(bool),
 
     /// The given character to the current guess.
     AddToCurrentGuess(char),
 
+    /// Add every letter of the given text to the current guess.
+    ///
+    /// This is sent when an input method editor (IME) finishes composing some text. Characters
+    /// that don't normalize to letters, like kana, are ignored.
+    AddComposedText(String),
+
     /// This message represents the enter key being pressed, meaning the user wants to submit their
     /// current guess.
     SendEnter,
@@ -152,6 +403,38 @@ pub enum ModelMsg {
     /// This message represents the backspace key being pressed, meaning the user wants to delete
     /// the last character they added to their guess.
     SendBackspace,
+
+    /// Clear the whole of the current guess.
+    ///
+    /// This is sent when the user long-presses the backspace key.
+    ClearCurrentGuess,
+
+    /// The user has started touching the board at the given `(x, y)` coordinates.
+    BoardTouchStart(i32, i32),
+
+    /// The user has stopped touching the board at the given `(x, y)` coordinates.
+    ///
+    /// If the touch has moved far enough to the left since [`BoardTouchStart`](ModelMsg::BoardTouchStart),
+    /// then it counts as a swipe and we delete the last character of the current guess.
+    BoardTouchEnd(i32, i32),
+}
+
+impl Model {
+    /// Check whether the game is over, because the player has either guessed the word or run out
+    /// of guesses.
+    fn is_finished(&self) -> bool {
+        self.guessed_correct || self.guesses.len() >= MAX_GUESSES
+    }
+
+    /// Give the player feedback that their guess was invalid, beyond shaking the row.
+    ///
+    /// This vibrates the device and plays a buzz if sound isn't muted.
+    fn invalid_guess_feedback(&mut self) {
+        vibrate();
+        if !self.muted {
+            self.sounds.invalid_guess();
+        }
+    }
 }
 
 impl Component for Model {
@@ -162,25 +445,77 @@ impl Component for Model {
 
     /// Create a simple, default struct for the component.
     fn create(_ctx: &Context<Self>) -> Self {
+        let colour_scheme = stored_colour_scheme();
+        colour_scheme.apply();
+
+        let motion = storage_get(MOTION_KEY)
+            .as_deref()
+            .and_then(Motion::by_name)
+            .unwrap_or_default();
+        motion::apply(motion);
+
+        let (game, guesses) = recovery::take_game_to_restore().unwrap_or_default();
+        let mut board = BoardState::default();
+        for _ in &guesses {
+            let _ = board.next_row();
+        }
+
+        let analytics: Box<dyn Analytics> = Box::new(NoAnalytics);
+        if guesses.is_empty() {
+            analytics.game_started(&GameStarted::from_game(&game));
+        }
+
         Self {
-            game: Game::new(),
-            map: Game::new_keyboard_map(),
-            guesses: Vec::new(),
-            current_guess: None,
+            map: game.keyboard,
+            counts: game.letter_counts.clone(),
+            show_help: guesses.is_empty() && !storage_get_bool(HIDE_HELP_KEY).unwrap_or(false),
+            game,
+            guesses,
+            board,
             guessed_correct: false,
             show_correct_guess: false,
+            sounds: Sounds::default(),
+            muted: storage_get_bool(MUTED_KEY).unwrap_or_else(motion::prefers_reduced_motion),
+            colour_scheme,
+            motion,
             kbd_listener: None,
+            touch_listeners: Vec::new(),
+            composition_listeners: Vec::new(),
+            colour_scheme_listener: None,
+            motion_listener: None,
+            viewport_listener: None,
+            analytics,
+            touch_start: None,
             bad_guess: RefCell::new(false),
+            stats: stats::load(),
+            show_stats: false,
+            analysis: None,
+            grading: None,
         }
     }
 
     /// Update the model based on the given message. See [`ModelMsg`].
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        // Typing shouldn't affect the game while a modal is covering it
+        if (self.show_help || self.show_stats)
+            && matches!(
+                msg,
+                Self::Message::AddToCurrentGuess(_)
+                    | Self::Message::SendEnter
+                    | Self::Message::SendBackspace
+                    | Self::Message::ClearCurrentGuess
+            )
+        {
+            return false;
+        }
+
         match msg {
             Self::Message::DoNothing => false,
             Self::Message::ForceUpdate => true,
             Self::Message::UpdateMap => {
-                self.map = self.game.keyboard.clone();
+                self.board.unlock();
+                self.map = self.game.keyboard;
+                self.counts = self.game.letter_counts.clone();
                 true
             }
             Self::Message::ShowCorrectGuess => {
@@ -191,15 +526,37 @@ impl Component for Model {
                 match self.game.make_guess(&guess) {
                     Ok(letters) => {
                         self.guesses.push(letters);
-                        self.current_guess = None;
+                        if let Some(event) = GuessMade::from_game(&self.game) {
+                            self.analytics.guess_made(&event);
+                        }
+                        let _ = self.board.next_row();
+
+                        // Nothing can be typed or submitted until the tiles have been revealed
+                        self.board.lock();
 
                         if letters.iter().map(|l| l.position).collect::<Vec<_>>() == vec![Position::Correct; 5] {
                             self.guessed_correct = true;
-                        } else if self.guesses.len() >= 6 {
+
+                            let link = ctx.link().clone();
+                            Timeout::new(1800, move || link.send_message(ModelMsg::PlayWinSound)).forget();
+                        } else if self.guesses.len() >= MAX_GUESSES {
                             let link = ctx.link().clone();
                             Timeout::new(2000, move || link.send_message(ModelMsg::ShowCorrectGuess)).forget();
                         }
 
+                        recovery::save_game(&self.game, &self.guesses, self.is_finished());
+
+                        if let Some(event) = GameFinished::from_game(&self.game) {
+                            self.analytics.game_finished(&event);
+                        }
+
+                        if self.is_finished() {
+                            self.stats = stats::record_game(&self.guesses);
+
+                            let link = ctx.link().clone();
+                            Timeout::new(3000, move || link.send_message(ModelMsg::ShowStats)).forget();
+                        }
+
                         Timeout::new(1800, {
                             let link = ctx.link().clone();
                             move || link.send_message(ModelMsg::UpdateMap)
@@ -209,55 +566,215 @@ impl Component for Model {
                     Err(e) => match e {
                         GuessError::WrongWordLength => unreachable!("The player should only be able to submit a guess with 5 letters, not {}", guess.len()),
                         GuessError::IncludesNonAscii => unreachable!("The guess should never be able to contain non-ASCII characters (guess = {guess:?})"),
+                        GuessError::TooFast(_) => unreachable!("The game has no policy, so guesses can't be too fast"),
+                        GuessError::TooManyAttempts => unreachable!("The web app doesn't limit guess attempts"),
+                        GuessError::BannedOpener | GuessError::TooFewVowels(_) => unreachable!("The game has no policy, so any opener is allowed"),
+                        GuessError::RepeatedGuess | GuessError::MissingCorrectLetter(..) | GuessError::MissingLetter(_) => unreachable!("The game has no policy, so it isn't in hard mode and allows repeated guesses"),
                         GuessError::InvalidWord => {
                             self.bad_guess.replace(true);
+                            self.invalid_guess_feedback();
                         }
                     }
                 };
                 true
             }
-            Self::Message::ToggleDarkMode => {
-                let dark_mode = storage_get_dark_mode().unwrap_or(false);
-                storage_set_dark_mode(!dark_mode);
+            Self::Message::CycleColourScheme => {
+                self.colour_scheme = self.colour_scheme.next();
+                storage_set(COLOUR_SCHEME_KEY, self.colour_scheme.storage_value());
+                self.colour_scheme.apply();
                 true
             }
-            Self::Message::AddToCurrentGuess(letter) => {
-                if self.guessed_correct {
-                    return false;
+            Self::Message::SystemColourSchemeChanged => {
+                if self.colour_scheme == ColourScheme::System {
+                    self.colour_scheme.apply();
                 }
+                false
+            }
+            Self::Message::CycleMotion => {
+                self.motion = self.motion.next();
+                storage_set(MOTION_KEY, self.motion.name());
+                motion::apply(self.motion);
+                true
+            }
+            Self::Message::SystemMotionChanged => {
+                if self.motion == Motion::System {
+                    motion::apply(self.motion);
+                }
+                false
+            }
+            Self::Message::ToggleAnalysis => {
+                self.analysis = match self.analysis {
+                    Some(_) => None,
+                    None => {
+                        let link = ctx.link().clone();
+                        Timeout::new(0, move || link.send_message(ModelMsg::GradeNextRow)).forget();
 
-                match self.current_guess.as_mut() {
-                    Some(letters) => {
-                        if letters.len() < 5 {
-                            letters.push(letter);
-                        }
+                        Some(
+                            analysis::candidate_counts(&self.guesses)
+                                .into_iter()
+                                .map(|candidates_left| RowAnalysis {
+                                    candidates_left,
+                                    grade: None,
+                                    progress: 0,
+                                })
+                                .collect(),
+                        )
                     }
-                    None => self.current_guess = Some(vec![letter]),
                 };
                 true
             }
-            Self::Message::SendEnter => {
-                if let Some(chars) = &self.current_guess {
-                    if chars.len() == 5 {
-                        let guess: String = chars.iter().collect();
-                        self.update(ctx, Self::Message::MakeGuess(guess.to_uppercase()))
-                    } else {
-                        self.bad_guess.replace(true);
-                        true
+            Self::Message::GradeNextRow => {
+                let Some(rows) = &mut self.analysis else {
+                    self.grading = None;
+                    return false;
+                };
+                let Some(index) = rows.iter().position(|row| row.grade.is_none()) else {
+                    self.grading = None;
+                    return false;
+                };
+
+                let search = match &mut self.grading {
+                    Some((row, search)) if *row == index => search,
+                    grading => {
+                        let search = Solver::from_history(&self.guesses[..index]).search();
+                        &mut grading.insert((index, search)).1
                     }
+                };
+
+                // The progress is a fraction from 0 to 1, so the percentage always fits in a u8
+                let finished = search.step(GUESSES_PER_GRADING_STEP);
+                rows[index].progress = (search.progress() * 100.0).round() as u8;
+                if finished {
+                    let guess: String = self.guesses[index]
+                        .iter()
+                        .map(|letter| letter.letter)
+                        .collect();
+                    rows[index].grade = Some(search.grade(&guess));
+                    self.grading = None;
+                }
+
+                if rows.iter().any(|row| row.grade.is_none()) {
+                    let link = ctx.link().clone();
+                    Timeout::new(0, move || link.send_message(ModelMsg::GradeNextRow)).forget();
+                }
+                true
+            }
+            Self::Message::ExportGame => {
+                // The answer is hidden behind a click, so the page can be shared without spoilers
+                let options = HtmlOptions {
+                    theme: if self.colour_scheme.is_dark() {
+                        Theme::DARK
+                    } else {
+                        Theme::CLASSIC
+                    },
+                    hide_answer: true,
+                };
+                download(
+                    "wordle.html",
+                    "text/html",
+                    &render_page(&self.guesses, self.game.word.reveal_for_display(), &options),
+                );
+                false
+            }
+            Self::Message::ShareGame => {
+                // Like the official Wordle, the share text only has the colours of the tiles
+                let theme = if self.colour_scheme.is_dark() {
+                    Theme::DARK
                 } else {
+                    Theme::CLASSIC
+                };
+                if let Some(text) = self.game.share_string_with(true, &theme) {
+                    let _ = window().navigator().clipboard().write_text(&text);
+                }
+                false
+            }
+            Self::Message::ToggleMute => {
+                self.muted = !self.muted;
+                storage_set_bool(MUTED_KEY, self.muted);
+                true
+            }
+            Self::Message::PlayWinSound => {
+                if !self.muted {
+                    self.sounds.win();
+                }
+                false
+            }
+            Self::Message::ShowStats => {
+                self.show_stats = true;
+                true
+            }
+            Self::Message::CloseStats => {
+                self.show_stats = false;
+                true
+            }
+            Self::Message::ShowHelp => {
+                self.show_help = true;
+                true
+            }
+            Self::Message::CloseHelp(dont_show_again) => {
+                if dont_show_again {
+                    storage_set_bool(HIDE_HELP_KEY, true);
+                }
+                self.show_help = false;
+                true
+            }
+            Self::Message::AddToCurrentGuess(letter) => {
+                if self.guessed_correct {
+                    return false;
+                }
+
+                if self.board.push(letter).is_err() {
+                    return false;
+                }
+
+                if !self.muted {
+                    self.sounds.key_click();
+                }
+                true
+            }
+            Self::Message::AddComposedText(text) => {
+                let letters = text
+                    .chars()
+                    .filter_map(|c| normalize_letter(c, &KEY_NORMALIZATION))
+                    .filter(|letter| ALPHABET.contains(letter));
+
+                let mut changed = false;
+                for letter in letters {
+                    changed |= self.update(ctx, Self::Message::AddToCurrentGuess(letter));
+                }
+                changed
+            }
+            Self::Message::SendEnter => match self.board.guess() {
+                Ok(guess) => self.update(ctx, Self::Message::MakeGuess(guess)),
+                Err(CursorError::NoRowsLeft | CursorError::Locked) => false,
+                Err(_) => {
                     self.bad_guess.replace(true);
+                    self.invalid_guess_feedback();
                     true
                 }
+            },
+            Self::Message::SendBackspace => self.board.pop().is_some(),
+            Self::Message::ClearCurrentGuess => {
+                if self.guessed_correct {
+                    return false;
+                }
+
+                self.board.clear()
             }
-            Self::Message::SendBackspace => {
-                if let Some(chars) = &mut self.current_guess {
-                    if chars.len() > 0 {
-                        chars.pop();
-                        true
-                    } else {
-                        false
-                    }
+            Self::Message::BoardTouchStart(x, y) => {
+                self.touch_start = Some((x, y));
+                false
+            }
+            Self::Message::BoardTouchEnd(x, y) => {
+                let Some((start_x, start_y)) = self.touch_start.take() else {
+                    return false;
+                };
+
+                let dx = x - start_x;
+                let dy = y - start_y;
+
+                if dx <= -SWIPE_THRESHOLD && dx.abs() > dy.abs() {
+                    self.update(ctx, Self::Message::SendBackspace)
                 } else {
                     false
                 }
@@ -267,34 +784,134 @@ impl Component for Model {
 
     /// Return the HTML of the whole model.
     ///
-    /// This includes the header with dark mode button, the game board, and the virtual keyboard.
+    /// This includes the header with help, mute, and colour scheme buttons, the game board, and the virtual
+    /// keyboard, as well as the [`HelpModal`] if it should be shown.
     /// It also sets up a keyboard listener to allow the user to type.
+    #[allow(clippy::unnecessary_operation)]
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let dark_mode = storage_get_dark_mode().unwrap_or(false);
-        set_dark_mode(dark_mode);
-
-        let button_icon: Html = if dark_mode {
-            html! {
+        let button_icon: Html = match self.colour_scheme {
+            ColourScheme::Dark => html! {
                 <svg viewBox="0 0 24 24" width="24" height="24">
                     <path fill="currentColor" d="M9.37,5.51C9.19,6.15,9.1,6.82,9.1,7.5c0,4.08,3.32,7.4,7.4,7.4c0.68,0,1.35-0.09,1.99-0.27C17.45,17.19,14.93,19,12,19 c-3.86,0-7-3.14-7-7C5,9.07,6.81,6.55,9.37,5.51z M12,3c-4.97,0-9,4.03-9,9s4.03,9,9,9s9-4.03,9-9c0-0.46-0.04-0.92-0.1-1.36 c-0.98,1.37-2.58,2.26-4.4,2.26c-2.98,0-5.4-2.42-5.4-5.4c0-1.81,0.89-3.42,2.26-4.4C12.92,3.04,12.46,3,12,3L12,3z" />
                 </svg>
-            }
-        } else {
-            html! {
+            },
+            ColourScheme::Light => html! {
                 <svg viewBox="0 0 24 24" width="24" height="24">
                     <path fill="currentColor" d="M12,9c1.65,0,3,1.35,3,3s-1.35,3-3,3s-3-1.35-3-3S10.35,9,12,9 M12,7c-2.76,0-5,2.24-5,5s2.24,5,5,5s5-2.24,5-5 S14.76,7,12,7L12,7z M2,13l2,0c0.55,0,1-0.45,1-1s-0.45-1-1-1l-2,0c-0.55,0-1,0.45-1,1S1.45,13,2,13z M20,13l2,0c0.55,0,1-0.45,1-1 s-0.45-1-1-1l-2,0c-0.55,0-1,0.45-1,1S19.45,13,20,13z M11,2v2c0,0.55,0.45,1,1,1s1-0.45,1-1V2c0-0.55-0.45-1-1-1S11,1.45,11,2z M11,20v2c0,0.55,0.45,1,1,1s1-0.45,1-1v-2c0-0.55-0.45-1-1-1C11.45,19,11,19.45,11,20z M5.99,4.58c-0.39-0.39-1.03-0.39-1.41,0 c-0.39,0.39-0.39,1.03,0,1.41l1.06,1.06c0.39,0.39,1.03,0.39,1.41,0s0.39-1.03,0-1.41L5.99,4.58z M18.36,16.95 c-0.39-0.39-1.03-0.39-1.41,0c-0.39,0.39-0.39,1.03,0,1.41l1.06,1.06c0.39,0.39,1.03,0.39,1.41,0c0.39-0.39,0.39-1.03,0-1.41 L18.36,16.95z M19.42,5.99c0.39-0.39,0.39-1.03,0-1.41c-0.39-0.39-1.03-0.39-1.41,0l-1.06,1.06c-0.39,0.39-0.39,1.03,0,1.41 s1.03,0.39,1.41,0L19.42,5.99z M7.05,18.36c0.39-0.39,0.39-1.03,0-1.41c-0.39-0.39-1.03-0.39-1.41,0l-1.06,1.06 c-0.39,0.39-0.39,1.03,0,1.41s1.03,0.39,1.41,0L7.05,18.36z" />
                 </svg>
-            }
+            },
+            ColourScheme::System => html! {
+                <svg viewBox="0 0 24 24" width="24" height="24">
+                    <path fill="currentColor" d="M10.85 12.65h2.3L12 9l-1.15 3.65zM20 8.69V4h-4.69L12 .69 8.69 4H4v4.69L.69 12 4 15.31V20h4.69L12 23.31 15.31 20H20v-4.69L23.31 12 20 8.69zM14.3 16l-.7-2h-3.2l-.7 2H7.8L11 7h2l3.2 9h-1.9z" />
+                </svg>
+            },
         };
 
         let onclick = ctx.link().callback(|event: MouseEvent| {
             if event.detail() == 0 {
                 ModelMsg::DoNothing
             } else {
-                ModelMsg::ToggleDarkMode
+                ModelMsg::CycleColourScheme
+            }
+        });
+
+        let help_onclick = ctx.link().callback(|event: MouseEvent| {
+            if event.detail() == 0 {
+                ModelMsg::DoNothing
+            } else {
+                ModelMsg::ShowHelp
+            }
+        });
+        let on_help_close = ctx.link().callback(ModelMsg::CloseHelp);
+
+        let stats_onclick = ctx.link().callback(|event: MouseEvent| {
+            if event.detail() == 0 {
+                ModelMsg::DoNothing
+            } else {
+                ModelMsg::ShowStats
+            }
+        });
+        let on_stats_close = ctx.link().callback(|()| ModelMsg::CloseStats);
+
+        let export_onclick = ctx.link().callback(|event: MouseEvent| {
+            if event.detail() == 0 {
+                ModelMsg::DoNothing
+            } else {
+                ModelMsg::ExportGame
+            }
+        });
+
+        let share_onclick = ctx.link().callback(|event: MouseEvent| {
+            if event.detail() == 0 {
+                ModelMsg::DoNothing
+            } else {
+                ModelMsg::ShareGame
             }
         });
 
+        let analysis_onclick = ctx.link().callback(|event: MouseEvent| {
+            if event.detail() == 0 {
+                ModelMsg::DoNothing
+            } else {
+                ModelMsg::ToggleAnalysis
+            }
+        });
+        let analysis_label = if self.analysis.is_some() {
+            "Hide the analysis"
+        } else {
+            "Show the analysis"
+        };
+
+        let motion_onclick = ctx.link().callback(|event: MouseEvent| {
+            if event.detail() == 0 {
+                ModelMsg::DoNothing
+            } else {
+                ModelMsg::CycleMotion
+            }
+        });
+
+        let motion_icon: Html = match self.motion {
+            Motion::Full => html! {
+                <svg viewBox="0 0 24 24" width="24" height="24">
+                    <circle fill="currentColor" cx="16" cy="12" r="5" />
+                    <circle fill="currentColor" opacity="0.6" cx="8.5" cy="12" r="3.5" />
+                    <circle fill="currentColor" opacity="0.3" cx="3" cy="12" r="2" />
+                </svg>
+            },
+            Motion::Reduced => html! {
+                <svg viewBox="0 0 24 24" width="24" height="24">
+                    <circle fill="currentColor" cx="12" cy="12" r="5" />
+                </svg>
+            },
+            Motion::System => html! {
+                <svg viewBox="0 0 24 24" width="24" height="24">
+                    <path fill="currentColor" d="M10.85 12.65h2.3L12 9l-1.15 3.65zM20 8.69V4h-4.69L12 .69 8.69 4H4v4.69L.69 12 4 15.31V20h4.69L12 23.31 15.31 20H20v-4.69L23.31 12 20 8.69zM14.3 16l-.7-2h-3.2l-.7 2H7.8L11 7h2l3.2 9h-1.9z" />
+                </svg>
+            },
+        };
+
+        let mute_onclick = ctx.link().callback(|event: MouseEvent| {
+            if event.detail() == 0 {
+                ModelMsg::DoNothing
+            } else {
+                ModelMsg::ToggleMute
+            }
+        });
+
+        let mute_icon: Html = if self.muted {
+            html! {
+                <svg viewBox="0 0 24 24" width="24" height="24">
+                    <path fill="currentColor" d="M16.5 12c0-1.77-1.02-3.29-2.5-4.03v2.21l2.45 2.45c.03-.2.05-.41.05-.63zm2.5 0c0 .94-.2 1.82-.54 2.64l1.51 1.51C20.63 14.91 21 13.5 21 12c0-4.28-2.99-7.86-7-8.77v2.06c2.89.86 5 3.54 5 6.71zM4.27 3L3 4.27 7.73 9H3v6h4l5 5v-6.73l4.25 4.25c-.67.52-1.42.93-2.25 1.18v2.06c1.38-.31 2.63-.95 3.69-1.81L19.73 21 21 19.73l-9-9L4.27 3zM12 4L9.91 6.09 12 8.18V4z" />
+                </svg>
+            }
+        } else {
+            html! {
+                <svg viewBox="0 0 24 24" width="24" height="24">
+                    <path fill="currentColor" d="M3 9v6h4l5 5V4L7 9H3zm13.5 3c0-1.77-1.02-3.29-2.5-4.03v8.05c1.48-.73 2.5-2.25 2.5-4.02zM14 3.23v2.06c2.89.86 5 3.54 5 6.71s-2.11 5.85-5 6.71v2.06c4.01-.91 7-4.49 7-8.77s-2.99-7.86-7-8.77z" />
+                </svg>
+            }
+        };
+
         let bad_guess = self.bad_guess.replace(false) && !self.guessed_correct;
 
         if bad_guess {
@@ -309,21 +926,78 @@ impl Component for Model {
                     <div class="main-title">{ "Wordle" }</div>
                     <div class="subtitle">{ "by Dyson" }</div>
                 </div>
+                // The game can be analysed, shared, and exported once the last guess has been revealed
+                if self.is_finished() && !self.board.is_locked() {
+                    <div>
+                        <button class="header-button" onclick={analysis_onclick} aria-label={analysis_label} title={analysis_label} aria-pressed={self.analysis.is_some().to_string()}>
+                            <svg viewBox="0 0 24 24" width="24" height="24">
+                                <path fill="currentColor" d="M5 9.2h3V19H5zM10.6 5h2.8v14h-2.8zm5.6 8H19v6h-2.8z" />
+                            </svg>
+                        </button>
+                    </div>
+                    <div>
+                        <button class="header-button" onclick={share_onclick} aria-label="Copy the result to share" title="Copy the result to share">
+                            <svg viewBox="0 0 24 24" width="24" height="24">
+                                <path fill="currentColor" d="M18 16.1c-.8 0-1.4.3-2 .8l-7.1-4.2c.1-.2.1-.5.1-.7s0-.5-.1-.7L16 7.2c.5.5 1.2.8 2 .8 1.7 0 3-1.3 3-3s-1.3-3-3-3-3 1.3-3 3c0 .2 0 .5.1.7L8 9.8C7.5 9.3 6.8 9 6 9c-1.7 0-3 1.3-3 3s1.3 3 3 3c.8 0 1.5-.3 2-.8l7.1 4.2c-.1.2-.1.4-.1.6 0 1.6 1.3 2.9 2.9 2.9s2.9-1.3 2.9-2.9-1.2-2.9-2.8-2.9z" />
+                            </svg>
+                        </button>
+                    </div>
+                    <div>
+                        <button class="header-button" onclick={export_onclick} aria-label="Export the board" title="Export the board">
+                            <svg viewBox="0 0 24 24" width="24" height="24">
+                                <path fill="currentColor" d="M19 9h-4V3H9v6H5l7 7 7-7zM5 18v2h14v-2H5z" />
+                            </svg>
+                        </button>
+                    </div>
+                }
+                <div>
+                    <button class="header-button" onclick={stats_onclick} aria-label="Show the statistics" title="Show the statistics">
+                        <svg viewBox="0 0 24 24" width="24" height="24">
+                            <path fill="currentColor" d="M16 11V3H8v6H2v12h20V11h-6zm-6-6h4v14h-4V5zm-6 6h4v8H4v-8zm16 8h-4v-6h4v6z" />
+                        </svg>
+                    </button>
+                </div>
+                <div>
+                    <button class="header-button" onclick={help_onclick}>
+                        <svg viewBox="0 0 24 24" width="24" height="24">
+                            <path fill="currentColor" d="M11 18h2v-2h-2v2zm1-16C6.48 2 2 6.48 2 12s4.48 10 10 10 10-4.48 10-10S17.52 2 12 2zm0 18c-4.41 0-8-3.59-8-8s3.59-8 8-8 8 3.59 8 8-3.59 8-8 8zm0-14c-2.21 0-4 1.79-4 4h2c0-1.1.9-2 2-2s2 .9 2 2c0 2-3 1.75-3 5h2c0-2.25 3-2.5 3-5 0-2.21-1.79-4-4-4z" />
+                        </svg>
+                    </button>
+                </div>
+                <div>
+                    <button class="header-button" onclick={mute_onclick}>
+                        {mute_icon}
+                    </button>
+                </div>
+                <div>
+                    <button class="header-button" onclick={motion_onclick} aria-label={motion::label(self.motion)} title={motion::label(self.motion)}>
+                        {motion_icon}
+                    </button>
+                </div>
                 <div>
-                    <button class="dark-mode-button" {onclick}>
+                    <button class="header-button" {onclick} aria-label={self.colour_scheme.label()} title={self.colour_scheme.label()}>
                         {button_icon}
                     </button>
                 </div>
             </header>
             <div class="game">
                 <div class="board-container">
-                    <BoardComp guesses={self.guesses.clone()} current_guess={self.current_guess.clone()} {bad_guess} />
+                    <BoardComp guesses={self.guesses.clone()} current_guess={self.board.current_guess().to_vec()} {bad_guess} analysis={self.analysis.clone()} />
+                </div>
+                <KeyboardComp map={self.map} counts={self.counts.clone()} />
+                <div class="screen-reader-only" aria-live="polite">
+                    { self.guesses.last().map(describe_guess).unwrap_or_default() }
                 </div>
-                <KeyboardComp map={self.map.clone()} />
                 if self.show_correct_guess {
-                    <ShowCorrectGuess word={self.game.word.clone()} />
+                    <ShowCorrectGuess word={self.game.word.reveal_for_display().clone()} />
                 }
             </div>
+            if self.show_help {
+                <HelpModal on_close={on_help_close} />
+            }
+            if self.show_stats {
+                <StatsModal stats={self.stats} on_close={on_stats_close} />
+            }
             </>
         }
     }
@@ -336,18 +1010,51 @@ impl Component for Model {
     /// [`KeyboardEvent`](https://docs.rs/web-sys/0.3.60/web_sys/struct.KeyboardEvent.html)s
     /// and update the model accordingly when the user types on their keyboard.
     ///
-    /// See [`Model::kbd_listener`].
+    /// We also set up listeners for
+    /// [`TouchEvent`](https://docs.rs/web-sys/0.3.60/web_sys/struct.TouchEvent.html)s so that
+    /// the user can swipe left on the board to delete a letter.
+    ///
+    /// While an input method editor (IME) is composing text, we ignore keyboard events, since the
+    /// keys are part of the composition rather than letters. We listen for the end of the
+    /// composition instead, and add the composed letters all at once.
+    ///
+    /// We listen for changes to the system colour scheme and reduced motion preferences, so that
+    /// the page can follow them live when the [`ColourScheme`] or [`Motion`] is `System`.
+    ///
+    /// Finally, we listen for changes to the visual viewport, so that the page always fits the
+    /// part of the screen that the user can see.
+    ///
+    /// See [`Model::kbd_listener`], [`Model::touch_listeners`],
+    /// [`Model::composition_listeners`], [`Model::colour_scheme_listener`],
+    /// [`Model::motion_listener`], and [`Model::viewport_listener`].
     fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
         if !first_render {
             return;
         }
 
-        let callback = ctx.link().callback(|event: KeyboardEvent| {
+        // Some browsers send the keydown event for the key that ends a composition after the
+        // compositionend event, so we can't rely on `isComposing` alone
+        let composing = Rc::new(Cell::new(false));
+
+        let keydown_composing = Rc::clone(&composing);
+        let callback = ctx.link().callback(move |event: KeyboardEvent| {
             let key = event.key().to_ascii_lowercase();
-            if key.len() == 1
-                && ALPHABET.contains(&key.to_ascii_uppercase().chars().next().unwrap())
-            {
-                Self::Message::AddToCurrentGuess(key.chars().next().unwrap())
+
+            if keydown_composing.get() || event.is_composing() || key == "process" {
+                return Self::Message::DoNothing;
+            }
+
+            let mut chars = key.chars();
+
+            // Mobile keyboards and input methods can send letters with diacritics or full-width
+            // letters, so we normalize single characters before checking them
+            let letter = match (chars.next(), chars.next()) {
+                (Some(c), None) => normalize_letter(c, &KEY_NORMALIZATION),
+                _ => None,
+            };
+
+            if let Some(letter) = letter.filter(|letter| ALPHABET.contains(letter)) {
+                Self::Message::AddToCurrentGuess(letter.to_ascii_lowercase())
             } else if key == "enter" {
                 Self::Message::SendEnter
             } else if key == "backspace" {
@@ -365,10 +1072,64 @@ impl Component for Model {
         });
 
         self.kbd_listener.replace(listener);
+
+        let start_composing = Rc::clone(&composing);
+        let composition_end_callback = ctx.link().callback(|event: CompositionEvent| {
+            Self::Message::AddComposedText(event.data().unwrap_or_default())
+        });
+
+        self.composition_listeners = vec![
+            EventListener::new(&document, "compositionstart", move |_| {
+                start_composing.set(true);
+            }),
+            EventListener::new(&document, "compositionend", move |event| {
+                composing.set(false);
+                let event = event.dyn_ref::<CompositionEvent>().unwrap_throw();
+                composition_end_callback.emit(event.clone());
+            }),
+        ];
+
+        let touch_start_callback = ctx.link().batch_callback(|event: TouchEvent| {
+            board_touch_coords(&event).map(|(x, y)| Self::Message::BoardTouchStart(x, y))
+        });
+        let touch_end_callback = ctx.link().batch_callback(|event: TouchEvent| {
+            board_touch_coords(&event).map(|(x, y)| Self::Message::BoardTouchEnd(x, y))
+        });
+
+        self.touch_listeners = vec![
+            EventListener::new(&document, "touchstart", move |event| {
+                let event = event.dyn_ref::<TouchEvent>().unwrap_throw();
+                touch_start_callback.emit(event.clone());
+            }),
+            EventListener::new(&document, "touchend", move |event| {
+                let event = event.dyn_ref::<TouchEvent>().unwrap_throw();
+                touch_end_callback.emit(event.clone());
+            }),
+        ];
+
+        let link = ctx.link().clone();
+        self.colour_scheme_listener = prefers_dark_query().map(|query| {
+            EventListener::new(&query, "change", move |_| {
+                link.send_message(Self::Message::SystemColourSchemeChanged);
+            })
+        });
+
+        let link = ctx.link().clone();
+        self.motion_listener = motion::prefers_reduced_motion_query().map(|query| {
+            EventListener::new(&query, "change", move |_| {
+                link.send_message(Self::Message::SystemMotionChanged);
+            })
+        });
+
+        self.viewport_listener = viewport::listen_for_viewport_changes();
     }
 }
 
 /// Run the [`yew`](https://docs.rs/yew/0.19.3/yew/) app.
+///
+/// We install a panic hook first, so that a crash shows a message rather than a blank page. See
+/// [`recovery`].
 fn main() {
+    recovery::install_panic_hook();
     yew::start_app::<Model>();
 }
@@ -0,0 +1,48 @@
+//! This module handles the animations setting, which can be full, reduced, or follow the system's
+//! `prefers-reduced-motion` preference. See [`Motion`].
+//!
+//! When motion is reduced, we put [`Motion::REDUCED_CSS_CLASS`] on the body, and the stylesheet
+//! replaces the tile flips, row shake, and bounce with instant changes and a brief colour pulse.
+
+use gloo_utils::{body, window};
+use web_sys::MediaQueryList;
+use wordle::theme::Motion;
+
+/// The media query that matches when the system prefers reduced motion.
+const PREFERS_REDUCED_MOTION_QUERY: &str = "(prefers-reduced-motion: reduce)";
+
+/// Get a description of this motion preference for the header button.
+pub fn label(motion: Motion) -> &'static str {
+    match motion {
+        Motion::System => "Animations: follow system",
+        Motion::Full => "Animations: on",
+        Motion::Reduced => "Animations: reduced",
+    }
+}
+
+/// Check whether the system prefers reduced motion.
+pub fn prefers_reduced_motion() -> bool {
+    prefers_reduced_motion_query().is_some_and(|query| query.matches())
+}
+
+/// Set or remove the reduced motion class on the body of the HTML to match this preference.
+pub fn apply(motion: Motion) -> Option<()> {
+    body()
+        .class_list()
+        .toggle_with_force(
+            Motion::REDUCED_CSS_CLASS,
+            motion.is_reduced(prefers_reduced_motion()),
+        )
+        .ok()
+        .map(|_| ())
+}
+
+/// Get the media query list for whether the system prefers reduced motion.
+///
+/// Listen for its `change` event to find out when the system preference changes.
+pub fn prefers_reduced_motion_query() -> Option<MediaQueryList> {
+    window()
+        .match_media(PREFERS_REDUCED_MOTION_QUERY)
+        .ok()
+        .flatten()
+}
@@ -0,0 +1,174 @@
+//! This module handles recovering from a crash, so that a panic doesn't leave a blank page.
+//!
+//! While a game is in progress, it's saved to `localStorage` as a replay [`Script`] after every
+//! guess. If the app panics, [`install_panic_hook`] replaces the page with a message that offers to
+//! restore that game, along with a diagnostic that can be copied into a bug report.
+
+use crate::{storage_get, storage_get_bool, storage_remove, storage_set, storage_set_bool};
+use gloo_events::EventListener;
+use gloo_utils::{body, document, window};
+use std::panic::{self, PanicHookInfo};
+use web_sys::{wasm_bindgen::JsCast, Element, HtmlTextAreaElement};
+use wordle::{
+    replay::{Script, ScriptGuess},
+    Game, Word,
+};
+
+/// The `localStorage` key for the [`Script`] of the game in progress.
+const SAVED_GAME_KEY: &str = "wordleSavedGame";
+
+/// The `localStorage` key for whether the saved game should be restored on the next startup.
+///
+/// This is only set by the button in the crash message, so a normal reload still starts a new
+/// game.
+const RESTORE_KEY: &str = "wordleRestoreGame";
+
+/// Save the given game, which has had the given guesses, so that it can be restored after a
+/// crash.
+///
+/// Finished games can't be continued, so the saved game is removed instead.
+pub fn save_game(game: &Game, guesses: &[Word], finished: bool) {
+    if finished {
+        storage_remove(SAVED_GAME_KEY);
+        return;
+    }
+
+    let mut script = Script::for_game(game);
+    script.guesses = guesses
+        .iter()
+        .map(|word| ScriptGuess {
+            millis: 0,
+            guess: word.iter().map(|letter| letter.letter).collect(),
+        })
+        .collect();
+
+    storage_set(SAVED_GAME_KEY, &script.to_string());
+}
+
+/// Get the saved game and its guesses, if the player asked to restore it from the crash message.
+///
+/// The request is only honoured once, so that another crash while restoring doesn't trap the
+/// player in a loop.
+pub fn take_game_to_restore() -> Option<(Game, Vec<Word>)> {
+    if storage_get_bool(RESTORE_KEY) != Some(true) {
+        return None;
+    }
+    storage_remove(RESTORE_KEY);
+
+    let script = Script::parse(&storage_get(SAVED_GAME_KEY)?).ok()?;
+    let (game, results) = Game::replay_script(&script).ok()?;
+
+    Some((game, results.into_iter().filter_map(Result::ok).collect()))
+}
+
+/// Replace the default panic hook with one that also shows the crash message.
+///
+/// The default hook is still called first, so the panic is logged to the console as normal.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        // If we can't even show the message, there's nothing more we can do
+        let _ = show_crash_message(&diagnostic(info));
+    }));
+}
+
+/// Get the diagnostic to show in the crash message, which describes the panic and the saved game.
+///
+/// The saved game's `answer` line is redacted, since the diagnostic is meant to be shared and the
+/// game isn't finished.
+fn diagnostic(info: &PanicHookInfo) -> String {
+    let user_agent = window().navigator().user_agent().unwrap_or_default();
+    let saved_game = storage_get(SAVED_GAME_KEY).map_or_else(
+        || "No saved game\n".to_string(),
+        |script| {
+            script
+                .lines()
+                .map(|line| {
+                    if line.starts_with("answer ") {
+                        "answer <redacted>\n".to_string()
+                    } else {
+                        format!("{line}\n")
+                    }
+                })
+                .collect()
+        },
+    );
+
+    format!(
+        "wordle-web {}\n{info}\n{user_agent}\n\n{saved_game}",
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// Create an element with the given tag, class, and text.
+fn element(tag: &str, class: &str, text: &str) -> Option<Element> {
+    let element = document().create_element(tag).ok()?;
+    if !class.is_empty() {
+        element.set_class_name(class);
+    }
+    element.set_text_content(Some(text));
+    Some(element)
+}
+
+/// Create a button that stores whether to restore the saved game, and then reloads the page.
+fn reload_button(text: &str, restore: bool) -> Option<Element> {
+    let button = element("button", "modal-close-button", text)?;
+
+    EventListener::new(&button, "click", move |_| {
+        if restore {
+            storage_set_bool(RESTORE_KEY, true);
+        } else {
+            storage_remove(SAVED_GAME_KEY);
+        }
+        let _ = window().location().reload();
+    })
+    .forget();
+
+    Some(button)
+}
+
+/// Replace the whole page with a message saying that the app has crashed.
+///
+/// Yew can't render anything after a panic, so this builds the message with the DOM directly. It
+/// reuses the styles of the [`HelpModal`](crate::help::HelpModal).
+fn show_crash_message(diagnostic: &str) -> Option<()> {
+    let container = element("div", "modal-container", "")?;
+    let modal = element("div", "modal crash", "")?;
+
+    let textarea: HtmlTextAreaElement = element("textarea", "", diagnostic)?.dyn_into().ok()?;
+    textarea.set_read_only(true);
+    textarea.set_attribute("aria-label", "Diagnostic").ok()?;
+    {
+        let textarea = textarea.clone();
+        EventListener::new(&textarea.clone(), "focus", move |_| textarea.select()).forget();
+    }
+
+    let footer = element("div", "modal-footer", "")?;
+    if storage_get(SAVED_GAME_KEY).is_some() {
+        let restore = reload_button("Restore game", true)?;
+        footer.append_child(&restore).ok()?;
+    }
+    let new_game = reload_button("New game", false)?;
+    footer.append_child(&new_game).ok()?;
+
+    let heading = element("h2", "", "Something went wrong")?;
+    let explanation = element(
+        "p",
+        "",
+        "Sorry, the game has crashed. If you report this bug, please include the text below.",
+    )?;
+
+    modal.append_child(&heading).ok()?;
+    modal.append_child(&explanation).ok()?;
+    modal.append_child(&textarea).ok()?;
+    modal.append_child(&footer).ok()?;
+    container.append_child(&modal).ok()?;
+
+    let body = body();
+    body.set_inner_html("");
+    body.append_child(&container).ok()?;
+
+    Some(())
+}
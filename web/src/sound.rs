@@ -0,0 +1,86 @@
+//! This module handles the sound effects, which are synthesised with the Web Audio API.
+
+use web_sys::{AudioContext, AudioContextState, OscillatorType};
+
+/// A player for the game's sound effects.
+///
+/// Browsers only allow audio to start after the user has interacted with the page, so the
+/// [`AudioContext`] is only created when the first sound is played, which will always be in
+/// response to user input.
+#[derive(Default)]
+pub struct Sounds {
+    /// The audio context, if it has been created yet.
+    context: Option<AudioContext>,
+}
+
+impl Sounds {
+    /// Get the audio context, creating it or resuming it if needed.
+    fn context(&mut self) -> Option<&AudioContext> {
+        if self.context.is_none() {
+            self.context = AudioContext::new().ok();
+        }
+
+        let context = self.context.as_ref()?;
+        if context.state() == AudioContextState::Suspended {
+            let _ = context.resume();
+        }
+
+        Some(context)
+    }
+
+    /// Play a single tone after `delay` seconds, lasting for `duration` seconds.
+    fn play_tone(
+        &mut self,
+        frequency: f32,
+        delay: f64,
+        duration: f64,
+        oscillator_type: OscillatorType,
+        volume: f32,
+    ) -> Option<()> {
+        let context = self.context()?;
+        let start = context.current_time() + delay;
+
+        let oscillator = context.create_oscillator().ok()?;
+        oscillator.set_type(oscillator_type);
+        oscillator.frequency().set_value(frequency);
+
+        // Fade out quickly rather than stopping dead, which would click
+        let gain = context.create_gain().ok()?;
+        gain.gain().set_value_at_time(volume, start).ok()?;
+        gain.gain()
+            .exponential_ramp_to_value_at_time(0.001, start + duration)
+            .ok()?;
+
+        oscillator.connect_with_audio_node(&gain).ok()?;
+        gain.connect_with_audio_node(&context.destination()).ok()?;
+
+        oscillator.start_with_when(start).ok()?;
+        oscillator.stop_with_when(start + duration).ok()?;
+
+        Some(())
+    }
+
+    /// Play a short click for a key press.
+    pub fn key_click(&mut self) {
+        self.play_tone(900., 0., 0.04, OscillatorType::Square, 0.05);
+    }
+
+    /// Play a low buzz for an invalid guess.
+    pub fn invalid_guess(&mut self) {
+        self.play_tone(110., 0., 0.25, OscillatorType::Sawtooth, 0.15);
+    }
+
+    /// Play a rising jingle for a correct guess.
+    pub fn win(&mut self) {
+        // C5, E5, G5, C6
+        for (i, frequency) in [523.25, 659.25, 783.99, 1046.5].into_iter().enumerate() {
+            self.play_tone(
+                frequency,
+                i as f64 * 0.12,
+                0.3,
+                OscillatorType::Triangle,
+                0.2,
+            );
+        }
+    }
+}
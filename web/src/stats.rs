@@ -0,0 +1,100 @@
+//! This module handles the player's stats, which are saved in `localStorage` between games, and
+//! the modal that shows them.
+
+use crate::{storage_get, storage_set};
+use web_sys::MouseEvent;
+use wordle::{
+    session::{BoardSummary, SessionStats},
+    Word,
+};
+use yew::{function_component, html, Callback, Html, Properties};
+
+/// The `localStorage` key for the [`SessionStats`], in the text format of [`SessionStats::parse`].
+const STATS_KEY: &str = "wordleStats";
+
+/// Load the saved stats, or get empty stats if there aren't any.
+pub fn load() -> SessionStats {
+    storage_get(STATS_KEY).map_or_else(SessionStats::default, |text| SessionStats::parse(&text))
+}
+
+/// Add the finished game with the given guesses to the saved stats, and return the new stats.
+pub fn record_game(guesses: &[Word]) -> SessionStats {
+    let mut stats = load();
+    stats.record(&BoardSummary::from_history(guesses));
+    storage_set(STATS_KEY, &stats.to_string());
+    stats
+}
+
+/// The props for [`StatsModal`].
+#[derive(Clone, PartialEq, Properties)]
+pub struct StatsModalProps {
+    /// The stats to show.
+    pub stats: SessionStats,
+
+    /// The callback to run when the modal is closed.
+    pub on_close: Callback<()>,
+}
+
+/// A modal that shows how many games the player has played and won, their streaks, and a bar
+/// chart of how many guesses each win took.
+#[function_component(StatsModal)]
+pub fn stats_modal(props: &StatsModalProps) -> Html {
+    let stats = &props.stats;
+    let onclick = props.on_close.reform(|_: MouseEvent| ());
+
+    let win_rate = stats.win_rate().unwrap_or_default() * 100.0;
+    let most = stats
+        .distribution
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    let totals = [
+        ("Played", stats.boards.to_string()),
+        ("Win %", format!("{win_rate:.0}")),
+        ("Current streak", stats.current_streak.to_string()),
+        ("Best streak", stats.best_streak.to_string()),
+    ];
+
+    let totals: Html = totals
+        .into_iter()
+        .map(|(label, value)| {
+            html! {
+                <div class="stat">
+                    <div class="stat-value">{ value }</div>
+                    <div class="stat-label">{ label }</div>
+                </div>
+            }
+        })
+        .collect();
+
+    let distribution: Html = (1..)
+        .zip(stats.distribution)
+        .map(|(guesses, count): (usize, u64)| {
+            let style = format!("width: {}%;", (count * 100 / most).max(7));
+            html! {
+                <div class="distribution-row">
+                    <div class="distribution-guesses">{ guesses }</div>
+                    <div class="distribution-bar" {style}>{ count }</div>
+                </div>
+            }
+        })
+        .collect();
+
+    html! {
+        <div class="modal-container">
+            <div class="modal stats-modal">
+                <h2>{ "Statistics" }</h2>
+                <div class="stats-totals">{totals}</div>
+                <h3>{ "Guess distribution" }</h3>
+                <div class="distribution">{distribution}</div>
+                <div class="modal-footer">
+                    <div />
+                    <button class="modal-close-button" {onclick}>{ "Close" }</button>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,51 @@
+//! This module handles keeping the page the same size as the part of the screen that the user can
+//! actually see.
+//!
+//! Mobile browsers show and hide their toolbars as the user scrolls, and shrink the visible area
+//! when an on-screen keyboard appears, but `100vh` doesn't follow either of these. We listen to
+//! the [`VisualViewport`](https://developer.mozilla.org/en-US/docs/Web/API/VisualViewport) and
+//! copy its height into the `--app-height` CSS variable, which the stylesheet uses to size the
+//! page. Without the API, the stylesheet falls back to `100dvh` or `100vh`.
+
+use gloo_events::EventListener;
+use gloo_utils::{document, window};
+use web_sys::{wasm_bindgen::JsCast, HtmlElement};
+
+/// The name of the CSS variable that holds the height of the visible part of the page.
+const APP_HEIGHT_VARIABLE: &str = "--app-height";
+
+/// Copy the height of the visual viewport into [`APP_HEIGHT_VARIABLE`].
+///
+/// We also scroll back to the top, because some browsers scroll the page up to make room for an
+/// on-screen keyboard, which would push the header out of view.
+pub fn update_app_height() {
+    let Some(viewport) = window().visual_viewport() else {
+        return;
+    };
+    let Some(root) = document()
+        .document_element()
+        .and_then(|element| element.dyn_into::<HtmlElement>().ok())
+    else {
+        return;
+    };
+
+    let height = viewport.height();
+    if height.is_finite() && height > 0. {
+        let _ = root
+            .style()
+            .set_property(APP_HEIGHT_VARIABLE, &format!("{height}px"));
+        window().scroll_to_with_x_and_y(0., 0.);
+    }
+}
+
+/// Set [`APP_HEIGHT_VARIABLE`] now, and return a listener that keeps it up to date whenever the
+/// visual viewport is resized, or [`None`] if the browser doesn't support the API.
+///
+/// The listener must be kept alive for as long as the page should follow the viewport.
+pub fn listen_for_viewport_changes() -> Option<EventListener> {
+    update_app_height();
+
+    window()
+        .visual_viewport()
+        .map(|viewport| EventListener::new(&viewport, "resize", |_| update_app_height()))
+}